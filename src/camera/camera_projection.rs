@@ -0,0 +1,190 @@
+/// Camera Projection - Data-Oriented Programming (DOP) style
+///
+/// Projection modes are plain data; the functions below turn them into
+/// clip-space matrices. Both modes map depth into wgpu's 0..1 range so the
+/// rest of the pipeline (frustum extraction, culling, screen mapping) does
+/// not need to know which one is active.
+
+use cgmath::{InnerSpace, Matrix4, Rad, Vector3, Vector4};
+use serde::{Deserialize, Serialize};
+
+/// Converts cgmath's OpenGL-style clip space (z in -1..1) to wgpu (z in 0..1)
+#[rustfmt::skip]
+pub const OPENGL_TO_WGPU_MATRIX: Matrix4<f32> = Matrix4::new(
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 0.5, 0.0,
+    0.0, 0.0, 0.5, 1.0,
+);
+
+/// Projection mode for a camera
+///
+/// `Perspective` is the normal first-person view. `Orthographic` is used for
+/// isometric/strategy views, map thumbnails and UI rendered in 3D.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CameraProjection {
+    /// Perspective projection with a vertical field of view in radians
+    Perspective { fov_y_radians: f32 },
+    /// Orthographic projection covering `height` world units vertically;
+    /// the width follows from the aspect ratio
+    Orthographic { height: f32 },
+}
+
+impl Default for CameraProjection {
+    fn default() -> Self {
+        CameraProjection::Perspective {
+            fov_y_radians: std::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
+/// Build a projection matrix for the given mode
+/// Pure function - returns a wgpu-ready matrix (depth 0..1)
+pub fn build_projection_matrix_for(
+    projection: &CameraProjection,
+    aspect_ratio: f32,
+    znear: f32,
+    zfar: f32,
+) -> Matrix4<f32> {
+    let aspect_ratio = if aspect_ratio > 0.0 { aspect_ratio } else { 1.0 };
+
+    let proj = match *projection {
+        CameraProjection::Perspective { fov_y_radians } => {
+            cgmath::perspective(Rad(fov_y_radians), aspect_ratio, znear, zfar)
+        }
+        CameraProjection::Orthographic { height } => {
+            let half_h = height.abs().max(f32::EPSILON) * 0.5;
+            let half_w = half_h * aspect_ratio;
+            cgmath::ortho(-half_w, half_w, -half_h, half_h, znear, zfar)
+        }
+    };
+
+    OPENGL_TO_WGPU_MATRIX * proj
+}
+
+/// Orthographic projection framing a square region seen from straight above
+/// Pure function - used for map thumbnails (`side` world units across)
+pub fn top_down_map_projection(side: f32) -> CameraProjection {
+    CameraProjection::Orthographic { height: side }
+}
+
+/// Project a world position to screen pixels (origin top-left)
+/// Pure function - works for any projection since it only uses the combined matrix
+///
+/// Returns `None` when the point lies outside the depth range or behind the camera.
+pub fn world_to_screen(
+    view_projection: &Matrix4<f32>,
+    world_pos: [f32; 3],
+    screen_width: f32,
+    screen_height: f32,
+) -> Option<[f32; 2]> {
+    let clip = view_projection * Vector4::new(world_pos[0], world_pos[1], world_pos[2], 1.0);
+    if clip.w <= f32::EPSILON {
+        return None;
+    }
+
+    let ndc_x = clip.x / clip.w;
+    let ndc_y = clip.y / clip.w;
+    let ndc_z = clip.z / clip.w;
+    if !(0.0..=1.0).contains(&ndc_z) {
+        return None;
+    }
+
+    Some([
+        (ndc_x * 0.5 + 0.5) * screen_width,
+        (1.0 - (ndc_y * 0.5 + 0.5)) * screen_height,
+    ])
+}
+
+/// A plane `dot(normal, p) + d = 0`; points with a positive distance are on the inside
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3<f32>,
+    pub d: f32,
+}
+
+/// Signed distance from a plane to a point (world units when the plane is normalized)
+pub fn plane_distance(plane: &Plane, point: [f32; 3]) -> f32 {
+    plane.normal.dot(Vector3::new(point[0], point[1], point[2])) + plane.d
+}
+
+/// Extract the six frustum planes from a view-projection matrix
+/// Pure function - Gribb-Hartmann, order: left, right, bottom, top, near, far
+///
+/// Expects wgpu's 0..1 depth range (as produced by `build_projection_matrix_for`),
+/// so the near plane is the z row alone rather than `w + z`. Planes are
+/// normalized and point inwards.
+pub fn frustum_planes_from_matrix(view_projection: &Matrix4<f32>) -> [Plane; 6] {
+    let m = view_projection;
+    let row = |i: usize| Vector4::new(m.x[i], m.y[i], m.z[i], m.w[i]);
+    let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+
+    [w + x, w - x, w + y, w - y, z, w - z].map(|v| {
+        let normal = Vector3::new(v.x, v.y, v.z);
+        let length = normal.magnitude();
+        if length > f32::EPSILON {
+            Plane {
+                normal: normal / length,
+                d: v.w / length,
+            }
+        } else {
+            Plane { normal, d: v.w }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orthographic_keeps_size_constant_with_depth() {
+        let proj = build_projection_matrix_for(
+            &CameraProjection::Orthographic { height: 10.0 },
+            1.0,
+            0.1,
+            100.0,
+        );
+
+        let near = world_to_screen(&proj, [5.0, 0.0, -1.0], 100.0, 100.0)
+            .expect("point should be visible");
+        let far = world_to_screen(&proj, [5.0, 0.0, -90.0], 100.0, 100.0)
+            .expect("point should be visible");
+
+        assert!((near[0] - 100.0).abs() < 1e-3);
+        assert!((far[0] - near[0]).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_perspective_rejects_points_behind_camera() {
+        let proj = build_projection_matrix_for(&CameraProjection::default(), 16.0 / 9.0, 0.1, 100.0);
+
+        assert!(world_to_screen(&proj, [0.0, 0.0, -10.0], 1280.0, 720.0).is_some());
+        assert!(world_to_screen(&proj, [0.0, 0.0, 10.0], 1280.0, 720.0).is_none());
+    }
+
+    #[test]
+    fn test_frustum_planes_follow_aspect_ratio() {
+        let inside = |aspect: f32, point: [f32; 3]| {
+            let projection = CameraProjection::default();
+            let proj = build_projection_matrix_for(&projection, aspect, 0.1, 100.0);
+            frustum_planes_from_matrix(&proj)
+                .iter()
+                .all(|plane| plane_distance(plane, point) >= 0.0)
+        };
+
+        // Half-height at z = -10 is ~4.14, so x = 6 only fits a wide viewport
+        assert!(!inside(1.0, [6.0, 0.0, -10.0]));
+        assert!(inside(2.0, [6.0, 0.0, -10.0]));
+        assert!(!inside(2.0, [0.0, 0.0, -0.05]));
+        assert!(!inside(2.0, [0.0, 0.0, -150.0]));
+
+        let proj = build_projection_matrix_for(&CameraProjection::default(), 1.5, 0.1, 100.0);
+        let planes = frustum_planes_from_matrix(&proj);
+        for plane in &planes {
+            assert!((plane.normal.magnitude() - 1.0).abs() < 1e-5);
+        }
+        // Normalized near plane gives true distances along the view axis
+        assert!((plane_distance(&planes[4], [0.0, 0.0, -10.0]) - 9.9).abs() < 1e-3);
+    }
+}
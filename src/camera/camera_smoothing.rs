@@ -0,0 +1,89 @@
+/// Camera Smoothing - Data-Oriented Programming (DOP) style
+///
+/// Interpolation and damping for follow and cinematic cameras. Like the rest
+/// of the camera operations these are pure functions that return a new
+/// `CameraData` instead of mutating the input.
+use super::camera_data::CameraData;
+use std::f32::consts::{PI, TAU};
+
+/// Signed difference `to - from` wrapped into `[-PI, PI)`
+/// Pure function - the shortest way round the circle
+pub fn shortest_angle_delta(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// Interpolate an angle along the shortest arc
+/// Pure function - never spins the long way round at the +-PI wrap
+pub fn lerp_angle(from: f32, to: f32, t: f32) -> f32 {
+    from + shortest_angle_delta(from, to) * t
+}
+
+/// Fraction of the remaining distance covered by exponential damping in `dt`
+/// Pure function - frame-rate independent and always in `[0, 1)`, so it cannot overshoot
+pub fn damping_factor(stiffness: f32, dt: f32) -> f32 {
+    if stiffness <= 0.0 || dt <= 0.0 {
+        return 0.0;
+    }
+    1.0 - (-stiffness * dt).exp()
+}
+
+/// Blend two cameras: position and pitch linearly, yaw along the shortest arc
+/// Pure function - every other field (projection, aspect, ...) comes from `current`
+pub fn camera_lerp(current: &CameraData, target: &CameraData, t: f32) -> CameraData {
+    let t = t.clamp(0.0, 1.0);
+    let mut camera = current.clone();
+
+    for axis in 0..3 {
+        camera.position[axis] += (target.position[axis] - current.position[axis]) * t;
+    }
+    camera.yaw_radians = lerp_angle(current.yaw_radians, target.yaw_radians, t);
+    camera.pitch_radians += (target.pitch_radians - current.pitch_radians) * t;
+
+    camera
+}
+
+/// Exponentially damp a camera toward a target position and yaw
+/// Pure function - higher `stiffness` (1/s) catches up faster; never overshoots
+pub fn camera_smooth_follow(
+    current: &CameraData,
+    target_pos: [f32; 3],
+    target_yaw: f32,
+    stiffness: f32,
+    dt: f32,
+) -> CameraData {
+    let t = damping_factor(stiffness, dt);
+    let mut camera = current.clone();
+
+    for (axis, target) in target_pos.iter().enumerate() {
+        camera.position[axis] += (target - current.position[axis]) * t;
+    }
+    camera.yaw_radians = lerp_angle(current.yaw_radians, target_yaw, t);
+
+    camera
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lerp_angle_takes_shortest_path_across_wrap() {
+        let from = PI - 0.1;
+        let to = -PI + 0.1;
+
+        assert!((shortest_angle_delta(from, to) - 0.2).abs() < 1e-5);
+        let halfway = lerp_angle(from, to, 0.5);
+        assert!((halfway - PI).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_damping_never_overshoots() {
+        let mut value = 0.0f32;
+        for _ in 0..120 {
+            value += (10.0 - value) * damping_factor(50.0, 1.0 / 60.0);
+            assert!(value <= 10.0);
+        }
+        assert!((value - 10.0).abs() < 1e-3);
+        assert_eq!(damping_factor(0.0, 1.0), 0.0);
+    }
+}
@@ -3,15 +3,30 @@
 /// This module follows pure DOP principles:
 /// - camera_data.rs: Pure data structures with NO methods
 /// - camera_operations.rs: Pure functions that operate on data
+/// - camera_projection.rs: Projection modes (perspective/orthographic)
+/// - camera_smoothing.rs: Interpolation and damping for follow cameras
 /// 
 /// Sprint 35: Full DOP conversion complete
 
 pub mod camera_data;
 pub mod camera_operations;
+pub mod camera_projection;
+pub mod camera_smoothing;
 
 // Re-export data structures
 pub use camera_data::{CameraData, CameraTransformBatch, CameraUniform};
 
+// Re-export projection modes
+pub use camera_projection::{
+    build_projection_matrix_for, frustum_planes_from_matrix, plane_distance,
+    top_down_map_projection, world_to_screen, CameraProjection, Plane,
+};
+
+// Re-export smoothing
+pub use camera_smoothing::{
+    camera_lerp, camera_smooth_follow, damping_factor, lerp_angle, shortest_angle_delta,
+};
+
 // Re-export all operations
 pub use camera_operations::{
     // Initialization
@@ -65,4 +80,10 @@ pub fn camera_resize(camera: &CameraData, width: u32, height: u32) -> CameraData
 // Calculate forward vector from camera data (compatibility function)
 pub fn calculate_forward_vector_from_camera(camera_data: &CameraData) -> cgmath::Vector3<f32> {
     calculate_forward_vector(camera_data.yaw_radians, camera_data.pitch_radians)
-}
\ No newline at end of file
+}
+
+// Frustum planes from the camera's current view and projection
+// Rebuilt from the camera each call, so aspect ratio changes are picked up
+pub fn extract_frustum_planes(camera: &CameraData) -> [Plane; 6] {
+    frustum_planes_from_matrix(&(build_projection_matrix(camera) * build_view_matrix(camera)))
+}
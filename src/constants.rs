@@ -22,9 +22,9 @@ pub mod core {
 pub mod blocks {
     // Core engine blocks (0-99)
     pub const AIR: u16 = 0;
-    pub const STONE: u16 = 1;
+    pub const GRASS: u16 = 1;
     pub const DIRT: u16 = 2;
-    pub const GRASS: u16 = 3;
+    pub const STONE: u16 = 3;
     pub const WOOD: u16 = 4;
     pub const SAND: u16 = 5;
     pub const WATER: u16 = 6;
@@ -158,6 +158,11 @@ pub mod terrain {
     /// Base terrain generation height: 6.4m × 10 voxels/m = 64 voxels
     pub const TERRAIN_THRESHOLD: i32 = 64;
     
+    /// Surface wave period and amplitude (voxels)
+    /// Integer-only so CPU and GPU produce bit-identical heights
+    pub const TERRAIN_WAVE_PERIOD: i32 = 126;
+    pub const TERRAIN_WAVE_AMPLITUDE: i32 = 5;
+    
     /// Terrain height limits (voxels)
     /// Range: 10m-200m × 10 voxels/m = 100-2000 voxels
     pub const MIN_HEIGHT: i32 = 100;
@@ -685,6 +690,8 @@ const SNOW_HEIGHT_TYPICAL_HIGH: i32 = {}i;
 
 // Terrain constants
 const TERRAIN_THRESHOLD: i32 = {}i;
+const TERRAIN_WAVE_PERIOD: i32 = {}i;
+const TERRAIN_WAVE_AMPLITUDE: i32 = {}i;
 const SEA_LEVEL: i32 = {}i;
 
 // World dimensions
@@ -749,6 +756,8 @@ const MAX_INDICES_PER_CHUNK: u32 = {}u;
         weather::SNOW_HEIGHT_TYPICAL_LOW,
         weather::SNOW_HEIGHT_TYPICAL_HIGH,
         terrain::TERRAIN_THRESHOLD,
+        terrain::TERRAIN_WAVE_PERIOD,
+        terrain::TERRAIN_WAVE_AMPLITUDE,
         terrain::SEA_LEVEL,
         core::MAX_WORLD_SIZE,
         256u32, // WORLD_HEIGHT (hardcoded for now)
@@ -0,0 +1,194 @@
+//! Logical input actions mapped to physical keys and mouse buttons
+//!
+//! Games query actions like `"jump"` instead of hardcoding `KeyCode::Space`.
+//! The binding table is serde-serializable so players can save and load keymaps.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// Physical input that triggers a binding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputTrigger {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+/// A trigger plus the modifier keys that must be held with it (e.g. Shift+Space)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputBinding {
+    pub trigger: InputTrigger,
+    #[serde(default)]
+    pub modifiers: Vec<KeyCode>,
+}
+
+impl InputBinding {
+    pub fn key(key: KeyCode) -> Self {
+        Self {
+            trigger: InputTrigger::Key(key),
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn mouse(button: MouseButton) -> Self {
+        Self {
+            trigger: InputTrigger::Mouse(button),
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// Require `modifier` to be held as well
+    pub fn with_modifier(mut self, modifier: KeyCode) -> Self {
+        if !self.modifiers.contains(&modifier) {
+            self.modifiers.push(modifier);
+        }
+        self
+    }
+
+    /// Same trigger, and `other`'s modifiers plus at least one more
+    ///
+    /// While both are held, the more specific chord wins (Shift+Space over Space).
+    pub fn is_more_specific_than(&self, other: &InputBinding) -> bool {
+        self.trigger == other.trigger
+            && self.modifiers.len() > other.modifiers.len()
+            && other.modifiers.iter().all(|m| self.modifiers.contains(m))
+    }
+}
+
+/// Bindings compare as the same combination regardless of modifier order
+impl PartialEq for InputBinding {
+    fn eq(&self, other: &Self) -> bool {
+        self.trigger == other.trigger
+            && self.modifiers.len() == other.modifiers.len()
+            && self.modifiers.iter().all(|m| other.modifiers.contains(m))
+    }
+}
+
+impl Eq for InputBinding {}
+
+/// Two actions bound to the same combination
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("binding {binding:?} for '{action}' is already used by '{existing_action}'")]
+pub struct BindingConflict {
+    pub action: String,
+    pub existing_action: String,
+    pub binding: InputBinding,
+}
+
+/// Action name -> bindings; any one of an action's bindings activates it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub actions: BTreeMap<String, Vec<InputBinding>>,
+}
+
+impl InputBindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a binding to an action, refusing combinations another action already uses
+    pub fn bind(
+        &mut self,
+        action: impl Into<String>,
+        binding: InputBinding,
+    ) -> Result<(), BindingConflict> {
+        let action = action.into();
+        if let Some(existing_action) = self.action_for(&binding) {
+            if existing_action != action {
+                return Err(BindingConflict {
+                    action,
+                    existing_action: existing_action.to_string(),
+                    binding,
+                });
+            }
+            return Ok(());
+        }
+
+        self.actions.entry(action).or_default().push(binding);
+        Ok(())
+    }
+
+    /// Remove every binding of an action
+    pub fn unbind(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    /// Bindings of an action (empty if unbound)
+    pub fn bindings_for(&self, action: &str) -> &[InputBinding] {
+        self.actions.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Action that uses exactly this combination, if any
+    pub fn action_for(&self, binding: &InputBinding) -> Option<&str> {
+        self.actions
+            .iter()
+            .find(|(_, bindings)| bindings.contains(binding))
+            .map(|(action, _)| action.as_str())
+    }
+
+    /// Every pair of actions sharing a combination
+    ///
+    /// `bind` prevents new conflicts; this catches ones in hand-edited or
+    /// deserialized keymaps.
+    pub fn conflicts(&self) -> Vec<BindingConflict> {
+        let mut conflicts = Vec::new();
+        let entries: Vec<_> = self.actions.iter().collect();
+
+        for (i, (action, bindings)) in entries.iter().enumerate() {
+            for (other_action, other_bindings) in &entries[i + 1..] {
+                for binding in bindings.iter().filter(|b| other_bindings.contains(b)) {
+                    conflicts.push(BindingConflict {
+                        action: (*other_action).clone(),
+                        existing_action: (*action).clone(),
+                        binding: binding.clone(),
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_reports_conflict() {
+        let mut bindings = InputBindings::new();
+        let sprint_jump = InputBinding::key(KeyCode::Space).with_modifier(KeyCode::ShiftLeft);
+
+        bindings
+            .bind("jump", InputBinding::key(KeyCode::Space))
+            .expect("jump should bind");
+        bindings
+            .bind("long_jump", sprint_jump.clone())
+            .expect("chord differs from plain Space");
+
+        let conflict = bindings
+            .bind("fly", sprint_jump)
+            .expect_err("Shift+Space is taken");
+        assert_eq!(conflict.existing_action, "long_jump");
+        assert!(bindings.conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_bindings_round_trip_through_serde() {
+        let mut bindings = InputBindings::new();
+        bindings
+            .bind("place_block", InputBinding::mouse(MouseButton::Right))
+            .expect("place_block should bind");
+        bindings
+            .bind(
+                "drop_stack",
+                InputBinding::key(KeyCode::KeyQ).with_modifier(KeyCode::ControlLeft),
+            )
+            .expect("drop_stack should bind");
+
+        let json = serde_json::to_string(&bindings).expect("serialize");
+        let loaded: InputBindings = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(loaded, bindings);
+    }
+}
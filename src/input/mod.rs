@@ -1,12 +1,24 @@
+mod bindings;
+
 use std::collections::HashSet;
-use winit::event::{ElementState, MouseButton};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta};
 pub use winit::keyboard::KeyCode;
 
+pub use bindings::{BindingConflict, InputBinding, InputBindings, InputTrigger};
+
+/// Pixels of touchpad scrolling that count as one wheel line
+pub const SCROLL_PIXELS_PER_LINE: f32 = 20.0;
+
 #[derive(Debug)]
 pub struct InputState {
     keys_pressed: HashSet<KeyCode>,
     mouse_buttons_pressed: HashSet<MouseButton>,
+    /// Held sets as of the last `end_frame`, for edge detection
+    prev_keys_pressed: HashSet<KeyCode>,
+    prev_mouse_buttons_pressed: HashSet<MouseButton>,
     mouse_delta: (f32, f32),
+    /// Vertical scroll in wheel lines since the last clear (positive = away from the user)
+    scroll_delta: f32,
     pub cursor_locked: bool,
     last_mouse_pos: Option<(f32, f32)>,
 }
@@ -16,7 +28,10 @@ impl InputState {
         Self {
             keys_pressed: HashSet::new(),
             mouse_buttons_pressed: HashSet::new(),
+            prev_keys_pressed: HashSet::new(),
+            prev_mouse_buttons_pressed: HashSet::new(),
             mouse_delta: (0.0, 0.0),
+            scroll_delta: 0.0,
             cursor_locked: false,
             last_mouse_pos: None,
         }
@@ -68,6 +83,21 @@ impl InputState {
         }
     }
 
+    /// Accumulate vertical scroll, in wheel lines
+    pub fn process_scroll(&mut self, delta: f32) {
+        self.scroll_delta += delta;
+    }
+
+    /// Handle a winit `MouseWheel` event
+    /// Line and pixel deltas are normalized to wheel lines.
+    pub fn process_mouse_wheel(&mut self, delta: MouseScrollDelta) {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / SCROLL_PIXELS_PER_LINE,
+        };
+        self.process_scroll(lines);
+    }
+
     pub fn is_key_pressed(&self, key: KeyCode) -> bool {
         self.keys_pressed.contains(&key)
     }
@@ -76,6 +106,65 @@ impl InputState {
         self.mouse_buttons_pressed.contains(&button)
     }
 
+    /// Key went down this frame (held now, not at the last `end_frame`)
+    pub fn was_key_just_pressed(&self, key: KeyCode) -> bool {
+        self.keys_pressed.contains(&key) && !self.prev_keys_pressed.contains(&key)
+    }
+
+    /// Key went up this frame (held at the last `end_frame`, not now)
+    pub fn was_key_just_released(&self, key: KeyCode) -> bool {
+        !self.keys_pressed.contains(&key) && self.prev_keys_pressed.contains(&key)
+    }
+
+    pub fn was_mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_pressed.contains(&button)
+            && !self.prev_mouse_buttons_pressed.contains(&button)
+    }
+
+    pub fn was_mouse_button_just_released(&self, button: MouseButton) -> bool {
+        !self.mouse_buttons_pressed.contains(&button)
+            && self.prev_mouse_buttons_pressed.contains(&button)
+    }
+
+    /// Snapshot the held keys and buttons; call once after the frame's input is handled
+    pub fn end_frame(&mut self) {
+        self.prev_keys_pressed.clone_from(&self.keys_pressed);
+        self.prev_mouse_buttons_pressed
+            .clone_from(&self.mouse_buttons_pressed);
+    }
+
+    /// Whether any binding of `action` is held, including its modifier keys
+    ///
+    /// A held binding does not count while a more specific chord on the same
+    /// trigger is also held, so Shift+Space does not fire a plain Space action.
+    /// Extra modifiers alone do not block it: Shift+W still fires W unless
+    /// Shift+W is bound.
+    pub fn is_action_pressed(&self, bindings: &InputBindings, action: &str) -> bool {
+        bindings.bindings_for(action).iter().any(|binding| {
+            self.is_binding_pressed(binding) && !self.is_binding_shadowed(bindings, binding)
+        })
+    }
+
+    /// Whether a more specific chord than `binding` is held
+    fn is_binding_shadowed(&self, bindings: &InputBindings, binding: &InputBinding) -> bool {
+        bindings
+            .actions
+            .values()
+            .flatten()
+            .any(|other| other.is_more_specific_than(binding) && self.is_binding_pressed(other))
+    }
+
+    /// Whether the trigger and all of the binding's modifiers are held
+    ///
+    /// This ignores other bindings; `is_action_pressed` resolves overlapping chords.
+    pub fn is_binding_pressed(&self, binding: &InputBinding) -> bool {
+        let trigger_held = match binding.trigger {
+            InputTrigger::Key(key) => self.is_key_pressed(key),
+            InputTrigger::Mouse(button) => self.is_mouse_button_pressed(button),
+        };
+        trigger_held && binding.modifiers.iter().all(|m| self.is_key_pressed(*m))
+    }
+
     pub fn get_mouse_delta(&self) -> (f32, f32) {
         self.mouse_delta
     }
@@ -84,9 +173,104 @@ impl InputState {
         self.mouse_delta = (0.0, 0.0);
     }
 
+    pub fn get_scroll_delta(&self) -> f32 {
+        self.scroll_delta
+    }
+
+    pub fn clear_scroll_delta(&mut self) {
+        self.scroll_delta = 0.0;
+    }
+
     pub fn reset_mouse_tracking(&mut self) {
         self.last_mouse_pos = None;
         self.mouse_delta = (0.0, 0.0);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_edges_across_frames() {
+        let mut input = InputState::new();
+
+        // Frame 1: press
+        input.process_key(KeyCode::Digit1, ElementState::Pressed);
+        assert!(input.was_key_just_pressed(KeyCode::Digit1));
+        assert!(!input.was_key_just_released(KeyCode::Digit1));
+        input.end_frame();
+
+        // Frame 2: hold
+        assert!(input.is_key_pressed(KeyCode::Digit1));
+        assert!(!input.was_key_just_pressed(KeyCode::Digit1));
+        assert!(!input.was_key_just_released(KeyCode::Digit1));
+        input.end_frame();
+
+        // Frame 3: release
+        input.process_key(KeyCode::Digit1, ElementState::Released);
+        assert!(!input.was_key_just_pressed(KeyCode::Digit1));
+        assert!(input.was_key_just_released(KeyCode::Digit1));
+        input.end_frame();
+        assert!(!input.was_key_just_released(KeyCode::Digit1));
+    }
+
+    #[test]
+    fn test_mouse_button_edges_across_frames() {
+        let mut input = InputState::new();
+
+        input.process_mouse_button(MouseButton::Left, ElementState::Pressed);
+        assert!(input.was_mouse_button_just_pressed(MouseButton::Left));
+        input.end_frame();
+
+        assert!(!input.was_mouse_button_just_pressed(MouseButton::Left));
+        input.end_frame();
+
+        input.process_mouse_button(MouseButton::Left, ElementState::Released);
+        assert!(input.was_mouse_button_just_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn test_scroll_normalizes_line_and_pixel_deltas() {
+        let mut input = InputState::new();
+
+        input.process_mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0));
+        input.process_mouse_wheel(MouseScrollDelta::PixelDelta(
+            winit::dpi::PhysicalPosition::new(0.0, -2.0 * SCROLL_PIXELS_PER_LINE as f64),
+        ));
+        assert!((input.get_scroll_delta() + 1.0).abs() < 1e-6);
+
+        input.clear_scroll_delta();
+        assert_eq!(input.get_scroll_delta(), 0.0);
+    }
+
+    #[test]
+    fn test_shift_space_does_not_fire_plain_space() {
+        let mut bindings = InputBindings::new();
+        bindings
+            .bind("jump", InputBinding::key(KeyCode::Space))
+            .expect("jump should bind");
+        bindings
+            .bind(
+                "long_jump",
+                InputBinding::key(KeyCode::Space).with_modifier(KeyCode::ShiftLeft),
+            )
+            .expect("long_jump should bind");
+        bindings
+            .bind("forward", InputBinding::key(KeyCode::KeyW))
+            .expect("forward should bind");
+        let mut input = InputState::new();
 
+        input.process_key(KeyCode::Space, ElementState::Pressed);
+        assert!(input.is_action_pressed(&bindings, "jump"));
+        assert!(!input.is_action_pressed(&bindings, "long_jump"));
+
+        input.process_key(KeyCode::ShiftLeft, ElementState::Pressed);
+        assert!(input.is_action_pressed(&bindings, "long_jump"));
+        assert!(!input.is_action_pressed(&bindings, "jump"));
+
+        // No Shift+W chord is bound, so the modifier does not block W
+        input.process_key(KeyCode::KeyW, ElementState::Pressed);
+        assert!(input.is_action_pressed(&bindings, "forward"));
+    }
 }
@@ -108,6 +108,25 @@ pub enum WorldGeneratorType {
     Default,
     DangerMoney,
     Custom(String),
+    /// Solid `block` below `ground_level`, air above; needs no GPU
+    Flat { ground_level: i32, block: BlockId },
+}
+
+impl WorldGeneratorType {
+    /// Build the generator for types that run without a GPU device
+    /// Returns `None` for types that need GPU resources or a factory.
+    pub fn create_cpu_generator(&self) -> Option<Box<dyn WorldGenerator + Send + Sync>> {
+        match self {
+            WorldGeneratorType::Flat {
+                ground_level,
+                block,
+            } => Some(Box::new(world::generation::FlatWorldGenerator::new(
+                *ground_level,
+                *block,
+            ))),
+            _ => None,
+        }
+    }
 }
 
 /// Factory function type for creating world generators when GPU resources are available
@@ -259,6 +278,66 @@ impl EngineConfig {
 
         suggestions.join("\n")
     }
+
+    /// Apply the live-tunable settings of a reloaded config
+    ///
+    /// Only `render_distance` can change mid-session; the caller streams
+    /// chunks in or out to match the returned change. A different
+    /// `chunk_size` is refused because every chunk buffer is sized from it.
+    /// Invalid values leave `self` untouched.
+    pub fn apply_live_update(&mut self, reloaded: &EngineConfig) -> Result<LiveConfigChange> {
+        if reloaded.chunk_size != self.chunk_size {
+            return Err(anyhow::anyhow!(
+                "EngineConfig: chunk_size cannot change from {} to {} while running; restart the engine to apply it",
+                self.chunk_size,
+                reloaded.chunk_size
+            ));
+        }
+
+        let candidate = EngineConfig {
+            window_title: self.window_title.clone(),
+            window_width: self.window_width,
+            window_height: self.window_height,
+            chunk_size: self.chunk_size,
+            render_distance: reloaded.render_distance,
+            world_generator: None,
+            world_generator_type: self.world_generator_type.clone(),
+            world_generator_factory: None,
+        };
+        if let Err(e) = candidate.validate() {
+            log::error!("[EngineConfig] Rejected live config update: {}", e);
+            log::error!(
+                "[EngineConfig] Suggestions:\n{}",
+                candidate.suggest_safe_config()
+            );
+            return Err(e);
+        }
+
+        let mut change = LiveConfigChange::default();
+        if reloaded.render_distance != self.render_distance {
+            change.render_distance = Some((self.render_distance, reloaded.render_distance));
+            log::info!(
+                "[EngineConfig] render_distance {} -> {}",
+                self.render_distance,
+                reloaded.render_distance
+            );
+            self.render_distance = reloaded.render_distance;
+        }
+        Ok(change)
+    }
+}
+
+/// Settings changed by `EngineConfig::apply_live_update`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LiveConfigChange {
+    /// `(old, new)` render distance in chunks
+    pub render_distance: Option<(u32, u32)>,
+}
+
+impl LiveConfigChange {
+    pub fn is_empty(&self) -> bool {
+        self.render_distance.is_none()
+    }
 }
 
 impl Default for EngineConfig {
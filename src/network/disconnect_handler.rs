@@ -33,6 +33,27 @@ pub struct DisconnectingPlayer {
     pub chunks_to_save: HashSet<ChunkPos>,
     pub disconnect_time: Instant,
     pub state: ConnectionState,
+    /// Serialized connection and interest state, restored on resumption
+    pub session_state: Option<Vec<u8>>,
+}
+
+/// Secret handed to a client on connect; presenting it on reconnect resumes the session
+pub type ResumeToken = u128;
+
+/// Outcome of a reconnect attempt that presented a resume token
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResumeResult {
+    /// Session restored; the old token is spent and `new_token` replaces it
+    Resumed {
+        session_state: Option<Vec<u8>>,
+        new_token: ResumeToken,
+    },
+    /// Player is not in the disconnecting list; treat as a fresh join
+    NotDisconnecting,
+    /// Token does not match the one issued to this player
+    InvalidToken,
+    /// Reconnect came after `resume_window_ms`
+    Expired,
 }
 
 /// Configuration for disconnect handling
@@ -46,6 +67,8 @@ pub struct DisconnectConfig {
     pub emergency_save_enabled: bool,
     /// Grace period for reconnection before save
     pub reconnect_grace_period: Duration,
+    /// How long after disconnecting a resume token is still accepted (0 disables resumption)
+    pub resume_window_ms: u64,
 }
 
 impl Default for DisconnectConfig {
@@ -55,6 +78,7 @@ impl Default for DisconnectConfig {
             chunk_save_radius: 3,
             emergency_save_enabled: true,
             reconnect_grace_period: Duration::from_secs(5),
+            resume_window_ms: 10_000,
         }
     }
 }
@@ -68,6 +92,8 @@ pub struct DisconnectStats {
     pub emergency_saves: u64,
     pub average_save_time: Duration,
     pub force_disconnects: u64,
+    pub successful_resumptions: u64,
+    pub rejected_resumptions: u64,
 }
 
 /// Handles player disconnections with save protection
@@ -84,6 +110,9 @@ pub struct DisconnectHandler {
     /// Statistics
     stats: Arc<Mutex<DisconnectStats>>,
 
+    /// Current resume token per player uuid
+    resume_tokens: Arc<Mutex<HashMap<String, ResumeToken>>>,
+
     /// Background thread handle
     worker_thread: Option<thread::JoinHandle<()>>,
 
@@ -105,7 +134,10 @@ impl DisconnectHandler {
                 emergency_saves: 0,
                 average_save_time: Duration::from_millis(0),
                 force_disconnects: 0,
+                successful_resumptions: 0,
+                rejected_resumptions: 0,
             })),
+            resume_tokens: Arc::new(Mutex::new(HashMap::new())),
             worker_thread: None,
             shutdown: Arc::new(Mutex::new(false)),
         };
@@ -119,10 +151,18 @@ impl DisconnectHandler {
         let save_data = Arc::clone(&self.save_data);
         let config = self.config.clone();
         let stats = Arc::clone(&self.stats);
+        let resume_tokens = Arc::clone(&self.resume_tokens);
         let shutdown = Arc::clone(&self.shutdown);
 
         self.worker_thread = Some(thread::spawn(move || {
-            Self::worker_loop(disconnecting_players, save_data, config, stats, shutdown);
+            Self::worker_loop(
+                disconnecting_players,
+                save_data,
+                config,
+                stats,
+                resume_tokens,
+                shutdown,
+            );
         }));
 
         Ok(())
@@ -158,6 +198,7 @@ impl DisconnectHandler {
             chunks_to_save,
             disconnect_time: Instant::now(),
             state: ConnectionState::Disconnecting,
+            session_state: None,
         };
 
         // Add to disconnecting players list
@@ -253,12 +294,16 @@ impl DisconnectHandler {
             .map_err(|_| PersistenceError::LockPoisoned("disconnecting_players".to_string()))?;
 
         if let Some(mut player) = players.remove(player_uuid) {
+            // A completed save already left the disconnecting count
+            let was_counted = player.state != ConnectionState::SaveComplete;
             player.state = ConnectionState::Disconnected;
 
             // Update stats
             if let Ok(mut stats) = self.stats.lock() {
                 stats.force_disconnects += 1;
-                stats.players_disconnecting = stats.players_disconnecting.saturating_sub(1);
+                if was_counted {
+                    stats.players_disconnecting = stats.players_disconnecting.saturating_sub(1);
+                }
             }
 
             println!(
@@ -271,6 +316,97 @@ impl DisconnectHandler {
         }
     }
 
+    /// Issue a fresh resume token for a connecting player, replacing any old one
+    pub fn issue_resume_token(&self, player_uuid: &str) -> PersistenceResult<ResumeToken> {
+        let token: ResumeToken = rand::random();
+        let mut tokens = self
+            .resume_tokens
+            .lock()
+            .map_err(|_| PersistenceError::LockPoisoned("resume_tokens".to_string()))?;
+        tokens.insert(player_uuid.to_string(), token);
+        Ok(token)
+    }
+
+    /// Attach the state to restore if a disconnecting player resumes
+    ///
+    /// Returns false if the player is not disconnecting.
+    pub fn set_session_state(&self, player_uuid: &str, state: Vec<u8>) -> PersistenceResult<bool> {
+        let mut players = self
+            .disconnecting_players
+            .lock()
+            .map_err(|_| PersistenceError::LockPoisoned("disconnecting_players".to_string()))?;
+
+        match players.get_mut(player_uuid) {
+            Some(player) => {
+                player.session_state = Some(state);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Resume a disconnecting player's session if `token` is valid and the
+    /// reconnect falls inside `resume_window_ms`
+    ///
+    /// Expired tokens are discarded, so a stale token can never succeed later;
+    /// the worker also discards them once the window closes.
+    pub fn try_resume(
+        &self,
+        player_uuid: &str,
+        token: ResumeToken,
+    ) -> PersistenceResult<ResumeResult> {
+        let mut players = self
+            .disconnecting_players
+            .lock()
+            .map_err(|_| PersistenceError::LockPoisoned("disconnecting_players".to_string()))?;
+        let mut tokens = self
+            .resume_tokens
+            .lock()
+            .map_err(|_| PersistenceError::LockPoisoned("resume_tokens".to_string()))?;
+
+        // Only players whose save has not completed still count as disconnecting
+        let mut was_counted = false;
+        let result = match players.get(player_uuid) {
+            None => ResumeResult::NotDisconnecting,
+            Some(_) if tokens.get(player_uuid) != Some(&token) => ResumeResult::InvalidToken,
+            Some(player) => {
+                let window = Duration::from_millis(self.config.resume_window_ms);
+                if player.disconnect_time.elapsed() >= window {
+                    tokens.remove(player_uuid);
+                    ResumeResult::Expired
+                } else {
+                    let new_token: ResumeToken = rand::random();
+                    tokens.insert(player_uuid.to_string(), new_token);
+                    let player = players.remove(player_uuid);
+                    was_counted = player
+                        .as_ref()
+                        .is_some_and(|p| p.state != ConnectionState::SaveComplete);
+                    ResumeResult::Resumed {
+                        session_state: player.and_then(|p| p.session_state),
+                        new_token,
+                    }
+                }
+            }
+        };
+
+        if let Ok(mut stats) = self.stats.lock() {
+            match result {
+                ResumeResult::Resumed { .. } => {
+                    stats.successful_resumptions += 1;
+                    if was_counted {
+                        stats.players_disconnecting = stats.players_disconnecting.saturating_sub(1);
+                    }
+                }
+                ResumeResult::InvalidToken | ResumeResult::Expired => {
+                    stats.rejected_resumptions += 1;
+                }
+                ResumeResult::NotDisconnecting => {}
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Get chunks around a player position that need saving
     fn get_chunks_around_player(&self, position: (f64, f64, f64)) -> HashSet<ChunkPos> {
         let (x, _y, z) = position;
@@ -323,8 +459,11 @@ impl DisconnectHandler {
         save_data: Arc<AtomicSaveData>,
         config: DisconnectConfig,
         stats: Arc<Mutex<DisconnectStats>>,
+        resume_tokens: Arc<Mutex<HashMap<String, ResumeToken>>>,
         shutdown: Arc<Mutex<bool>>,
     ) {
+        let resume_window = Duration::from_millis(config.resume_window_ms);
+
         loop {
             // Check shutdown signal
             if let Ok(shutdown_flag) = shutdown.lock() {
@@ -347,6 +486,14 @@ impl DisconnectHandler {
             for player in players_to_process {
                 let now = Instant::now();
                 let disconnect_duration = now.duration_since(player.disconnect_time);
+                let save_complete = player.state == ConnectionState::SaveComplete;
+
+                // Drop the resume token once it can no longer be accepted
+                if disconnect_duration >= resume_window {
+                    if let Ok(mut tokens) = resume_tokens.lock() {
+                        tokens.remove(&player.uuid);
+                    }
+                }
 
                 // Check if save timeout exceeded
                 if disconnect_duration > config.max_save_timeout {
@@ -361,14 +508,19 @@ impl DisconnectHandler {
 
                     if let Ok(mut stats_lock) = stats.lock() {
                         stats_lock.force_disconnects += 1;
-                        stats_lock.players_disconnecting =
-                            stats_lock.players_disconnecting.saturating_sub(1);
+                        if !save_complete {
+                            stats_lock.players_disconnecting =
+                                stats_lock.players_disconnecting.saturating_sub(1);
+                        }
                     }
                     continue;
                 }
 
                 // Check if grace period passed and saves are complete
-                if disconnect_duration > config.reconnect_grace_period && Self::are_player_saves_complete(&save_data, &player) {
+                if !save_complete
+                    && disconnect_duration > config.reconnect_grace_period
+                    && Self::are_player_saves_complete(&save_data, &player)
+                {
                     println!(
                         "[DisconnectHandler] Save complete for player {}",
                         player.uuid
@@ -530,6 +682,148 @@ mod tests {
         assert!(chunks.contains(&ChunkPos { x: -1, y: 0, z: -1 }));
     }
 
+    #[test]
+    fn test_resume_within_window_restores_session() {
+        let save_data = create_test_save_data();
+        let handler = DisconnectHandler::new(save_data, DisconnectConfig::default());
+        let world = create_test_world();
+
+        let token = handler
+            .issue_resume_token("resume_test")
+            .expect("Failed to issue token");
+        handler
+            .handle_disconnect(
+                "resume_test".to_string(),
+                "ResumeTest".to_string(),
+                &world,
+                (0.0, 64.0, 0.0),
+            )
+            .expect("Failed to handle disconnect");
+        handler
+            .set_session_state("resume_test", vec![1, 2, 3])
+            .expect("Failed to store session state");
+
+        assert_eq!(
+            handler
+                .try_resume("resume_test", token ^ 1)
+                .expect("resume"),
+            ResumeResult::InvalidToken
+        );
+        match handler.try_resume("resume_test", token).expect("resume") {
+            ResumeResult::Resumed {
+                session_state,
+                new_token,
+            } => {
+                assert_eq!(session_state, Some(vec![1, 2, 3]));
+                assert_ne!(new_token, token);
+            }
+            other => panic!("expected resumption, got {:?}", other),
+        }
+
+        assert!(!handler.is_player_disconnecting("resume_test"));
+        let stats = handler.get_stats().expect("Failed to get stats");
+        assert_eq!(stats.successful_resumptions, 1);
+        assert_eq!(stats.rejected_resumptions, 1);
+    }
+
+    #[test]
+    fn test_stale_resume_token_rejected() {
+        let save_data = create_test_save_data();
+        let config = DisconnectConfig {
+            resume_window_ms: 0,
+            ..Default::default()
+        };
+        let handler = DisconnectHandler::new(save_data, config);
+        let world = create_test_world();
+
+        let token = handler
+            .issue_resume_token("stale_test")
+            .expect("Failed to issue token");
+        handler
+            .handle_disconnect(
+                "stale_test".to_string(),
+                "StaleTest".to_string(),
+                &world,
+                (0.0, 64.0, 0.0),
+            )
+            .expect("Failed to handle disconnect");
+
+        assert_eq!(
+            handler.try_resume("stale_test", token).expect("resume"),
+            ResumeResult::Expired
+        );
+        // The expired token is discarded, not just refused once
+        assert_eq!(
+            handler.try_resume("stale_test", token).expect("resume"),
+            ResumeResult::InvalidToken
+        );
+        assert!(handler.is_player_disconnecting("stale_test"));
+    }
+
+    #[test]
+    fn test_resume_after_save_complete_counts_player_once() {
+        let save_data = create_test_save_data();
+        let handler = DisconnectHandler::new(save_data, DisconnectConfig::default());
+        let world = create_test_world();
+
+        let token = handler
+            .issue_resume_token("saved")
+            .expect("Failed to issue token");
+        for uuid in ["saved", "pending"] {
+            handler
+                .handle_disconnect(uuid.to_string(), uuid.to_string(), &world, (0.0, 64.0, 0.0))
+                .expect("Failed to handle disconnect");
+        }
+
+        // What the worker does once the save of "saved" completes
+        {
+            let mut players = handler.disconnecting_players.lock().expect("players lock");
+            if let Some(player) = players.get_mut("saved") {
+                player.state = ConnectionState::SaveComplete;
+            }
+            let mut stats = handler.stats.lock().expect("stats lock");
+            stats.players_disconnecting -= 1;
+        }
+
+        assert!(matches!(
+            handler.try_resume("saved", token).expect("resume"),
+            ResumeResult::Resumed { .. }
+        ));
+        let stats = handler.get_stats().expect("Failed to get stats");
+        assert_eq!(stats.players_disconnecting, 1);
+    }
+
+    #[test]
+    fn test_worker_expires_resume_tokens() {
+        let save_data = create_test_save_data();
+        let config = DisconnectConfig {
+            resume_window_ms: 0,
+            ..Default::default()
+        };
+        let mut handler = DisconnectHandler::new(save_data, config);
+        let world = create_test_world();
+
+        handler
+            .issue_resume_token("expiry_test")
+            .expect("Failed to issue token");
+        handler
+            .handle_disconnect(
+                "expiry_test".to_string(),
+                "ExpiryTest".to_string(),
+                &world,
+                (0.0, 64.0, 0.0),
+            )
+            .expect("Failed to handle disconnect");
+
+        handler.start().expect("Failed to start worker");
+        thread::sleep(Duration::from_millis(300));
+        handler.stop().expect("Failed to stop worker");
+
+        // Removed without any reconnect attempt
+        let tokens = handler.resume_tokens.lock().expect("tokens lock");
+        assert!(!tokens.contains_key("expiry_test"));
+    }
+
     #[test]
     fn test_force_disconnect() {
         let save_data = create_test_save_data();
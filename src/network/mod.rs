@@ -50,7 +50,7 @@ pub use anticheat::{AntiCheat, CombatAction, InteractionType, ValidationResult};
 // Player sync module removed - used game-specific inventory types
 pub use disconnect_handler::{
     ConnectionState as DisconnectConnectionState, DisconnectConfig, DisconnectHandler,
-    DisconnectStats, DisconnectingPlayer,
+    DisconnectStats, DisconnectingPlayer, ResumeResult, ResumeToken,
 };
 pub use error::{connection_error, protocol_error, NetworkErrorContext, NetworkResult};
 pub use network_data::{
@@ -2,18 +2,105 @@
 //!
 //! This module provides a custom panic handler that logs panic information
 //! before the process terminates, helping with debugging and stability monitoring.
+//! It also keeps a bounded log of recent engine events, written out with the
+//! panic so a crash report shows what led up to it.
 
+use crate::constants::event_system::DEFAULT_MAX_HISTORY_SIZE;
 use chrono::{DateTime, Local};
+use parking_lot::{Mutex, RwLock};
 use std::backtrace::Backtrace;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::panic::{self, PanicHookInfo};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 /// Global panic counter for telemetry
 static PANIC_COUNT: AtomicUsize = AtomicUsize::new(0);
 
+/// Recent engine events, created on first use
+static EVENT_LOG: RwLock<Option<EventLog>> = RwLock::new(None);
+
+/// An entry in the recent event log
+#[derive(Debug, Clone)]
+pub struct LoggedEvent {
+    /// Position in the order events were recorded
+    pub sequence: u64,
+    pub timestamp: DateTime<Local>,
+    pub description: String,
+}
+
+/// Ring buffer of the most recent events
+///
+/// Each writer claims a slot with an atomic counter and locks only that
+/// slot, so threads recording at the same time rarely contend.
+struct EventLog {
+    slots: Vec<Mutex<Option<LoggedEvent>>>,
+    next_sequence: AtomicU64,
+}
+
+impl EventLog {
+    fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity.max(1)).map(|_| Mutex::new(None)).collect(),
+            next_sequence: AtomicU64::new(0),
+        }
+    }
+
+    fn push(&self, description: String) {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        let slot = &self.slots[(sequence % self.slots.len() as u64) as usize];
+        *slot.lock() = Some(LoggedEvent {
+            sequence,
+            timestamp: Local::now(),
+            description,
+        });
+    }
+
+    /// Entries oldest first; slots held by a writer are skipped
+    fn snapshot(&self) -> Vec<LoggedEvent> {
+        let mut events: Vec<LoggedEvent> = self
+            .slots
+            .iter()
+            .filter_map(|slot| slot.try_lock().and_then(|event| event.clone()))
+            .collect();
+        events.sort_by_key(|event| event.sequence);
+        events
+    }
+}
+
+/// Keep the last `capacity` events, discarding anything recorded so far
+///
+/// Defaults to `DEFAULT_MAX_HISTORY_SIZE` when never called.
+pub fn configure_event_log(capacity: usize) {
+    *EVENT_LOG.write() = Some(EventLog::new(capacity));
+}
+
+/// Record an engine event in the recent event log
+pub fn record_event(description: impl Into<String>) {
+    let description = description.into();
+    if let Some(log) = EVENT_LOG.read().as_ref() {
+        log.push(description);
+        return;
+    }
+
+    EVENT_LOG
+        .write()
+        .get_or_insert_with(|| EventLog::new(DEFAULT_MAX_HISTORY_SIZE))
+        .push(description);
+}
+
+/// The recent event log, oldest event first
+///
+/// Safe to call from a panic hook: it never blocks, and returns an empty
+/// log if the log is being reconfigured.
+pub fn dump_event_log() -> Vec<LoggedEvent> {
+    EVENT_LOG
+        .try_read()
+        .and_then(|log| log.as_ref().map(EventLog::snapshot))
+        .unwrap_or_default()
+}
+
 /// Panic telemetry data
 #[derive(Debug)]
 pub struct PanicTelemetry {
@@ -22,6 +109,7 @@ pub struct PanicTelemetry {
     pub message: String,
     pub backtrace: String,
     pub panic_count: usize,
+    pub recent_events: Vec<LoggedEvent>,
 }
 
 impl PanicTelemetry {
@@ -54,6 +142,7 @@ impl PanicTelemetry {
             message,
             backtrace,
             panic_count,
+            recent_events: dump_event_log(),
         }
     }
 
@@ -73,6 +162,16 @@ impl PanicTelemetry {
         writeln!(file, "Location: {}", self.location)?;
         writeln!(file, "Message: {}", self.message)?;
         writeln!(file, "Backtrace:\n{}", self.backtrace)?;
+        writeln!(file, "Recent events ({}):", self.recent_events.len())?;
+        for event in &self.recent_events {
+            writeln!(
+                file,
+                "  #{} {} {}",
+                event.sequence,
+                event.timestamp.format("%H:%M:%S%.3f"),
+                event.description
+            )?;
+        }
         writeln!(file, "================\n")?;
 
         file.flush()?;
@@ -175,10 +274,24 @@ mod tests {
             message: "test panic".to_string(),
             backtrace: "backtrace here".to_string(),
             panic_count: 1,
+            recent_events: Vec::new(),
         };
 
         assert!(telemetry.location.contains("test.rs"));
         assert_eq!(telemetry.message, "test panic");
         assert_eq!(telemetry.panic_count, 1);
     }
+
+    #[test]
+    fn test_event_log_keeps_most_recent_events() {
+        configure_event_log(3);
+        for frame in 0..5 {
+            record_event(format!("frame {}", frame));
+        }
+
+        let events = dump_event_log();
+        let descriptions: Vec<&str> = events.iter().map(|e| e.description.as_str()).collect();
+        assert_eq!(descriptions, ["frame 2", "frame 3", "frame 4"]);
+        assert_eq!(events[0].sequence, 2);
+    }
 }
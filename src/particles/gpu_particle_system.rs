@@ -1,6 +1,7 @@
 use crate::gpu::error_recovery::{GpuErrorRecovery, GpuRecoveryError, GpuResultExt};
 use anyhow::{anyhow, Result};
 use glam::Vec3;
+use std::num::NonZeroU64;
 use std::sync::Arc;
 use std::time::Duration;
 use wgpu::util::DeviceExt;
@@ -42,9 +43,96 @@ pub struct GpuParticleSystem {
     emitter_count: u32,
     next_emitter_id: u64,
 
+    // Depth sorting
+    sort_keys_pipeline: wgpu::ComputePipeline,
+    sort_step_pipeline: wgpu::ComputePipeline,
+    sort_bind_group: wgpu::BindGroup,
+    sort_entry_buffer: wgpu::Buffer,
+    sort_params_buffer: wgpu::Buffer,
+    sort_staging_buffer: wgpu::Buffer,
+    sort_step_stride: u32,
+    sort_stats: ParticleSortStats,
+
     // Physics parameters
     pub wind_velocity: Vec3,
     pub gravity: f32,
+
+    /// Sort live particles back to front before readback
+    ///
+    /// Needed for alpha-blended effects; purely additive ones (fire) can
+    /// turn it off since their blend result does not depend on order.
+    pub depth_sort: bool,
+    /// Eye position the depth sort measures distance from
+    pub camera_position: Vec3,
+}
+
+/// What the last `GpuParticleSystem::update` did about depth sorting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParticleSortStats {
+    pub sorted: bool,
+    pub sorted_count: u32,
+}
+
+/// Sort key of one particle; `distance_sq` is negative for dead or padding entries
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ParticleSortEntry {
+    pub distance_sq: f32,
+    pub index: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SortParams {
+    camera_position: [f32; 3],
+    particle_count: u32,
+    entry_count: u32,
+    _padding: [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct BitonicStep {
+    j: u32,
+    k: u32,
+    _padding: [u32; 2],
+}
+
+/// `(j, k)` of every pass of a bitonic sort over `count` entries
+///
+/// `count` must be a power of two. The passes for a smaller count are a
+/// prefix of those for a larger one, so one table serves every batch size.
+pub fn bitonic_passes(count: u32) -> Vec<(u32, u32)> {
+    let mut passes = Vec::new();
+    let mut k = 2;
+    while k <= count {
+        let mut j = k / 2;
+        while j > 0 {
+            passes.push((j, k));
+            j /= 2;
+        }
+        k *= 2;
+    }
+    passes
+}
+
+/// CPU version of the `particle_sort.wgsl` network
+///
+/// Applies the same compare-exchange passes in the same order, leaving
+/// `entries` far to near. The length must be a power of two.
+pub fn bitonic_sort_cpu(entries: &mut [ParticleSortEntry]) {
+    for (j, k) in bitonic_passes(entries.len() as u32) {
+        for i in 0..entries.len() {
+            let partner = i ^ j as usize;
+            if partner <= i {
+                continue;
+            }
+            let descending = i & k as usize == 0;
+            if (entries[i].distance_sq < entries[partner].distance_sq) == descending {
+                entries.swap(i, partner);
+            }
+        }
+    }
 }
 
 #[repr(C)]
@@ -138,6 +226,55 @@ impl GpuParticleSystem {
             mapped_at_creation: false,
         });
 
+        let max_sort_entries = max_particles.next_power_of_two().max(2);
+        let sort_entry_size =
+            (std::mem::size_of::<ParticleSortEntry>() * max_sort_entries as usize) as u64;
+
+        let sort_entry_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Sort Entry Buffer"),
+            size: sort_entry_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let sort_staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Sort Staging Buffer"),
+            size: sort_entry_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let sort_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Sort Params Buffer"),
+            size: std::mem::size_of::<SortParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Every pass's (j, k) at its own dynamic offset, written once up front
+        let sort_step_stride = device
+            .limits()
+            .min_uniform_buffer_offset_alignment
+            .max(std::mem::size_of::<BitonicStep>() as u32);
+        let mut sort_steps = Vec::new();
+        for (j, k) in bitonic_passes(max_sort_entries) {
+            let step = BitonicStep {
+                j,
+                k,
+                _padding: [0; 2],
+            };
+            sort_steps.extend_from_slice(bytemuck::bytes_of(&step));
+            sort_steps.resize(
+                sort_steps.len().next_multiple_of(sort_step_stride as usize),
+                0,
+            );
+        }
+        let sort_step_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle Sort Step Buffer"),
+            contents: &sort_steps,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
         // Load shader
         let shader_source = include_str!("../shaders/compute/gpu_update.wgsl");
         let validated_shader =
@@ -244,6 +381,82 @@ impl GpuParticleSystem {
             entry_point: "apply_force_field",
         });
 
+        let sort_shader_source = include_str!("../shaders/compute/particle_sort.wgsl");
+        let sort_shader = crate::gpu::automation::create_gpu_shader(
+            &device,
+            "particle_sort",
+            sort_shader_source,
+        )?;
+
+        let sort_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Particle Sort Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: NonZeroU64::new(
+                                std::mem::size_of::<BitonicStep>() as u64
+                            ),
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let sort_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Particle Sort Pipeline Layout"),
+            bind_group_layouts: &[&sort_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let sort_keys_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Particle Sort Keys Pipeline"),
+            layout: Some(&sort_pipeline_layout),
+            module: &sort_shader.module,
+            entry_point: "compute_sort_keys",
+        });
+
+        let sort_step_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Particle Sort Step Pipeline"),
+            layout: Some(&sort_pipeline_layout),
+            module: &sort_shader.module,
+            entry_point: "bitonic_step",
+        });
+
         // Create bind groups
         let update_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Particle Update Bind Group"),
@@ -286,6 +499,33 @@ impl GpuParticleSystem {
             ],
         });
 
+        let sort_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Particle Sort Bind Group"),
+            layout: &sort_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: particle_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: sort_entry_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: sort_params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &sort_step_buffer,
+                        offset: 0,
+                        size: NonZeroU64::new(std::mem::size_of::<BitonicStep>() as u64),
+                    }),
+                },
+            ],
+        });
+
         Ok(Self {
             device,
             queue,
@@ -304,8 +544,18 @@ impl GpuParticleSystem {
             active_particles: 0,
             emitter_count: 0,
             next_emitter_id: 0,
+            sort_keys_pipeline,
+            sort_step_pipeline,
+            sort_bind_group,
+            sort_entry_buffer,
+            sort_params_buffer,
+            sort_staging_buffer,
+            sort_step_stride,
+            sort_stats: ParticleSortStats::default(),
             wind_velocity: Vec3::ZERO,
             gravity: -crate::constants::physics_constants::GRAVITY, // Use voxel-scaled gravity (98.1 voxels/s²)
+            depth_sort: true,
+            camera_position: Vec3::ZERO,
             error_recovery,
         })
     }
@@ -360,6 +610,61 @@ impl GpuParticleSystem {
             update_pass.dispatch_workgroups(workgroups, 1, 1);
         }
 
+        // Depth sort after the update so keys see this frame's positions
+        self.sort_stats = ParticleSortStats::default();
+        if self.depth_sort && self.active_particles > 1 {
+            let entry_count = self.active_particles.next_power_of_two();
+            let sort_params = SortParams {
+                camera_position: self.camera_position.into(),
+                particle_count: self.active_particles,
+                entry_count,
+                _padding: [0; 3],
+            };
+            self.queue.write_buffer(
+                &self.sort_params_buffer,
+                0,
+                bytemuck::bytes_of(&sort_params),
+            );
+
+            let workgroups = entry_count.div_ceil(64);
+            {
+                let mut keys_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Particle Sort Keys Pass"),
+                    timestamp_writes: None,
+                });
+                keys_pass.set_pipeline(&self.sort_keys_pipeline);
+                keys_pass.set_bind_group(0, &self.sort_bind_group, &[0]);
+                keys_pass.dispatch_workgroups(workgroups, 1, 1);
+            }
+
+            // One compute pass per network pass so each sees the previous swaps
+            for pass_index in 0..bitonic_passes(entry_count).len() as u32 {
+                let mut step_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Particle Sort Step Pass"),
+                    timestamp_writes: None,
+                });
+                step_pass.set_pipeline(&self.sort_step_pipeline);
+                step_pass.set_bind_group(
+                    0,
+                    &self.sort_bind_group,
+                    &[pass_index * self.sort_step_stride],
+                );
+                step_pass.dispatch_workgroups(workgroups, 1, 1);
+            }
+
+            encoder.copy_buffer_to_buffer(
+                &self.sort_entry_buffer,
+                0,
+                &self.sort_staging_buffer,
+                0,
+                (std::mem::size_of::<ParticleSortEntry>() * entry_count as usize) as u64,
+            );
+            self.sort_stats = ParticleSortStats {
+                sorted: true,
+                sorted_count: self.active_particles,
+            };
+        }
+
         // Copy particle data to staging buffer for CPU readback
         encoder.copy_buffer_to_buffer(
             &self.particle_buffer,
@@ -410,7 +715,15 @@ impl GpuParticleSystem {
     }
 
     /// Read back particle data for rendering
+    ///
+    /// After a depth-sorted update the particles come back far to near.
     pub async fn read_render_data(&mut self) -> Result<&[ParticleGPUData]> {
+        let order = if self.sort_stats.sorted {
+            Some(self.read_sort_entries().await?)
+        } else {
+            None
+        };
+
         // Map staging buffer and read data
         let buffer_slice = self.staging_buffer.slice(..);
         let (tx, rx) = futures::channel::oneshot::channel();
@@ -436,19 +749,30 @@ impl GpuParticleSystem {
             let data = buffer_slice.get_mapped_range();
             let gpu_particles: &[GpuParticleData] = bytemuck::cast_slice(&data);
 
+            let live = &gpu_particles[..self.active_particles as usize];
+
             // Convert to render format
             self.render_data.clear();
-            for particle in &gpu_particles[..self.active_particles as usize] {
-                if particle.lifetime > 0.0 {
-                    self.render_data.push(ParticleGPUData {
-                        position: particle.position,
-                        size: particle.size,
-                        color: particle.color,
-                        rotation: particle.rotation,
-                        texture_index: particle.texture_frame,
-                        _padding: [0.0, 0.0],
-                    });
-                }
+            let mut push = |particle: &GpuParticleData| {
+                self.render_data.push(ParticleGPUData {
+                    position: particle.position,
+                    size: particle.size,
+                    color: particle.color,
+                    rotation: particle.rotation,
+                    texture_index: particle.texture_frame,
+                    _padding: [0.0, 0.0],
+                });
+            };
+            match &order {
+                Some(entries) => entries
+                    .iter()
+                    .filter(|entry| entry.distance_sq >= 0.0)
+                    .filter_map(|entry| live.get(entry.index as usize))
+                    .for_each(&mut push),
+                None => live
+                    .iter()
+                    .filter(|particle| particle.lifetime > 0.0)
+                    .for_each(&mut push),
             }
         }
 
@@ -456,9 +780,77 @@ impl GpuParticleSystem {
         Ok(&self.render_data)
     }
 
+    /// Read back the sort entries of the last depth-sorted update, far to near
+    ///
+    /// Empty if that update did not sort.
+    pub async fn read_sort_entries(&self) -> Result<Vec<ParticleSortEntry>> {
+        if !self.sort_stats.sorted {
+            return Ok(Vec::new());
+        }
+        let entry_count = self.sort_stats.sorted_count.next_power_of_two() as usize;
+        let size = (std::mem::size_of::<ParticleSortEntry>() * entry_count) as u64;
+
+        let buffer_slice = self.sort_staging_buffer.slice(..size);
+        let (tx, rx) = futures::channel::oneshot::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            // The receiver only goes away if the caller stopped waiting
+            let _ = tx.send(result);
+        });
+
+        self.device.poll(wgpu::Maintain::Wait);
+
+        rx.await
+            .map_err(|_| {
+                anyhow!("Failed to receive particle sort mapping result - channel was closed")
+            })?
+            .map_err(|e| anyhow!("Failed to map particle sort buffer for reading: {:?}", e))?;
+
+        let entries = bytemuck::cast_slice(&buffer_slice.get_mapped_range()).to_vec();
+        self.sort_staging_buffer.unmap();
+        Ok(entries)
+    }
+
     /// Get particle count
     pub fn particle_count(&self) -> usize {
         self.active_particles as usize
     }
 
+    /// Depth sort work done by the last update
+    pub fn sort_stats(&self) -> ParticleSortStats {
+        self.sort_stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitonic_sort_orders_far_to_near() {
+        assert_eq!(bitonic_passes(4), vec![(1, 2), (2, 4), (1, 4)]);
+        assert!(bitonic_passes(64).starts_with(&bitonic_passes(32)));
+
+        // 13 live particles padded to 16 entries, with two dead ones mixed in
+        let distances = [
+            9.0, 1.0, 25.0, -1.0, 4.0, 16.0, 0.25, 49.0, 36.0, -1.0, 2.0, 81.0, 64.0,
+        ];
+        let mut entries: Vec<ParticleSortEntry> = (0..16)
+            .map(|index| ParticleSortEntry {
+                distance_sq: distances.get(index).copied().unwrap_or(-1.0),
+                index: index as u32,
+            })
+            .collect();
+
+        bitonic_sort_cpu(&mut entries);
+
+        assert!(entries
+            .windows(2)
+            .all(|pair| pair[0].distance_sq >= pair[1].distance_sq));
+        assert_eq!(entries[0].index, 11);
+        assert_eq!(entries.iter().filter(|e| e.distance_sq >= 0.0).count(), 11);
+
+        let mut indices: Vec<u32> = entries.iter().map(|e| e.index).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..16).collect::<Vec<_>>());
+    }
 }
@@ -31,10 +31,10 @@ pub use physics_operations::{create_physics_data, update_particle_physics};
 
 // Export existing data-oriented types
 pub use dop_system_operations::*;
-pub use gpu_particle_system::GpuParticleSystem;
-pub use particle_data::{EmitterData, ParticleData, ParticleGPUData, ParticlePool, MAX_PARTICLES, create_particle_data, create_emitter_data, clear_particle_data, clear_emitter_data, remove_particle_swap};
+pub use gpu_particle_system::{GpuParticleSystem, ParticleSortEntry, ParticleSortStats};
+pub use particle_data::{CollisionResponse, EmitterData, ParticleData, ParticleGPUData, ParticlePool, ParticleRng, MAX_PARTICLES, create_particle_data, create_particle_rng, create_emitter_data, clear_particle_data, clear_emitter_data, remove_particle_swap};
 pub use system_data::{DOPParticleSystem, ParticleStats};
-pub use update::{spawn_particle, update_emitters, update_particles};
+pub use update::{resolve_collisions, spawn_particle, update_emitters, update_particles};
 
 // Compatibility re-exports (temporary, can be removed after full migration)
 pub use particle_system_data::ParticleUpdateData as ParticleUpdate;
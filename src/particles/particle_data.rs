@@ -1,6 +1,17 @@
 /// Maximum number of particles that can exist
 pub const MAX_PARTICLES: usize = 1_000_000;
 
+/// What a particle does when its next position would be inside a solid block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionResponse {
+    /// Expire on contact
+    Die,
+    /// Reflect the blocked velocity, scaled by the particle's `bounce`
+    Bounce,
+    /// Drop the blocked velocity and keep moving along the surface
+    Slide,
+}
+
 /// Particle data stored in Structure of Arrays (SOA) layout for cache efficiency
 pub struct ParticleData {
     /// Current number of active particles
@@ -41,6 +52,7 @@ pub struct ParticleData {
     pub gravity_multiplier: Vec<f32>,
     pub drag: Vec<f32>,
     pub bounce: Vec<f32>,
+    pub collision_response: Vec<CollisionResponse>,
 
     /// Visual properties
     pub rotation: Vec<f32>,
@@ -111,6 +123,7 @@ pub fn create_particle_data(capacity: usize) -> ParticleData {
             gravity_multiplier: Vec::with_capacity(safe_capacity),
             drag: Vec::with_capacity(safe_capacity),
             bounce: Vec::with_capacity(safe_capacity),
+            collision_response: Vec::with_capacity(safe_capacity),
 
             rotation: Vec::with_capacity(safe_capacity),
             rotation_speed: Vec::with_capacity(safe_capacity),
@@ -161,6 +174,7 @@ pub fn clear_particle_data(data: &mut ParticleData) {
     data.gravity_multiplier.clear();
     data.drag.clear();
     data.bounce.clear();
+    data.collision_response.clear();
 
     data.rotation.clear();
     data.rotation_speed.clear();
@@ -214,6 +228,7 @@ pub fn remove_particle_swap(data: &mut ParticleData, index: usize) {
         data.gravity_multiplier.swap(index, last);
         data.drag.swap(index, last);
         data.bounce.swap(index, last);
+        data.collision_response.swap(index, last);
 
         data.rotation.swap(index, last);
         data.rotation_speed.swap(index, last);
@@ -260,6 +275,7 @@ pub fn remove_particle_swap(data: &mut ParticleData, index: usize) {
     data.gravity_multiplier.pop();
     data.drag.pop();
     data.bounce.pop();
+    data.collision_response.pop();
 
     data.rotation.pop();
     data.rotation_speed.pop();
@@ -315,6 +331,46 @@ pub fn reset_particle_pool(pool: &mut ParticlePool) {
     pool.next_free = 0;
 }
 
+/// Seeded random stream for particle spawning
+///
+/// SplitMix64, so the same seed yields the same spawn positions and velocity
+/// spread on every platform. Give each particle system its own stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParticleRng {
+    pub state: u64,
+}
+
+/// Create a random stream starting from `seed`
+pub fn create_particle_rng(seed: u64) -> ParticleRng {
+    ParticleRng { state: seed }
+}
+
+impl rand::RngCore for ParticleRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
 /// Emitter data in SOA layout
 pub struct EmitterData {
     /// Current number of active emitters
@@ -1,7 +1,9 @@
 use glam::Vec3;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
-use crate::particles::particle_data::{EmitterData, ParticleData, remove_particle_swap};
+use crate::particles::particle_data::{
+    remove_particle_swap, CollisionResponse, EmitterData, ParticleData, ParticleRng,
+};
 use crate::{BlockId, VoxelPos, World};
 
 /// Update all particles in the system
@@ -29,7 +31,7 @@ pub fn update_particles(
 
     // Handle collisions if enabled
     if collision_enabled {
-        handle_collisions(particles, world);
+        handle_collisions(particles, world, dt);
     }
 
     // Update visual properties
@@ -116,40 +118,94 @@ fn is_block_solid(block_id: BlockId) -> bool {
     }
 }
 
+/// How far in front of a face a particle blocked in the positive direction rests
+const FACE_EPSILON: f32 = 1e-4;
+
 /// Handle particle collisions with world
-pub fn handle_collisions(particles: &mut ParticleData, world: &World) {
+pub fn handle_collisions(particles: &mut ParticleData, world: &World, dt: f32) {
+    resolve_collisions(particles, dt, |voxel_pos| world.get_block(voxel_pos));
+}
+
+/// Keep particles out of solid blocks after `integrate_motion`
+///
+/// The step is replayed one axis at a time from the previous position, so
+/// only the axes that ran into a block are affected and a blocked particle
+/// ends up against the face it hit. `block_at` can be any block lookup; a
+/// `CpuBlockQuery` avoids a GPU round trip per particle.
+pub fn resolve_collisions(
+    particles: &mut ParticleData,
+    dt: f32,
+    block_at: impl Fn(VoxelPos) -> BlockId,
+) {
+    let voxel_of = |p: [f32; 3]| {
+        VoxelPos::new(
+            p[0].floor() as i32,
+            p[1].floor() as i32,
+            p[2].floor() as i32,
+        )
+    };
+
     for i in 0..particles.count {
-        let pos = Vec3::new(
+        let mut velocity = [
+            particles.velocity_x[i],
+            particles.velocity_y[i],
+            particles.velocity_z[i],
+        ];
+        let target = [
             particles.position_x[i],
             particles.position_y[i],
             particles.position_z[i],
-        );
-
-        // Convert to voxel position
-        let voxel_pos = VoxelPos::new(
-            pos.x.floor() as i32,
-            pos.y.floor() as i32,
-            pos.z.floor() as i32,
-        );
-
-        // Get block at position
-        let block_id = world.get_block(voxel_pos);
-
-        // Check if particle is inside a solid block
-        if is_block_solid(block_id) {
-            // Simple bounce back
-            let bounce = particles.bounce[i];
+        ];
+        let mut position = [
+            target[0] - velocity[0] * dt,
+            target[1] - velocity[1] * dt,
+            target[2] - velocity[2] * dt,
+        ];
+
+        let mut blocked = [false; 3];
+        for axis in 0..3 {
+            if velocity[axis] == 0.0 {
+                continue;
+            }
+            let mut next = position;
+            next[axis] = target[axis];
+            if is_block_solid(block_at(voxel_of(next))) {
+                blocked[axis] = true;
+                let entered = next[axis].floor();
+                position[axis] = if velocity[axis] < 0.0 {
+                    entered + 1.0
+                } else {
+                    entered - FACE_EPSILON
+                };
+            } else {
+                position = next;
+            }
+        }
 
-            // Reflect velocity
-            particles.velocity_x[i] *= -bounce;
-            particles.velocity_y[i] *= -bounce;
-            particles.velocity_z[i] *= -bounce;
+        if !blocked.contains(&true) {
+            continue;
+        }
 
-            // Move particle out of solid
-            particles.position_x[i] -= particles.velocity_x[i] * 0.016;
-            particles.position_y[i] -= particles.velocity_y[i] * 0.016;
-            particles.position_z[i] -= particles.velocity_z[i] * 0.016;
+        match particles.collision_response[i] {
+            CollisionResponse::Die => particles.lifetime[i] = 0.0,
+            CollisionResponse::Bounce => {
+                for axis in (0..3).filter(|&axis| blocked[axis]) {
+                    velocity[axis] *= -particles.bounce[i];
+                }
+            }
+            CollisionResponse::Slide => {
+                for axis in (0..3).filter(|&axis| blocked[axis]) {
+                    velocity[axis] = 0.0;
+                }
+            }
         }
+
+        particles.position_x[i] = position[0];
+        particles.position_y[i] = position[1];
+        particles.position_z[i] = position[2];
+        particles.velocity_x[i] = velocity[0];
+        particles.velocity_y[i] = velocity[1];
+        particles.velocity_z[i] = velocity[2];
     }
 }
 
@@ -270,14 +326,17 @@ fn temperature_to_color(temp: f32) -> (f32, f32, f32) {
 }
 
 /// Update emitters and spawn new particles
+///
+/// Spawn positions and velocity spread are drawn from `rng`, so two systems
+/// fed the same seed and the same steps spawn identical particles.
 pub fn update_emitters(
     emitters: &mut EmitterData,
     particles: &mut ParticleData,
     dt: f32,
     next_id: &mut u64,
+    rng: &mut ParticleRng,
 ) -> usize {
     let mut total_spawned = 0;
-    let mut rng = thread_rng();
 
     // Update each emitter
     let mut i = 0;
@@ -304,7 +363,7 @@ pub fn update_emitters(
             }
 
             // Generate spawn position based on shape
-            let spawn_pos = generate_spawn_position(emitters, i, &mut rng);
+            let spawn_pos = generate_spawn_position(emitters, i, rng);
 
             // Generate velocity
             let base_vel = Vec3::new(
@@ -427,6 +486,9 @@ pub fn spawn_particle(
     particles.gravity_multiplier.push(properties.gravity);
     particles.drag.push(properties.drag);
     particles.bounce.push(properties.bounce);
+    particles
+        .collision_response
+        .push(properties.collision_response);
 
     particles.rotation.push(properties.rotation);
     particles.rotation_speed.push(properties.rotation_speed);
@@ -476,6 +538,7 @@ struct ParticleDefaults {
     gravity: f32,
     drag: f32,
     bounce: f32,
+    collision_response: CollisionResponse,
     rotation: f32,
     rotation_speed: f32,
     texture_frame: u32,
@@ -499,6 +562,7 @@ fn get_particle_properties(particle_type: u32) -> ParticleDefaults {
             gravity: 2.0,
             drag: 0.1,
             bounce: 0.0,
+            collision_response: CollisionResponse::Die,
             rotation: 0.0,
             rotation_speed: 0.0,
             texture_frame: 0,
@@ -518,6 +582,7 @@ fn get_particle_properties(particle_type: u32) -> ParticleDefaults {
             gravity: 0.1,
             drag: 0.5,
             bounce: 0.0,
+            collision_response: CollisionResponse::Slide,
             rotation: 0.0,
             rotation_speed: 1.0,
             texture_frame: 0,
@@ -537,6 +602,7 @@ fn get_particle_properties(particle_type: u32) -> ParticleDefaults {
             gravity: -0.5,
             drag: 0.8,
             bounce: 0.0,
+            collision_response: CollisionResponse::Die,
             rotation: 0.0,
             rotation_speed: 0.0,
             texture_frame: 0,
@@ -556,6 +622,7 @@ fn get_particle_properties(particle_type: u32) -> ParticleDefaults {
             gravity: -0.2,
             drag: 0.5,
             bounce: 0.0,
+            collision_response: CollisionResponse::Slide,
             rotation: 0.0,
             rotation_speed: 0.5,
             texture_frame: 0,
@@ -575,6 +642,7 @@ fn get_particle_properties(particle_type: u32) -> ParticleDefaults {
             gravity: 1.0,
             drag: 0.0,
             bounce: 0.5,
+            collision_response: CollisionResponse::Bounce,
             rotation: 0.0,
             rotation_speed: 0.0,
             texture_frame: 0,
@@ -729,3 +797,95 @@ pub fn apply_turbulence(particles: &mut ParticleData, strength: f32, scale: f32,
         particles.acceleration_z[i] += noise_z * strength;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::particles::particle_data::{
+        create_emitter_data, create_particle_data, create_particle_rng,
+    };
+
+    fn push_sphere_emitter(emitters: &mut EmitterData) {
+        emitters.id.push(emitters.count as u64);
+        emitters.position_x.push(0.0);
+        emitters.position_y.push(10.0);
+        emitters.position_z.push(0.0);
+        emitters.emission_rate.push(120.0);
+        emitters.accumulated_particles.push(0.0);
+        emitters.particle_type.push(4);
+        emitters.elapsed_time.push(0.0);
+        emitters.duration.push(-1.0);
+        emitters.shape_type.push(1);
+        emitters.shape_param1.push(2.0);
+        emitters.shape_param2.push(0.0);
+        emitters.shape_param3.push(0.0);
+        emitters.base_velocity_x.push(0.0);
+        emitters.base_velocity_y.push(5.0);
+        emitters.base_velocity_z.push(0.0);
+        emitters.velocity_variance.push(3.0);
+        emitters.count += 1;
+    }
+
+    #[test]
+    fn test_same_seed_spawns_identical_particles() {
+        let dt = 1.0 / 60.0;
+        let run = |seed: u64| {
+            let mut emitters = create_emitter_data(1);
+            push_sphere_emitter(&mut emitters);
+            let mut particles = create_particle_data(256);
+            let mut rng = create_particle_rng(seed);
+            let mut next_id = 0;
+
+            let mut ticks = Vec::new();
+            for _ in 0..30 {
+                update_emitters(&mut emitters, &mut particles, dt, &mut next_id, &mut rng);
+                integrate_motion(&mut particles, dt);
+                ticks.push((
+                    particles.position_x.clone(),
+                    particles.position_y.clone(),
+                    particles.position_z.clone(),
+                    particles.velocity_x.clone(),
+                    particles.velocity_y.clone(),
+                    particles.velocity_z.clone(),
+                ));
+            }
+            ticks
+        };
+
+        let first = run(42);
+        assert!(first.last().is_some_and(|tick| !tick.0.is_empty()));
+        assert_eq!(first, run(42));
+        assert_ne!(first, run(43));
+    }
+
+    #[test]
+    fn test_falling_particle_stops_on_block() {
+        let floor = VoxelPos::new(0, 0, 0);
+        let block_at = |pos: VoxelPos| {
+            if pos == floor {
+                BlockId::STONE
+            } else {
+                BlockId::AIR
+            }
+        };
+        let dt = 1.0 / 60.0;
+
+        let mut particles = create_particle_data(4);
+        spawn_particle(&mut particles, Vec3::new(0.5, 3.0, 0.5), Vec3::ZERO, 5);
+        spawn_particle(&mut particles, Vec3::new(0.5, 3.0, 0.5), Vec3::ZERO, 5);
+        particles.collision_response[0] = CollisionResponse::Slide;
+        particles.collision_response[1] = CollisionResponse::Die;
+
+        for _ in 0..120 {
+            particles.acceleration_y.fill(0.0);
+            apply_gravity(&mut particles, dt);
+            integrate_motion(&mut particles, dt);
+            resolve_collisions(&mut particles, dt, block_at);
+        }
+
+        // Resting on the top face, not inside or below the block
+        assert_eq!(particles.position_y[0], 1.0);
+        assert_eq!(particles.velocity_y[0], 0.0);
+        assert_eq!(particles.lifetime[1], 0.0);
+    }
+}
@@ -7,6 +7,7 @@ pub mod chunk_serializer_data;
 pub mod compression_data;
 pub mod metadata_data;
 pub mod migration_data;
+pub mod modification_log_data;
 pub mod network_validator_data;
 pub mod player_data_dop;
 pub mod state_validator_data;
@@ -19,6 +20,7 @@ pub mod chunk_serializer_operations;
 pub mod compression_operations;
 pub mod metadata_operations;
 pub mod migration_operations;
+pub mod modification_log_operations;
 pub mod network_validator_operations;
 pub mod state_validator_operations;
 pub mod world_save_operations;
@@ -42,6 +44,10 @@ pub use migration_data::{
     MigrationData, MigrationManagerData, MigrationStep, MigrationSummary, MigrationType,
     MigrationValidatorData,
 };
+pub use modification_log_data::{
+    ModificationLogData, ModificationRecord, PlayerId, RollbackFilter, RollbackPreview,
+    RollbackResult, Timestamp,
+};
 pub use network_validator_data::{
     ChunkValidationData, NetworkValidatorData, PlayerValidationData, ValidationConfig as NetworkValidationConfig, 
     ValidationError as NetworkValidationError, ValidationResult as NetworkValidationResult, 
@@ -49,8 +55,8 @@ pub use network_validator_data::{
     WorldValidationState,
 };
 pub use player_data_dop::{
-    PlayerBufferMemoryStats, PlayerColdData, PlayerDataBuffer, PlayerHotData, CACHE_LINE_SIZE,
-    MAX_PLAYERS,
+    EvictedPlayer, PlayerBufferMemoryStats, PlayerColdData, PlayerDataBuffer,
+    PlayerEvictionPolicy, PlayerHotData, CACHE_LINE_SIZE, MAX_PLAYERS,
 };
 pub use state_validator_data::{
     StateSnapshot, StateValidatorData, ValidationConfig, ValidationError, ValidationResult,
@@ -65,6 +71,10 @@ pub use chunk_serializer_operations::{serialize_chunk, deserialize_chunk, analyz
 pub use compression_operations::{compress, decompress, analyze_data};
 pub use metadata_operations::{create_world_metadata, validate_metadata};
 pub use migration_operations::{create_migration_manager, migrate_world};
+pub use modification_log_operations::{
+    append_modification, compact_modification_log, create_modification_log,
+    open_modification_log, preview_rollback, rollback_by, rotate_modification_log,
+};
 pub use network_validator_operations::{create_network_validator, validate_chunk_save, validate_chunk_load};
 pub use state_validator_operations::{create_state_validator, validate_consistency};
 pub use world_save_operations::{create_world_save, load_world_save, save_world, save_chunk, load_chunk};
@@ -0,0 +1,91 @@
+//! Modification log data structures - Pure data, no methods
+//!
+//! Every world edit is appended to the log with a timestamp and the actor that
+//! caused it. The log is the source for rollbacks after griefing or accidents.
+
+use crate::world::core::{BlockId, VoxelPos};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Milliseconds since the Unix epoch
+pub type Timestamp = u64;
+
+/// Player identifier (matches `PlayerDataBuffer::player_ids`)
+pub type PlayerId = u32;
+
+/// Default size at which the on-disk log is rotated (64 MiB)
+pub const DEFAULT_MAX_LOG_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Number of rotated log files kept next to the active one
+pub const DEFAULT_ROTATED_LOGS: u32 = 4;
+
+/// A single world modification, stored as both sides of the edit so it can be undone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModificationRecord {
+    /// Monotonic sequence number within the log
+    pub sequence: u64,
+    /// When the edit was applied
+    pub timestamp: Timestamp,
+    /// Player that caused the edit (`None` for world/system edits)
+    pub actor: Option<PlayerId>,
+    /// Edited voxel
+    pub position: VoxelPos,
+    /// Block before the edit
+    pub old_block: BlockId,
+    /// Block after the edit
+    pub new_block: BlockId,
+}
+
+/// Append-only modification log
+#[derive(Debug, Clone)]
+pub struct ModificationLogData {
+    /// Records in append order
+    pub records: Vec<ModificationRecord>,
+    /// Next sequence number to assign
+    pub next_sequence: u64,
+    /// Active log file (`None` keeps the log in memory only)
+    pub path: Option<PathBuf>,
+    /// Bytes written to the active log file
+    pub file_bytes: u64,
+    /// Rotate the active file once it exceeds this size
+    pub max_file_bytes: u64,
+    /// Number of rotated files to keep
+    pub max_rotated_files: u32,
+}
+
+/// Selects which records a rollback reverses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollbackFilter {
+    /// Every edit at or after the timestamp
+    Since(Timestamp),
+    /// Every edit made by the player
+    Player(PlayerId),
+    /// Edits made by the player at or after the timestamp
+    PlayerSince(PlayerId, Timestamp),
+}
+
+/// What a rollback would do, computed without touching the world
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackPreview {
+    /// Number of edits that would be reversed
+    pub edit_count: usize,
+    /// Number of distinct voxels that would change
+    pub affected_voxels: usize,
+    /// Inclusive bounds of the affected region (`None` if nothing matches)
+    pub region_min: Option<VoxelPos>,
+    pub region_max: Option<VoxelPos>,
+    /// Sequence numbers of the matching records, newest first
+    pub sequences: Vec<u64>,
+}
+
+/// Outcome of an applied rollback
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackResult {
+    /// Edits successfully reversed
+    pub reverted: usize,
+    /// Edits that could not be written back (e.g. chunk not loaded)
+    pub failed: Vec<(VoxelPos, String)>,
+    /// Matching edits left alone because a newer edit outside the filter
+    /// changed the same voxel; reverting them would undo that edit too
+    pub conflicts: Vec<ModificationRecord>,
+}
@@ -0,0 +1,493 @@
+//! Modification log operations - Pure functions for logging and rolling back edits
+//!
+//! The on-disk format is a sequence of length-prefixed bincode records
+//! (`u32` little-endian length followed by the record). Appends never rewrite
+//! earlier data, so a crash can at most lose the record being written. A torn
+//! trailing record is cut off when the log is opened, so later appends start
+//! right after the last intact record.
+//!
+//! Rotation, compaction and rollback remove records, so the next sequence
+//! number is also kept in `<log>.seq`. Sequence numbers are never reused.
+
+use crate::persistence::modification_log_data::{
+    ModificationLogData, ModificationRecord, PlayerId, RollbackFilter, RollbackPreview,
+    RollbackResult, Timestamp, DEFAULT_MAX_LOG_BYTES, DEFAULT_ROTATED_LOGS,
+};
+use crate::persistence::{PersistenceError, PersistenceResult};
+use crate::world::core::{BlockId, VoxelPos};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Create an in-memory modification log
+pub fn create_modification_log() -> ModificationLogData {
+    ModificationLogData {
+        records: Vec::new(),
+        next_sequence: 0,
+        path: None,
+        file_bytes: 0,
+        max_file_bytes: DEFAULT_MAX_LOG_BYTES,
+        max_rotated_files: DEFAULT_ROTATED_LOGS,
+    }
+}
+
+/// Open (or create) a file-backed modification log, loading existing records
+pub fn open_modification_log(path: impl AsRef<Path>) -> PersistenceResult<ModificationLogData> {
+    let path = path.as_ref().to_path_buf();
+    let mut log = create_modification_log();
+
+    if path.exists() {
+        let bytes = fs::read(&path)?;
+        let (records, valid_bytes) = decode_records(&bytes)?;
+        if valid_bytes < bytes.len() {
+            log::warn!(
+                "[ModificationLog] Truncating {} bytes of torn trailing record",
+                bytes.len() - valid_bytes
+            );
+            OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_len(valid_bytes as u64)?;
+        }
+        log.records = records;
+        log.file_bytes = valid_bytes as u64;
+        log.next_sequence = log
+            .records
+            .last()
+            .map(|record| record.sequence + 1)
+            .unwrap_or(0);
+    } else if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Records may have been rotated or compacted away since the last append
+    let sequence_file = sequence_path(&path);
+    if sequence_file.exists() {
+        let bytes = fs::read(&sequence_file)?;
+        let stored: [u8; 8] = bytes.as_slice().try_into().map_err(|_| {
+            PersistenceError::CorruptedData("Invalid modification log sequence file".to_string())
+        })?;
+        log.next_sequence = log.next_sequence.max(u64::from_le_bytes(stored));
+    }
+
+    log.path = Some(path);
+    Ok(log)
+}
+
+/// Current wall-clock time as a log timestamp
+pub fn current_timestamp() -> Timestamp {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as Timestamp)
+        .unwrap_or(0)
+}
+
+/// Append a modification to the log, writing it through to disk if file-backed
+pub fn append_modification(
+    log: &mut ModificationLogData,
+    timestamp: Timestamp,
+    actor: Option<PlayerId>,
+    position: VoxelPos,
+    old_block: BlockId,
+    new_block: BlockId,
+) -> PersistenceResult<u64> {
+    let record = ModificationRecord {
+        sequence: log.next_sequence,
+        timestamp,
+        actor,
+        position,
+        old_block,
+        new_block,
+    };
+
+    if let Some(path) = log.path.clone() {
+        if log.file_bytes >= log.max_file_bytes {
+            rotate_modification_log(log)?;
+        }
+
+        let encoded = encode_record(&record)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(&encoded)?;
+        log.file_bytes += encoded.len() as u64;
+    }
+
+    log.records.push(record);
+    log.next_sequence += 1;
+    Ok(record.sequence)
+}
+
+/// Rotate the active log file to `<name>.1`, shifting older files up
+///
+/// Rotated files are kept for auditing but are no longer part of the
+/// in-memory log, so their edits can no longer be rolled back.
+pub fn rotate_modification_log(log: &mut ModificationLogData) -> PersistenceResult<()> {
+    let Some(path) = log.path.clone() else {
+        return Ok(());
+    };
+
+    if log.max_rotated_files == 0 {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    } else {
+        let oldest = rotated_path(&path, log.max_rotated_files);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (1..log.max_rotated_files).rev() {
+            let from = rotated_path(&path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&path, index + 1))?;
+            }
+        }
+        if path.exists() {
+            fs::rename(&path, rotated_path(&path, 1))?;
+        }
+    }
+
+    log.records.clear();
+    log.file_bytes = 0;
+    write_sequence(log)
+}
+
+/// Drop records older than `before`, rewriting the active file
+///
+/// Returns the number of records removed. Compacted edits can no longer be rolled back.
+pub fn compact_modification_log(
+    log: &mut ModificationLogData,
+    before: Timestamp,
+) -> PersistenceResult<usize> {
+    let original = log.records.len();
+    log.records.retain(|record| record.timestamp >= before);
+    let removed = original - log.records.len();
+
+    if removed > 0 {
+        rewrite_log_file(log)?;
+    }
+
+    Ok(removed)
+}
+
+/// Preview a rollback: how many edits it reverses and which region it touches
+pub fn preview_rollback(log: &ModificationLogData, filter: RollbackFilter) -> RollbackPreview {
+    let mut preview = RollbackPreview {
+        edit_count: 0,
+        affected_voxels: 0,
+        region_min: None,
+        region_max: None,
+        sequences: Vec::new(),
+    };
+    let mut voxels = HashSet::new();
+
+    for record in log.records.iter().rev().filter(|r| matches_filter(r, filter)) {
+        preview.edit_count += 1;
+        preview.sequences.push(record.sequence);
+        voxels.insert(record.position);
+
+        let p = record.position;
+        preview.region_min = Some(match preview.region_min {
+            Some(min) => VoxelPos::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+            None => p,
+        });
+        preview.region_max = Some(match preview.region_max {
+            Some(max) => VoxelPos::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            None => p,
+        });
+    }
+
+    preview.affected_voxels = voxels.len();
+    preview
+}
+
+/// Reverse every matching edit, newest first, writing through `set_block`
+///
+/// Reverted records are removed from the log. Edits that fail to apply are
+/// reported and kept so the rollback can be retried once their chunk is loaded;
+/// older matching edits of the same voxel are kept with them. A matching edit
+/// whose voxel was changed later by an edit outside the filter (e.g. another
+/// player building on top) is not reverted but reported as a conflict.
+pub fn rollback_by<E: std::fmt::Display>(
+    log: &mut ModificationLogData,
+    filter: RollbackFilter,
+    mut set_block: impl FnMut(VoxelPos, BlockId) -> Result<(), E>,
+) -> PersistenceResult<RollbackResult> {
+    let mut result = RollbackResult {
+        reverted: 0,
+        failed: Vec::new(),
+        conflicts: Vec::new(),
+    };
+    let mut reverted = HashSet::new();
+    // Voxels whose current block comes from a newer edit that stays in place
+    let mut kept_newer = HashSet::new();
+    let mut failed_newer = HashSet::new();
+
+    // Newest first, so each voxel ends at its oldest pre-edit block
+    for record in log.records.iter().rev() {
+        if !matches_filter(record, filter) {
+            kept_newer.insert(record.position);
+            continue;
+        }
+        if kept_newer.contains(&record.position) {
+            result.conflicts.push(*record);
+            continue;
+        }
+        if failed_newer.contains(&record.position) {
+            result.failed.push((
+                record.position,
+                "newer edit of this voxel was not reverted".to_string(),
+            ));
+            continue;
+        }
+        match set_block(record.position, record.old_block) {
+            Ok(()) => {
+                result.reverted += 1;
+                reverted.insert(record.sequence);
+            }
+            Err(e) => {
+                failed_newer.insert(record.position);
+                result.failed.push((record.position, e.to_string()));
+            }
+        }
+    }
+
+    if !reverted.is_empty() {
+        log.records
+            .retain(|record| !reverted.contains(&record.sequence));
+        rewrite_log_file(log)?;
+    }
+
+    Ok(result)
+}
+
+fn matches_filter(record: &ModificationRecord, filter: RollbackFilter) -> bool {
+    match filter {
+        RollbackFilter::Since(since) => record.timestamp >= since,
+        RollbackFilter::Player(player) => record.actor == Some(player),
+        RollbackFilter::PlayerSince(player, since) => {
+            record.actor == Some(player) && record.timestamp >= since
+        }
+    }
+}
+
+/// Rewrite the active file from the in-memory records after removing some
+fn rewrite_log_file(log: &mut ModificationLogData) -> PersistenceResult<()> {
+    let Some(path) = log.path.clone() else {
+        return Ok(());
+    };
+
+    let mut bytes = Vec::new();
+    for record in &log.records {
+        bytes.extend_from_slice(&encode_record(record)?);
+    }
+    crate::persistence::atomic_write(&path, &bytes)?;
+    log.file_bytes = bytes.len() as u64;
+    write_sequence(log)
+}
+
+/// Persist the next sequence number, which the remaining records may no longer imply
+fn write_sequence(log: &ModificationLogData) -> PersistenceResult<()> {
+    match &log.path {
+        Some(path) => {
+            crate::persistence::atomic_write(sequence_path(path), &log.next_sequence.to_le_bytes())
+        }
+        None => Ok(()),
+    }
+}
+
+fn sequence_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".seq");
+    PathBuf::from(name)
+}
+
+fn rotated_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+fn encode_record(record: &ModificationRecord) -> PersistenceResult<Vec<u8>> {
+    let body = bincode::serialize(record)?;
+    let mut bytes = Vec::with_capacity(4 + body.len());
+    bytes.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Decode every intact record, returning them with the length of the intact prefix
+///
+/// A torn final write (short header or short body) loses only the last record.
+fn decode_records(bytes: &[u8]) -> PersistenceResult<(Vec<ModificationRecord>, usize)> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let Some(len_bytes) = bytes.get(offset..offset + 4) else {
+            break;
+        };
+        let len =
+            u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]) as usize;
+        let Some(body) = bytes.get(offset + 4..offset + 4 + len) else {
+            break;
+        };
+        let record = bincode::deserialize(body)
+            .map_err(|e| PersistenceError::DeserializationError(e.to_string()))?;
+        records.push(record);
+        offset += 4 + len;
+    }
+    Ok((records, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn log_with_edits() -> ModificationLogData {
+        let mut log = create_modification_log();
+        append_modification(&mut log, 100, Some(1), VoxelPos::new(0, 0, 0), BlockId::AIR, BlockId::STONE)
+            .expect("append should succeed");
+        append_modification(&mut log, 200, Some(2), VoxelPos::new(5, 1, 0), BlockId::DIRT, BlockId::AIR)
+            .expect("append should succeed");
+        append_modification(&mut log, 300, Some(1), VoxelPos::new(0, 0, 0), BlockId::STONE, BlockId::GLASS)
+            .expect("append should succeed");
+        log
+    }
+
+    #[test]
+    fn test_preview_by_player() {
+        let log = log_with_edits();
+        let preview = preview_rollback(&log, RollbackFilter::Player(1));
+
+        assert_eq!(preview.edit_count, 2);
+        assert_eq!(preview.affected_voxels, 1);
+        assert_eq!(preview.sequences, vec![2, 0]);
+    }
+
+    #[test]
+    fn test_preview_since_reports_region() {
+        let log = log_with_edits();
+        let preview = preview_rollback(&log, RollbackFilter::Since(200));
+
+        assert_eq!(preview.edit_count, 2);
+        assert_eq!(preview.region_min, Some(VoxelPos::new(0, 0, 0)));
+        assert_eq!(preview.region_max, Some(VoxelPos::new(5, 1, 0)));
+    }
+
+    #[test]
+    fn test_file_log_round_trip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("modifications.log");
+
+        let mut log = open_modification_log(&path).expect("open");
+        append_modification(&mut log, 1, None, VoxelPos::new(1, 2, 3), BlockId::AIR, BlockId::SAND)
+            .expect("append should succeed");
+
+        let reopened = open_modification_log(&path).expect("reopen");
+        assert_eq!(reopened.records, log.records);
+        assert_eq!(reopened.next_sequence, 1);
+    }
+
+    #[test]
+    fn test_torn_tail_is_truncated_on_open() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("modifications.log");
+
+        let mut log = open_modification_log(&path).expect("open");
+        for x in 0..3 {
+            append_modification(&mut log, 1, None, VoxelPos::new(x, 0, 0), BlockId::AIR, BlockId::SAND)
+                .expect("append should succeed");
+        }
+        let full = fs::read(&path).expect("read log");
+        let record_len = full.len() / 3;
+
+        for chop in 1..full.len() {
+            let kept = full.len() - chop;
+            fs::write(&path, &full[..kept]).expect("write torn log");
+            let _ = fs::remove_file(sequence_path(&path));
+            let intact = kept / record_len;
+
+            let mut reopened = open_modification_log(&path).expect("reopen torn log");
+            assert_eq!(reopened.records, log.records[..intact]);
+            assert_eq!(reopened.file_bytes as usize, intact * record_len);
+            append_modification(&mut reopened, 2, None, VoxelPos::new(9, 9, 9), BlockId::AIR, BlockId::DIRT)
+                .expect("append should succeed");
+
+            let again = open_modification_log(&path).expect("reopen after append");
+            assert_eq!(again.records.len(), intact + 1);
+            assert_eq!(again.records[..intact], log.records[..intact]);
+            assert_eq!(again.records[intact].position, VoxelPos::new(9, 9, 9));
+        }
+    }
+
+    #[test]
+    fn test_player_rollback_skips_voxels_edited_later_by_others() {
+        let mut log = log_with_edits();
+        // Player 2 builds on the voxel player 1 placed glass at
+        append_modification(&mut log, 400, Some(2), VoxelPos::new(0, 0, 0), BlockId::GLASS, BlockId::SAND)
+            .expect("append should succeed");
+        append_modification(&mut log, 500, Some(1), VoxelPos::new(9, 9, 9), BlockId::AIR, BlockId::DIRT)
+            .expect("append should succeed");
+
+        let mut world = HashMap::from([
+            (VoxelPos::new(0, 0, 0), BlockId::SAND),
+            (VoxelPos::new(9, 9, 9), BlockId::DIRT),
+        ]);
+        let result = rollback_by(&mut log, RollbackFilter::Player(1), |pos, block| {
+            world.insert(pos, block);
+            Ok::<(), String>(())
+        })
+        .expect("rollback");
+
+        assert_eq!(result.reverted, 1);
+        assert!(result.failed.is_empty());
+        let conflicts: Vec<u64> = result.conflicts.iter().map(|r| r.sequence).collect();
+        assert_eq!(conflicts, vec![2, 0]);
+        assert_eq!(world[&VoxelPos::new(0, 0, 0)], BlockId::SAND);
+        assert_eq!(world[&VoxelPos::new(9, 9, 9)], BlockId::AIR);
+
+        // Conflicting edits stay in the log; the reverted one is gone
+        let remaining: Vec<u64> = log.records.iter().map(|r| r.sequence).collect();
+        assert_eq!(remaining, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_failed_rollback_keeps_records() {
+        let mut log = log_with_edits();
+        let result = rollback_by(&mut log, RollbackFilter::Since(0), |_, _| {
+            Err("chunk not loaded")
+        })
+        .expect("rollback");
+
+        assert_eq!(result.reverted, 0);
+        assert_eq!(result.failed.len(), 3);
+        assert_eq!(log.records.len(), 3);
+    }
+
+    #[test]
+    fn test_sequences_survive_rotate_compact_and_reopen() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("modifications.log");
+
+        let mut log = open_modification_log(&path).expect("open");
+        for timestamp in [10, 20, 30] {
+            append_modification(&mut log, timestamp, None, VoxelPos::new(0, 0, 0), BlockId::AIR, BlockId::SAND)
+                .expect("append should succeed");
+        }
+
+        assert_eq!(compact_modification_log(&mut log, 20).expect("compact"), 1);
+        let reopened = open_modification_log(&path).expect("reopen");
+        assert_eq!(reopened.records, log.records);
+        assert_eq!(reopened.next_sequence, 3);
+
+        rotate_modification_log(&mut log).expect("rotate");
+        assert!(log.records.is_empty());
+        assert!(rotated_path(&path, 1).exists());
+
+        let mut reopened = open_modification_log(&path).expect("reopen");
+        assert!(reopened.records.is_empty());
+        let sequence = append_modification(&mut reopened, 40, None, VoxelPos::new(1, 0, 0), BlockId::AIR, BlockId::DIRT)
+            .expect("append should succeed");
+        assert_eq!(sequence, 3);
+    }
+}
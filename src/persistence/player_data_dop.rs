@@ -18,6 +18,24 @@ pub const CACHE_LINE_SIZE: usize = 64;
 /// Number of cache lines to reserve for hot player data
 pub const HOT_DATA_CACHE_LINES: usize = 4;
 
+/// What `add_player` does when every slot is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayerEvictionPolicy {
+    /// Refuse the new player
+    #[default]
+    Reject,
+    /// Move the least-recently-active player's hot data to cold storage
+    LeastRecentlyActive,
+}
+
+/// Hot data of a player evicted from the buffer
+#[derive(Debug, Clone, Copy)]
+pub struct EvictedPlayer {
+    pub hot_data: PlayerHotData,
+    /// Activity tick of the player's last update before eviction
+    pub last_active: u64,
+}
+
 /// Player hot data - frequently accessed together for cache efficiency
 /// Layout fits in two cache lines (128 bytes total with alignment)
 /// First cache line: position + velocity + rotation + health/hunger
@@ -139,11 +157,26 @@ pub struct PlayerDataBuffer {
     /// Player ID mapping (hot path for lookups)
     pub player_ids: Vec<u32>,
 
+    /// Activity tick of the last update to each slot (LRU eviction order)
+    pub last_active: Vec<u64>,
+
     /// Cold data storage (separate allocation pattern)
     pub cold_data: HashMap<u32, PlayerColdData>,
 
+    /// Hot data of players evicted to make room, keyed by player ID
+    pub evicted_hot_data: HashMap<u32, EvictedPlayer>,
+
     /// Free slot indices for efficient allocation
     pub free_slots: Vec<usize>,
+
+    /// Behaviour when the buffer is full
+    pub eviction_policy: PlayerEvictionPolicy,
+    /// Monotonic counter stamped into `last_active`
+    pub activity_tick: u64,
+    /// Players evicted since creation
+    pub eviction_count: u64,
+    /// Evicted players reloaded since creation
+    pub reload_count: u64,
 }
 
 impl Default for PlayerHotData {
@@ -166,6 +199,9 @@ impl Default for PlayerHotData {
 
 impl PlayerDataBuffer {
     /// Create a new player data buffer with specified capacity
+    ///
+    /// A full buffer rejects new players unless a different eviction policy
+    /// is set with `with_eviction_policy`.
     pub fn new(capacity: usize) -> Self {
         let safe_capacity = capacity.min(MAX_PLAYERS);
 
@@ -198,19 +234,143 @@ impl PlayerDataBuffer {
             dirty_flags: Vec::with_capacity(safe_capacity),
 
             player_ids: Vec::with_capacity(safe_capacity),
+            last_active: Vec::with_capacity(safe_capacity),
 
             cold_data: HashMap::with_capacity(safe_capacity),
+            evicted_hot_data: HashMap::new(),
             free_slots: Vec::new(),
+
+            eviction_policy: PlayerEvictionPolicy::default(),
+            activity_tick: 0,
+            eviction_count: 0,
+            reload_count: 0,
         }
     }
 
+    /// Set what `add_player` does when every slot is taken
+    pub fn with_eviction_policy(mut self, policy: PlayerEvictionPolicy) -> Self {
+        self.eviction_policy = policy;
+        self
+    }
+
     /// Add a new player to the buffer, returns index
+    ///
+    /// When the buffer is full, the eviction policy decides whether the
+    /// least-recently-active player is moved to cold storage to make room.
+    /// A player that was evicted is reloaded with their stored hot and cold
+    /// data, and `hot_data`/`cold_data` are ignored.
     pub fn add_player(
         &mut self,
         player_id: u32,
         hot_data: PlayerHotData,
         cold_data: PlayerColdData,
     ) -> Option<usize> {
+        if self.is_evicted(player_id) {
+            return self.load_player(player_id);
+        }
+
+        let index = self.allocate_slot()?;
+        self.write_hot_data(index, player_id, &hot_data);
+
+        // Store cold data separately
+        self.cold_data.insert(player_id, cold_data);
+
+        Some(index)
+    }
+
+    /// Find a player's slot, reloading them from cold storage if they were evicted
+    pub fn load_player(&mut self, player_id: u32) -> Option<usize> {
+        if let Some(index) = self.find_player(player_id) {
+            self.touch(index);
+            return Some(index);
+        }
+
+        let evicted = *self.evicted_hot_data.get(&player_id)?;
+        let index = self.allocate_slot()?;
+        self.evicted_hot_data.remove(&player_id);
+        self.write_hot_data(index, player_id, &evicted.hot_data);
+        self.reload_count += 1;
+        Some(index)
+    }
+
+    /// Drop the stored hot and cold data of a player that is not in the buffer
+    ///
+    /// Returns whether anything was removed. Players holding a slot are
+    /// removed with `remove_player` instead.
+    pub fn forget_player(&mut self, player_id: u32) -> bool {
+        if self.find_player(player_id).is_some() {
+            return false;
+        }
+        let evicted = self.evicted_hot_data.remove(&player_id).is_some();
+        let cold = self.cold_data.remove(&player_id).is_some();
+        evicted || cold
+    }
+
+    /// Forget evicted players idle for more than `max_idle_ticks` activity ticks
+    ///
+    /// Returns the IDs of the expired players.
+    pub fn expire_evicted(&mut self, max_idle_ticks: u64) -> Vec<u32> {
+        let cutoff = self.activity_tick.saturating_sub(max_idle_ticks);
+        let expired: Vec<u32> = self
+            .evicted_hot_data
+            .iter()
+            .filter(|(_, evicted)| evicted.last_active < cutoff)
+            .map(|(&player_id, _)| player_id)
+            .collect();
+
+        for player_id in &expired {
+            self.evicted_hot_data.remove(player_id);
+            self.cold_data.remove(player_id);
+        }
+        expired
+    }
+
+    /// Whether a player's hot data is currently in cold storage
+    pub fn is_evicted(&self, player_id: u32) -> bool {
+        self.evicted_hot_data.contains_key(&player_id)
+    }
+
+    /// Record activity for a player so eviction prefers idle players
+    pub fn touch(&mut self, index: usize) {
+        if index < self.count && self.player_ids[index] != u32::MAX {
+            self.activity_tick += 1;
+            self.last_active[index] = self.activity_tick;
+        }
+    }
+
+    /// Move the least-recently-active player's hot data to cold storage
+    ///
+    /// The freed slot goes on the free list. Returns it, or `None` if the
+    /// buffer holds no players.
+    pub fn evict_least_recently_active(&mut self) -> Option<usize> {
+        let index = (0..self.count)
+            .filter(|&i| self.player_ids[i] != u32::MAX)
+            .min_by_key(|&i| self.last_active[i])?;
+        let player_id = self.player_ids[index];
+        let hot_data = self.get_hot_data(index)?;
+
+        self.evicted_hot_data.insert(
+            player_id,
+            EvictedPlayer {
+                hot_data,
+                last_active: self.last_active[index],
+            },
+        );
+        self.player_ids[index] = u32::MAX;
+        self.free_slots.push(index);
+        self.eviction_count += 1;
+        Some(index)
+    }
+
+    /// Pick a slot for a new or reloaded player, evicting if the policy allows
+    fn allocate_slot(&mut self) -> Option<usize> {
+        if self.free_slots.is_empty()
+            && self.count >= self.capacity
+            && self.eviction_policy == PlayerEvictionPolicy::LeastRecentlyActive
+        {
+            self.evict_least_recently_active()?;
+        }
+
         // Use free slot if available, otherwise append
         let index = if let Some(slot) = self.free_slots.pop() {
             slot
@@ -224,8 +384,11 @@ impl PlayerDataBuffer {
 
         // Ensure vectors are large enough
         self.ensure_capacity(index + 1);
+        Some(index)
+    }
 
-        // Insert hot data into SOA buffers
+    /// Write hot data into the SOA buffers at `index`
+    fn write_hot_data(&mut self, index: usize, player_id: u32, hot_data: &PlayerHotData) {
         self.player_ids[index] = player_id;
         self.position_x[index] = hot_data.position.x;
         self.position_y[index] = hot_data.position.y;
@@ -250,10 +413,7 @@ impl PlayerDataBuffer {
         self.movement_state[index] = hot_data.movement_state;
         self.dirty_flags[index] = hot_data.dirty_flags;
 
-        // Store cold data separately
-        self.cold_data.insert(player_id, cold_data);
-
-        Some(index)
+        self.touch(index);
     }
 
     /// Remove player from buffer
@@ -263,6 +423,9 @@ impl PlayerDataBuffer {
         }
 
         let player_id = self.player_ids[index];
+        if player_id == u32::MAX {
+            return;
+        }
 
         // Remove cold data
         self.cold_data.remove(&player_id);
@@ -315,6 +478,7 @@ impl PlayerDataBuffer {
             self.position_y[index] = position.y;
             self.position_z[index] = position.z;
             self.dirty_flags[index] |= DIRTY_POSITION;
+            self.touch(index);
         }
     }
 
@@ -325,6 +489,7 @@ impl PlayerDataBuffer {
             self.velocity_y[index] = velocity.y;
             self.velocity_z[index] = velocity.z;
             self.dirty_flags[index] |= DIRTY_VELOCITY;
+            self.touch(index);
         }
     }
 
@@ -333,6 +498,7 @@ impl PlayerDataBuffer {
         if index < self.count && self.player_ids[index] != u32::MAX {
             self.health[index] = health;
             self.dirty_flags[index] |= DIRTY_HEALTH;
+            self.touch(index);
         }
     }
 
@@ -404,7 +570,8 @@ impl PlayerDataBuffer {
             .cold_data
             .iter()
             .map(|(_, data)| estimate_cold_data_size(data))
-            .sum::<usize>();
+            .sum::<usize>()
+            + self.evicted_hot_data.len() * std::mem::size_of::<PlayerHotData>();
 
         PlayerBufferMemoryStats {
             hot_data_bytes: hot_data_size,
@@ -413,6 +580,9 @@ impl PlayerDataBuffer {
             active_players: self.count - self.free_slots.len(),
             capacity: self.capacity,
             cache_lines_used: (hot_data_size + CACHE_LINE_SIZE - 1) / CACHE_LINE_SIZE,
+            evicted_players: self.evicted_hot_data.len(),
+            eviction_count: self.eviction_count,
+            reload_count: self.reload_count,
         }
     }
 
@@ -443,6 +613,7 @@ impl PlayerDataBuffer {
             self.dirty_flags.resize(size, 0);
 
             self.player_ids.resize(size, u32::MAX);
+            self.last_active.resize(size, 0);
         }
     }
 }
@@ -466,6 +637,12 @@ pub struct PlayerBufferMemoryStats {
     pub active_players: usize,
     pub capacity: usize,
     pub cache_lines_used: usize,
+    /// Players whose hot data is currently in cold storage
+    pub evicted_players: usize,
+    /// Players evicted since the buffer was created
+    pub eviction_count: u64,
+    /// Evicted players reloaded since the buffer was created
+    pub reload_count: u64,
 }
 
 impl PlayerBufferMemoryStats {
@@ -592,6 +769,143 @@ mod tests {
         assert_eq!(updated_data.position, Vec3::new(0.1, 0.2, 0.3));
     }
 
+    fn cold(name: &str) -> PlayerColdData {
+        PlayerColdData {
+            uuid: format!("{}-uuid", name),
+            username: name.to_string(),
+            spawn_position: None,
+            last_login: 0,
+            play_time: 0,
+            stats: PlayerStatsData::default(),
+            effects: Vec::new(),
+            achievements: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_full_buffer_rejects_by_default() {
+        let mut buffer = PlayerDataBuffer::new(1);
+        assert_eq!(buffer.eviction_policy, PlayerEvictionPolicy::Reject);
+
+        buffer
+            .add_player(1, PlayerHotData::default(), cold("First"))
+            .expect("[Test] Failed to add first player");
+        assert_eq!(
+            buffer.add_player(2, PlayerHotData::default(), cold("Second")),
+            None
+        );
+        assert!(buffer.find_player(1).is_some());
+    }
+
+    #[test]
+    fn test_full_buffer_evicts_least_recently_active() {
+        let mut buffer = PlayerDataBuffer::new(2)
+            .with_eviction_policy(PlayerEvictionPolicy::LeastRecentlyActive);
+
+        let first = buffer
+            .add_player(1, PlayerHotData::default(), cold("First"))
+            .expect("[Test] Failed to add first player");
+        let second = buffer
+            .add_player(2, PlayerHotData::default(), cold("Second"))
+            .expect("[Test] Failed to add second player");
+        buffer.update_position(first, Vec3::new(5.0, 64.0, -3.0));
+        buffer.update_health(second, 7.5);
+        buffer.update_position(first, Vec3::new(6.0, 64.0, -3.0));
+
+        // Player 2 was active least recently
+        let third = buffer
+            .add_player(3, PlayerHotData::default(), cold("Third"))
+            .expect("[Test] Eviction should free a slot");
+        assert_eq!(third, second);
+        assert!(buffer.is_evicted(2));
+        assert_eq!(buffer.find_player(2), None);
+        assert!(buffer.get_cold_data(2).is_some());
+
+        // Rejoining evicts player 1 and restores player 2 unchanged
+        let reloaded = buffer
+            .load_player(2)
+            .expect("[Test] Failed to reload evicted player");
+        let hot = buffer
+            .get_hot_data(reloaded)
+            .expect("[Test] Failed to get reloaded hot data");
+        assert_eq!(hot.health, 7.5);
+        assert!(!buffer.is_evicted(2));
+        assert!(buffer.is_evicted(1));
+
+        let stats = buffer.memory_usage();
+        assert_eq!(stats.eviction_count, 2);
+        assert_eq!(stats.reload_count, 1);
+        assert_eq!(stats.evicted_players, 1);
+
+        buffer.eviction_policy = PlayerEvictionPolicy::Reject;
+        assert_eq!(
+            buffer.add_player(4, PlayerHotData::default(), cold("Fourth")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rejoining_evicted_player_keeps_their_state() {
+        let mut buffer = PlayerDataBuffer::new(1)
+            .with_eviction_policy(PlayerEvictionPolicy::LeastRecentlyActive);
+
+        let first = buffer
+            .add_player(1, PlayerHotData::default(), cold("First"))
+            .expect("[Test] Failed to add first player");
+        buffer.update_health(first, 3.0);
+        if let Some(data) = buffer.get_cold_data_mut(1) {
+            data.play_time = 120;
+        }
+        buffer
+            .add_player(2, PlayerHotData::default(), cold("Second"))
+            .expect("[Test] Eviction should free a slot");
+        assert!(buffer.is_evicted(1));
+
+        // A rejoin through add_player restores the evicted state, not fresh defaults
+        let rejoined = buffer
+            .add_player(1, PlayerHotData::default(), cold("First"))
+            .expect("[Test] Failed to re-add evicted player");
+        let hot = buffer
+            .get_hot_data(rejoined)
+            .expect("[Test] Failed to get rejoined hot data");
+        assert_eq!(hot.health, 3.0);
+        assert_eq!(
+            buffer.get_cold_data(1).map(|data| data.play_time),
+            Some(120)
+        );
+        assert!(!buffer.is_evicted(1));
+        assert_eq!(buffer.memory_usage().reload_count, 1);
+    }
+
+    #[test]
+    fn test_forget_and_expire_evicted_players() {
+        let mut buffer = PlayerDataBuffer::new(1)
+            .with_eviction_policy(PlayerEvictionPolicy::LeastRecentlyActive);
+
+        for player_id in 1..=3 {
+            buffer
+                .add_player(player_id, PlayerHotData::default(), cold("Player"))
+                .expect("[Test] Failed to add player");
+        }
+        assert!(buffer.is_evicted(1));
+        assert!(buffer.is_evicted(2));
+
+        // Resident players are not forgotten
+        assert!(!buffer.forget_player(3));
+        assert!(buffer.forget_player(2));
+        assert!(!buffer.is_evicted(2));
+        assert!(buffer.get_cold_data(2).is_none());
+        assert!(!buffer.forget_player(2));
+
+        // Player 1 was last active at tick 1, the buffer is now at tick 3
+        assert!(buffer.expire_evicted(5).is_empty());
+        assert_eq!(buffer.expire_evicted(1), vec![1]);
+        assert!(!buffer.is_evicted(1));
+        assert!(buffer.get_cold_data(1).is_none());
+        assert!(buffer.get_cold_data(3).is_some());
+    }
+
     #[test]
     fn test_memory_stats() {
         let buffer = PlayerDataBuffer::new(100);
@@ -22,9 +22,13 @@ pub mod visual_indicators_operations;
 pub use parallel_processor_data::ParallelProcessorData;
 pub use parallel_processor_data::ProcessBatch;
 pub use parallel_processor_operations::{create_parallel_processor_data, submit_process_batch_to_gpu};
+pub use error::ProcessResult;
 pub use process_control::{InterruptReason, ProcessControl};
-pub use process_data::{ProcessData, ProcessId, ProcessStatus, ProcessType};
-pub use process_executor::{ExecutionResult, ProcessExecutor};
+pub use process_data::{
+    ProcessChain, ProcessData, ProcessIO, ProcessId, ProcessStatus, ProcessType,
+    MAX_PROCESS_SPEED, MIN_PROCESS_SPEED,
+};
+pub use process_executor::{ExecutionResult, ProcessExecutor, QualityCurve};
 pub use state_machine::{ProcessState, StateMachine, StateTransition, TransitionAction};
 pub use transform_stage_data::{
     ActualOutput, OutputType, StageOutput, StageRequirement, TransformStage,
@@ -44,6 +48,7 @@ pub use visual_indicators_operations::{
     quality_to_visual, generate_progress_bar_vertices,
 };
 
+use crate::error::EngineError;
 use crate::instance::InstanceId;
 use serde::{Deserialize, Serialize};
 
@@ -113,6 +118,9 @@ pub struct ProcessManager {
     /// Process data tables
     pub processes: ProcessData,
 
+    /// Input/output instance storage
+    pub io: ProcessIO,
+
     /// State machines for each process
     pub state_machines: Vec<StateMachine>,
 
@@ -127,18 +135,22 @@ pub struct ProcessManager {
 
     /// Parallel processor data for batch updates
     pub parallel_data: ParallelProcessorData,
-    
+
     /// GPU thread pool for command submission
     pub gpu_thread_pool: crate::thread_pool::GpuThreadPoolData,
 
     /// Control system for interrupts
     pub control: ProcessControl,
+
+    /// Chains that could not be started, until taken with `take_chain_failures`
+    chain_failures: Vec<(ProcessId, EngineError)>,
 }
 
 impl ProcessManager {
     pub fn new() -> Result<Self, crate::error::EngineError> {
         Ok(Self {
             processes: ProcessData::new(),
+            io: ProcessIO::new(),
             state_machines: Vec::with_capacity(MAX_PROCESSES),
             transform_stages: Vec::with_capacity(MAX_PROCESSES),
             visuals: Vec::with_capacity(MAX_PROCESSES),
@@ -148,6 +160,7 @@ impl ProcessManager {
                 crate::thread_pool::GpuThreadPoolConfig::default()
             ).map_err(|e| crate::error::EngineError::InitializationError(e))?,
             control: ProcessControl::new(),
+            chain_failures: Vec::new(),
         })
     }
 
@@ -164,6 +177,10 @@ impl ProcessManager {
             .processes
             .add(id, process_type, owner, duration.to_ticks());
 
+        let (input_start, input_count) = self.io.add_inputs(inputs);
+        self.processes.input_start[index] = input_start;
+        self.processes.input_count[index] = input_count;
+
         // Initialize state machine
         self.state_machines.push(StateMachine::new());
 
@@ -176,7 +193,224 @@ impl ProcessManager {
         id
     }
 
+    /// Queue a follow-up process to start when `first` completes
+    ///
+    /// The follow-up receives `first`'s outputs followed by `inputs`.
+    pub fn chain_process(
+        &mut self,
+        first: ProcessId,
+        next: ProcessType,
+        inputs: Vec<InstanceId>,
+        duration: TimeUnit,
+    ) -> ProcessResult<()> {
+        let index = self
+            .processes
+            .find_index(first)
+            .ok_or_else(|| error::process_not_found(first.0))?;
+
+        match self.processes.status[index] {
+            ProcessStatus::Completed | ProcessStatus::Failed | ProcessStatus::Cancelled => {
+                return Err(error::process_update_error(
+                    first.0,
+                    "process already finished",
+                ));
+            }
+            _ => {}
+        }
+        if self.processes.chains[index].is_some() {
+            return Err(error::process_update_error(
+                first.0,
+                "process already has a chained follow-up",
+            ));
+        }
+
+        self.processes.chains[index] = Some(ProcessChain {
+            process_type: next,
+            inputs,
+            duration: duration.to_ticks(),
+            child: None,
+        });
+
+        Ok(())
+    }
+
+    /// Record the instances a process produces
+    ///
+    /// Must be called before the process completes. A process completing
+    /// without recorded outputs passes its inputs on as its outputs, the
+    /// instances having been transformed in place.
+    pub fn set_outputs(&mut self, id: ProcessId, outputs: Vec<InstanceId>) -> ProcessResult<()> {
+        let index = self
+            .processes
+            .find_index(id)
+            .ok_or_else(|| error::process_not_found(id.0))?;
+
+        if !self.processes.active[index] {
+            return Err(error::process_update_error(
+                id.0,
+                "process already finished",
+            ));
+        }
+
+        let (output_start, output_count) = self.io.add_outputs(outputs);
+        self.processes.output_start[index] = output_start;
+        self.processes.output_count[index] = output_count;
+        Ok(())
+    }
+
+    /// Record what the output quality of a process is computed from
+    ///
+    /// `input_qualities` are the qualities of the consumed inputs and
+    /// `failed_optional` the number of optional stage requirements the
+    /// validator reported as unmet. The quality is computed when the process
+    /// completes (see `ProcessExecutor::compute_output_quality`).
+    pub fn set_quality_inputs(
+        &mut self,
+        id: ProcessId,
+        input_qualities: Vec<QualityLevel>,
+        failed_optional: u32,
+    ) -> ProcessResult<()> {
+        let index = self
+            .processes
+            .find_index(id)
+            .ok_or_else(|| error::process_not_found(id.0))?;
+
+        if !self.processes.active[index] {
+            return Err(error::process_update_error(
+                id.0,
+                "process already finished",
+            ));
+        }
+
+        self.processes.input_qualities[index] = input_qualities;
+        self.processes.failed_optional[index] = failed_optional;
+        Ok(())
+    }
+
+    /// Chains that could not be started since the last call, keyed by parent
+    ///
+    /// A chain fails when its parent failed or was cancelled, or when the
+    /// owner is at the concurrent process limit.
+    pub fn take_chain_failures(&mut self) -> Vec<(ProcessId, EngineError)> {
+        std::mem::take(&mut self.chain_failures)
+    }
+
+    /// Wrap up processes that completed since the last call
+    ///
+    /// Completed processes get their outputs and final quality, stop counting
+    /// towards their owner's process limit, and start their chained
+    /// follow-ups. `update`
+    /// calls this after advancing the processes.
+    pub fn finish_completed_processes(&mut self) {
+        for index in 0..self.processes.len() {
+            if self.processes.status[index] != ProcessStatus::Completed
+                || !self.processes.active[index]
+            {
+                continue;
+            }
+
+            if self.processes.output_count[index] == 0 {
+                let inputs = self
+                    .io
+                    .get_inputs(
+                        self.processes.input_start[index],
+                        self.processes.input_count[index],
+                    )
+                    .to_vec();
+                let (output_start, output_count) = self.io.add_outputs(inputs);
+                self.processes.output_start[index] = output_start;
+                self.processes.output_count[index] = output_count;
+            }
+
+            let input_qualities = std::mem::take(&mut self.processes.input_qualities[index]);
+            let failed_optional = self.processes.failed_optional[index] as usize;
+            self.executor.finalize_output_quality(
+                index,
+                &mut self.processes,
+                &input_qualities,
+                failed_optional,
+                &mut [],
+            );
+            self.processes.active[index] = false;
+        }
+
+        self.start_completed_chains();
+    }
+
+    /// Start the follow-ups of every chained process that has finished
+    fn start_completed_chains(&mut self) {
+        for index in 0..self.processes.len() {
+            let pending =
+                matches!(&self.processes.chains[index], Some(chain) if chain.child.is_none());
+            if !pending || self.processes.active[index] {
+                continue;
+            }
+
+            let parent = self.processes.ids[index];
+            match self.processes.status[index] {
+                ProcessStatus::Completed => {
+                    if let Err(e) = self.start_chained(index) {
+                        self.processes.chains[index] = None;
+                        self.chain_failures.push((parent, e));
+                    }
+                }
+                ProcessStatus::Failed | ProcessStatus::Cancelled => {
+                    self.processes.chains[index] = None;
+                    self.chain_failures.push((
+                        parent,
+                        error::process_update_error(
+                            parent.0,
+                            "parent did not complete, chain dropped",
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn start_chained(&mut self, index: usize) -> ProcessResult<ProcessId> {
+        let parent = self.processes.ids[index];
+        let chain = self.processes.chains[index]
+            .clone()
+            .ok_or_else(|| error::process_update_error(parent.0, "no chained process"))?;
+        let owner = self.processes.owners[index];
+
+        if !self
+            .control
+            .can_player_start_process(owner, &self.processes)
+        {
+            return Err(error::process_update_error(
+                parent.0,
+                "owner is at the concurrent process limit",
+            ));
+        }
+
+        let mut inputs = self
+            .io
+            .get_outputs(
+                self.processes.output_start[index],
+                self.processes.output_count[index],
+            )
+            .to_vec();
+        inputs.extend(chain.inputs);
+
+        let child = self.start_process(
+            chain.process_type,
+            owner,
+            inputs,
+            TimeUnit::Ticks(chain.duration),
+        );
+        if let Some(chain) = self.processes.chains[index].as_mut() {
+            chain.child = Some(child);
+        }
+
+        Ok(child)
+    }
+
     /// Update all processes (called each tick)
+    ///
+    /// Chains that failed to start are reported by `take_chain_failures`.
     pub fn update(&mut self, delta_ticks: u64) {
         // Use parallel processor for batch updates
         let batch = ProcessBatch {
@@ -199,6 +433,70 @@ impl ProcessManager {
                 update_progress(&mut self.visuals[i], progress);
             }
         }
+
+        self.finish_completed_processes();
+    }
+
+    /// Pause an active process
+    ///
+    /// Elapsed ticks and the state machine's position are frozen until
+    /// `resume_process`, so no progress is lost or double counted.
+    pub fn pause_process(&mut self, id: ProcessId) -> ProcessResult<()> {
+        let index = self
+            .processes
+            .find_index(id)
+            .ok_or_else(|| error::process_not_found(id.0))?;
+
+        self.control
+            .interrupt_process(id, InterruptReason::UserPaused, &mut self.processes)
+            .map_err(|e| error::process_update_error(id.0, e))?;
+
+        if let Some(state_machine) = self.state_machines.get_mut(index) {
+            state_machine.pause();
+        }
+
+        Ok(())
+    }
+
+    /// Resume a process paused with `pause_process`
+    pub fn resume_process(&mut self, id: ProcessId) -> ProcessResult<()> {
+        let index = self
+            .processes
+            .find_index(id)
+            .ok_or_else(|| error::process_not_found(id.0))?;
+
+        self.control
+            .clear_interrupt(id, &InterruptReason::UserPaused);
+        self.control
+            .resume_process(id, &mut self.processes)
+            .map_err(|e| error::process_update_error(id.0, e))?;
+
+        if let Some(state_machine) = self.state_machines.get_mut(index) {
+            state_machine.resume();
+        }
+
+        Ok(())
+    }
+
+    /// Set a process's speed multiplier (e.g. from a crafting buff)
+    ///
+    /// The multiplier is clamped to `MIN_PROCESS_SPEED..=MAX_PROCESS_SPEED` and
+    /// scales every subsequent update, so `time_remaining` reflects the new rate.
+    pub fn set_process_speed(&mut self, id: ProcessId, multiplier: f32) -> ProcessResult<()> {
+        let index = self
+            .processes
+            .find_index(id)
+            .ok_or_else(|| error::process_not_found(id.0))?;
+
+        if !multiplier.is_finite() {
+            return Err(error::process_update_error(
+                id.0,
+                format!("invalid speed multiplier {}", multiplier),
+            ));
+        }
+
+        self.processes.set_speed(index, multiplier);
+        Ok(())
     }
 
     /// Get process info
@@ -212,7 +510,11 @@ impl ProcessManager {
             status: self.processes.status[index],
             progress: self.processes.get_progress(index),
             time_remaining: self.processes.get_time_remaining(index),
+            quality: self.processes.quality[index],
             current_state: self.state_machines[index].current_state(),
+            chained_child: self.processes.chains[index]
+                .as_ref()
+                .and_then(|chain| chain.child),
         })
     }
 }
@@ -226,7 +528,11 @@ pub struct ProcessInfo {
     pub status: ProcessStatus,
     pub progress: f32,
     pub time_remaining: u64,
+    /// Output quality (final once the process has completed)
+    pub quality: QualityLevel,
     pub current_state: ProcessState,
+    /// Follow-up started by `chain_process`, once the parent has completed
+    pub chained_child: Option<ProcessId>,
 }
 
 #[cfg(test)]
@@ -258,4 +564,234 @@ mod tests {
         assert_eq!(info.owner, owner);
         assert_eq!(info.time_remaining, 100); // 5 seconds * 20 ticks
     }
+
+    #[test]
+    fn test_pause_and_resume_process() {
+        let mut manager = ProcessManager::new().expect("Failed to create manager");
+        let process_id = manager.start_process(
+            ProcessType::default(),
+            InstanceId::new(),
+            vec![],
+            TimeUnit::Ticks(100),
+        );
+        let index = manager
+            .processes
+            .find_index(process_id)
+            .expect("Process should exist in test");
+        manager.processes.status[index] = ProcessStatus::Active;
+        manager.processes.update(index, 30);
+
+        manager
+            .pause_process(process_id)
+            .expect("pause should succeed");
+        manager.processes.update(index, 50);
+
+        let info = manager
+            .get_process(process_id)
+            .expect("Process should exist in test");
+        assert_eq!(info.status, ProcessStatus::Paused);
+        assert_eq!(info.current_state, ProcessState::PAUSED);
+        assert_eq!(info.time_remaining, 70);
+
+        manager
+            .resume_process(process_id)
+            .expect("resume should succeed");
+        manager.processes.update(index, 20);
+
+        let info = manager
+            .get_process(process_id)
+            .expect("Process should exist in test");
+        assert_eq!(info.status, ProcessStatus::Active);
+        assert_eq!(info.time_remaining, 50);
+    }
+
+    #[test]
+    fn test_chained_process_starts_on_completion() {
+        let mut manager = ProcessManager::new().expect("Failed to create manager");
+        let owner = InstanceId::new();
+        let material = InstanceId::new();
+        let extra = InstanceId::new();
+        let first = manager.start_process(
+            ProcessType::default(),
+            owner,
+            vec![material],
+            TimeUnit::Ticks(10),
+        );
+        manager
+            .chain_process(
+                first,
+                ProcessType::default(),
+                vec![extra],
+                TimeUnit::Ticks(40),
+            )
+            .expect("chain should be accepted");
+
+        let index = manager
+            .processes
+            .find_index(first)
+            .expect("Process should exist in test");
+        manager.processes.status[index] = ProcessStatus::Active;
+        manager.processes.update(index, 10);
+
+        manager.finish_completed_processes();
+        assert!(manager.take_chain_failures().is_empty());
+        assert!(!manager.processes.active[index]);
+
+        let child = manager
+            .get_process(first)
+            .and_then(|info| info.chained_child)
+            .expect("chained child should have started");
+        let child_info = manager.get_process(child).expect("child should exist");
+        assert_eq!(child_info.owner, owner);
+        assert_eq!(child_info.time_remaining, 40);
+
+        let child_index = manager
+            .processes
+            .find_index(child)
+            .expect("child should exist");
+        let inputs = manager.io.get_inputs(
+            manager.processes.input_start[child_index],
+            manager.processes.input_count[child_index],
+        );
+        // The parent's inputs were transformed in place and passed on
+        assert_eq!(inputs, &[material, extra]);
+    }
+
+    #[test]
+    fn test_chained_process_receives_recorded_outputs() {
+        let mut manager = ProcessManager::new().expect("Failed to create manager");
+        let product = InstanceId::new();
+        let first = manager.start_process(
+            ProcessType::default(),
+            InstanceId::new(),
+            vec![InstanceId::new()],
+            TimeUnit::Ticks(10),
+        );
+        manager
+            .chain_process(first, ProcessType::default(), vec![], TimeUnit::Ticks(10))
+            .expect("chain should be accepted");
+        manager
+            .set_outputs(first, vec![product])
+            .expect("outputs should be recorded");
+
+        let index = manager
+            .processes
+            .find_index(first)
+            .expect("Process should exist in test");
+        manager.processes.status[index] = ProcessStatus::Active;
+        manager.processes.update(index, 10);
+        manager.finish_completed_processes();
+
+        let child = manager
+            .get_process(first)
+            .and_then(|info| info.chained_child)
+            .expect("chained child should have started");
+        let child_index = manager
+            .processes
+            .find_index(child)
+            .expect("child should exist");
+        let inputs = manager.io.get_inputs(
+            manager.processes.input_start[child_index],
+            manager.processes.input_count[child_index],
+        );
+        assert_eq!(inputs, &[product]);
+        assert!(manager.set_outputs(first, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_completed_process_reports_output_quality() {
+        let mut manager = ProcessManager::new().expect("Failed to create manager");
+        let fine = manager.start_process(
+            ProcessType::default(),
+            InstanceId::new(),
+            vec![],
+            TimeUnit::Ticks(10),
+        );
+        let sloppy = manager.start_process(
+            ProcessType::default(),
+            InstanceId::new(),
+            vec![],
+            TimeUnit::Ticks(10),
+        );
+        manager
+            .set_quality_inputs(
+                fine,
+                vec![QualityLevel::Excellent, QualityLevel::Perfect],
+                0,
+            )
+            .expect("quality inputs should be recorded");
+        manager
+            .set_quality_inputs(sloppy, vec![QualityLevel::Good, QualityLevel::Good], 1)
+            .expect("quality inputs should be recorded");
+
+        for id in [fine, sloppy] {
+            let index = manager
+                .processes
+                .find_index(id)
+                .expect("Process should exist in test");
+            manager.processes.status[index] = ProcessStatus::Active;
+            manager.processes.update(index, 10);
+        }
+        manager.finish_completed_processes();
+
+        let quality = |id| manager.get_process(id).map(|info| info.quality);
+        assert_eq!(quality(fine), Some(QualityLevel::Perfect));
+        assert_eq!(quality(sloppy), Some(QualityLevel::Normal));
+    }
+
+    #[test]
+    fn test_chain_at_process_limit_is_reported() {
+        let mut manager = ProcessManager::new().expect("Failed to create manager");
+        manager.control.policies.max_concurrent = 1;
+        let owner = InstanceId::new();
+        let first =
+            manager.start_process(ProcessType::default(), owner, vec![], TimeUnit::Ticks(10));
+        let other =
+            manager.start_process(ProcessType::default(), owner, vec![], TimeUnit::Ticks(100));
+        manager
+            .chain_process(first, ProcessType::default(), vec![], TimeUnit::Ticks(10))
+            .expect("chain should be accepted");
+
+        let index = manager
+            .processes
+            .find_index(first)
+            .expect("Process should exist in test");
+        manager.processes.status[index] = ProcessStatus::Active;
+        manager.processes.update(index, 10);
+        manager.finish_completed_processes();
+
+        // `other` still holds the owner's only slot
+        let failures = manager.take_chain_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, first);
+        assert!(manager.take_chain_failures().is_empty());
+        assert_eq!(manager.processes.len(), 2);
+        assert!(manager.get_process(other).is_some());
+    }
+
+    #[test]
+    fn test_chain_of_cancelled_process_is_reported() {
+        let mut manager = ProcessManager::new().expect("Failed to create manager");
+        let first = manager.start_process(
+            ProcessType::default(),
+            InstanceId::new(),
+            vec![],
+            TimeUnit::Ticks(10),
+        );
+        manager
+            .chain_process(first, ProcessType::default(), vec![], TimeUnit::Ticks(10))
+            .expect("chain should be accepted");
+
+        let index = manager
+            .processes
+            .find_index(first)
+            .expect("Process should exist in test");
+        manager.processes.cancel(index);
+
+        manager.finish_completed_processes();
+        let failures = manager.take_chain_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, first);
+        assert!(manager.processes.chains[index].is_none());
+    }
 }
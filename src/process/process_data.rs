@@ -6,6 +6,12 @@ use crate::instance::InstanceId;
 use crate::process::{ProcessCategory, ProcessPriority, QualityLevel};
 use serde::{Deserialize, Serialize};
 
+/// Slowest allowed process speed multiplier
+pub const MIN_PROCESS_SPEED: f32 = 0.1;
+
+/// Fastest allowed process speed multiplier
+pub const MAX_PROCESS_SPEED: f32 = 10.0;
+
 /// Unique process identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ProcessId(pub u64);
@@ -45,6 +51,19 @@ pub enum ProcessStatus {
     Cancelled = 5,
 }
 
+/// Follow-up process started automatically when its parent completes
+#[derive(Debug, Clone)]
+pub struct ProcessChain {
+    /// Type of the follow-up process
+    pub process_type: ProcessType,
+    /// Extra inputs, passed along with the parent's outputs
+    pub inputs: Vec<InstanceId>,
+    /// Duration (game ticks)
+    pub duration: u64,
+    /// Follow-up process once started
+    pub child: Option<ProcessId>,
+}
+
 /// Core process data (Structure of Arrays)
 pub struct ProcessData {
     /// Process IDs (sparse, some may be inactive)
@@ -77,6 +96,18 @@ pub struct ProcessData {
     /// Quality modifiers
     pub quality: Vec<QualityLevel>,
 
+    /// Qualities of the consumed inputs, for the final output quality
+    pub input_qualities: Vec<Vec<QualityLevel>>,
+
+    /// Optional stage requirements the validator reported as unmet
+    pub failed_optional: Vec<u32>,
+
+    /// Speed multipliers (1.0 = normal rate)
+    pub speed: Vec<f32>,
+
+    /// Fractional ticks carried between updates by non-integer speeds
+    pub speed_remainder: Vec<f32>,
+
     /// Input instances (indices into separate storage)
     pub input_start: Vec<u32>,
     pub input_count: Vec<u32>,
@@ -85,6 +116,9 @@ pub struct ProcessData {
     pub output_start: Vec<u32>,
     pub output_count: Vec<u32>,
 
+    /// Chained follow-up processes
+    pub chains: Vec<Option<ProcessChain>>,
+
     /// Active flags
     pub active: Vec<bool>,
 }
@@ -102,10 +136,15 @@ impl ProcessData {
             elapsed: Vec::with_capacity(super::MAX_PROCESSES),
             pause_time: Vec::with_capacity(super::MAX_PROCESSES),
             quality: Vec::with_capacity(super::MAX_PROCESSES),
+            input_qualities: Vec::with_capacity(super::MAX_PROCESSES),
+            failed_optional: Vec::with_capacity(super::MAX_PROCESSES),
+            speed: Vec::with_capacity(super::MAX_PROCESSES),
+            speed_remainder: Vec::with_capacity(super::MAX_PROCESSES),
             input_start: Vec::with_capacity(super::MAX_PROCESSES),
             input_count: Vec::with_capacity(super::MAX_PROCESSES),
             output_start: Vec::with_capacity(super::MAX_PROCESSES),
             output_count: Vec::with_capacity(super::MAX_PROCESSES),
+            chains: Vec::with_capacity(super::MAX_PROCESSES),
             active: Vec::with_capacity(super::MAX_PROCESSES),
         }
     }
@@ -131,10 +170,15 @@ impl ProcessData {
         self.elapsed.push(0);
         self.pause_time.push(0);
         self.quality.push(QualityLevel::Normal);
+        self.input_qualities.push(Vec::new());
+        self.failed_optional.push(0);
+        self.speed.push(1.0);
+        self.speed_remainder.push(0.0);
         self.input_start.push(0);
         self.input_count.push(0);
         self.output_start.push(0);
         self.output_count.push(0);
+        self.chains.push(None);
         self.active.push(true);
 
         index
@@ -152,6 +196,8 @@ impl ProcessData {
             return;
         }
 
+        let delta_ticks = self.scaled_delta(index, delta_ticks);
+
         // Update elapsed time
         if let Some(elapsed) = self.elapsed.get_mut(index) {
             *elapsed += delta_ticks;
@@ -167,6 +213,29 @@ impl ProcessData {
         }
     }
 
+    /// Scale a tick delta by the process speed, carrying the fractional part
+    fn scaled_delta(&mut self, index: usize, delta_ticks: u64) -> u64 {
+        let speed = self.speed.get(index).copied().unwrap_or(1.0);
+        if speed == 1.0 {
+            return delta_ticks;
+        }
+
+        let Some(remainder) = self.speed_remainder.get_mut(index) else {
+            return delta_ticks;
+        };
+        let scaled = delta_ticks as f64 * speed as f64 + *remainder as f64;
+        let whole = scaled.floor();
+        *remainder = (scaled - whole) as f32;
+        whole as u64
+    }
+
+    /// Set the speed multiplier, clamped to `MIN_PROCESS_SPEED..=MAX_PROCESS_SPEED`
+    pub fn set_speed(&mut self, index: usize, multiplier: f32) {
+        if let Some(speed) = self.speed.get_mut(index) {
+            *speed = multiplier.clamp(MIN_PROCESS_SPEED, MAX_PROCESS_SPEED);
+        }
+    }
+
     /// Pause a process
     pub fn pause(&mut self, index: usize) {
         if let Some(&status) = self.status.get(index) {
@@ -186,7 +255,7 @@ impl ProcessData {
         if let Some(&status) = self.status.get(index) {
             if status == ProcessStatus::Paused {
                 self.status[index] = ProcessStatus::Active;
-                let pause_duration = Self::current_tick() - self.pause_time[index];
+                let pause_duration = Self::current_tick().saturating_sub(self.pause_time[index]);
                 self.pause_time[index] = pause_duration;
             }
         }
@@ -207,12 +276,18 @@ impl ProcessData {
         (self.elapsed[index] as f32 / self.duration[index] as f32).min(1.0)
     }
 
-    /// Get remaining time
+    /// Get remaining time in game ticks at the current speed
     pub fn get_time_remaining(&self, index: usize) -> u64 {
         if self.elapsed[index] >= self.duration[index] {
-            0
+            return 0;
+        }
+
+        let remaining = self.duration[index] - self.elapsed[index];
+        let speed = self.speed[index];
+        if speed == 1.0 {
+            remaining
         } else {
-            self.duration[index] - self.elapsed[index]
+            ((remaining as f64 - self.speed_remainder[index] as f64) / speed as f64).ceil() as u64
         }
     }
 
@@ -318,4 +393,26 @@ mod tests {
         assert_eq!(data.get_progress(index), 1.0);
         assert_eq!(data.status[index], ProcessStatus::Completed);
     }
+
+    #[test]
+    fn test_process_speed() {
+        let mut data = ProcessData::new();
+        let index = data.add(ProcessId::new(), ProcessType::default(), InstanceId::new(), 100);
+        data.status[index] = ProcessStatus::Active;
+
+        data.set_speed(index, 2.0);
+        assert_eq!(data.get_time_remaining(index), 50);
+        data.update(index, 10);
+        assert_eq!(data.get_progress(index), 0.2);
+        assert_eq!(data.get_time_remaining(index), 40);
+
+        // Fractional speeds accumulate without losing ticks
+        data.set_speed(index, 0.5);
+        data.update(index, 1);
+        data.update(index, 1);
+        assert_eq!(data.elapsed[index], 21);
+
+        data.set_speed(index, 100.0);
+        assert_eq!(data.speed[index], MAX_PROCESS_SPEED);
+    }
 }
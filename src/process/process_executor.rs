@@ -4,7 +4,7 @@
 /// Handles resource consumption and output generation.
 use crate::instance::InstanceId;
 use crate::process::{
-    ActualOutput, ProcessData, ProcessId, ProcessStatus, ProcessType, StateMachine,
+    ActualOutput, ProcessData, ProcessId, ProcessStatus, ProcessType, QualityLevel, StateMachine,
     TransformStage, TransitionAction, ValidationContext,
 };
 use std::collections::HashMap;
@@ -40,8 +40,31 @@ impl ExecutionResult {
     }
 }
 
+/// Curve mapping input quality to output quality
+#[derive(Debug, Clone)]
+pub struct QualityCurve {
+    /// Output quality score for each input quality level (Poor..=Perfect).
+    /// Averages between levels are interpolated.
+    pub output_for_input: [f32; 5],
+
+    /// Quality levels lost per failed optional requirement
+    pub optional_penalty: f32,
+}
+
+impl Default for QualityCurve {
+    fn default() -> Self {
+        Self {
+            output_for_input: [0.0, 1.0, 2.0, 3.0, 4.0],
+            optional_penalty: 1.0,
+        }
+    }
+}
+
 /// Process executor
 pub struct ProcessExecutor {
+    /// Input-to-output quality curve for stage outputs and finished processes
+    pub quality_curve: QualityCurve,
+
     /// Validation contexts per player
     contexts: HashMap<InstanceId, ValidationContext>,
 
@@ -57,6 +80,7 @@ impl ProcessExecutor {
         use rand::SeedableRng;
 
         Self {
+            quality_curve: QualityCurve::default(),
             contexts: HashMap::new(),
             rng: rand::rngs::StdRng::from_entropy(),
             resource_available: HashMap::new(),
@@ -101,10 +125,11 @@ impl ProcessExecutor {
                 // Check stage completion
                 if let Some(current_stage) = self.get_current_stage(state_machine, stages) {
                     if self.is_stage_complete(state_machine, current_stage) {
-                        // Generate stage outputs
+                        // Generate stage outputs from the input-biased quality
+                        let quality = self.stage_output_quality(index, data);
                         let outputs = StageValidator::calculate_outputs(
                             current_stage,
-                            data.quality[index],
+                            quality,
                             &mut self.rng,
                         );
 
@@ -165,6 +190,72 @@ impl ProcessExecutor {
         combined_result
     }
 
+    /// Compute the final output quality of a process
+    ///
+    /// `input_qualities` are the qualities of the consumed inputs and
+    /// `failed_optional` is the number of optional stage requirements the
+    /// validator reported as unmet. With no inputs the process's current
+    /// quality is used as the baseline.
+    pub fn compute_output_quality(
+        &self,
+        base_quality: QualityLevel,
+        input_qualities: &[QualityLevel],
+        failed_optional: usize,
+    ) -> QualityLevel {
+        let curve = &self.quality_curve;
+
+        let input_level = if input_qualities.is_empty() {
+            base_quality as u8 as f32
+        } else {
+            input_qualities.iter().map(|q| *q as u8 as f32).sum::<f32>()
+                / input_qualities.len() as f32
+        };
+
+        // Interpolate along the curve
+        let lower = (input_level.floor() as usize).min(4);
+        let upper = (lower + 1).min(4);
+        let t = input_level - lower as f32;
+        let score = curve.output_for_input[lower] * (1.0 - t) + curve.output_for_input[upper] * t
+            - curve.optional_penalty * failed_optional as f32;
+
+        quality_from_level(score.round() as i32)
+    }
+
+    /// Output quality for a process that is still running
+    ///
+    /// Uses the input qualities and failed optional requirements recorded
+    /// for the process so far, without changing the process's own quality.
+    pub fn stage_output_quality(&self, index: usize, data: &ProcessData) -> QualityLevel {
+        self.compute_output_quality(
+            data.quality[index],
+            &data.input_qualities[index],
+            data.failed_optional[index] as usize,
+        )
+    }
+
+    /// Apply the computed quality to a finished process and its outputs
+    ///
+    /// Outputs recorded while the process ran already got their quality from
+    /// `stage_output_quality`; this settles the process's final quality.
+    pub fn finalize_output_quality(
+        &self,
+        index: usize,
+        data: &mut ProcessData,
+        input_qualities: &[QualityLevel],
+        failed_optional: usize,
+        outputs: &mut [ActualOutput],
+    ) -> QualityLevel {
+        let quality =
+            self.compute_output_quality(data.quality[index], input_qualities, failed_optional);
+
+        data.quality[index] = quality;
+        for output in outputs {
+            output.quality = quality;
+        }
+
+        quality
+    }
+
     /// Process a transition action
     fn process_action(
         &mut self,
@@ -190,13 +281,14 @@ impl ProcessExecutor {
                 for (resource_id, amount) in resources {
                     // In real implementation, would add to inventory
                     *self.resource_available.entry(resource_id).or_insert(0) += amount;
+                    let quality = self.stage_output_quality(index, data);
 
                     result.produced.push((
                         data.types[index],
                         ActualOutput {
                             output_type: crate::process::OutputType::Item(resource_id),
                             quantity: amount,
-                            quality: data.quality[index],
+                            quality,
                         },
                     ));
                 }
@@ -258,6 +350,17 @@ impl ProcessExecutor {
     }
 }
 
+/// Convert a quality level number to `QualityLevel`, clamping out-of-range values
+fn quality_from_level(level: i32) -> QualityLevel {
+    match level {
+        i32::MIN..=0 => QualityLevel::Poor,
+        1 => QualityLevel::Normal,
+        2 => QualityLevel::Good,
+        3 => QualityLevel::Excellent,
+        _ => QualityLevel::Perfect,
+    }
+}
+
 /// Process scheduler for prioritized execution
 pub struct ProcessScheduler {
     /// Priority queues
@@ -294,10 +397,12 @@ impl ProcessScheduler {
             }
         }
 
-        // Build execution order (highest priority first)
+        // Build execution order (highest priority first, faster processes
+        // first within a priority)
         let mut order = Vec::new();
-        for queue in self.queues.iter().rev() {
-            order.extend(queue);
+        for queue in self.queues.iter_mut().rev() {
+            queue.sort_by(|&a, &b| data.speed[b].total_cmp(&data.speed[a]));
+            order.extend(queue.iter());
         }
 
         order
@@ -351,4 +456,67 @@ mod tests {
         // Should be ordered by priority (highest first)
         assert_eq!(order, vec![3, 2, 1, 0]);
     }
+
+    #[test]
+    fn test_output_quality() {
+        let executor = ProcessExecutor::new();
+
+        // Better inputs bias the output upward
+        let quality = executor.compute_output_quality(
+            QualityLevel::Normal,
+            &[QualityLevel::Excellent, QualityLevel::Perfect],
+            0,
+        );
+        assert_eq!(quality, QualityLevel::Perfect);
+
+        // Failed optional requirements bias it downward
+        let quality = executor.compute_output_quality(
+            QualityLevel::Normal,
+            &[QualityLevel::Good, QualityLevel::Good],
+            1,
+        );
+        assert_eq!(quality, QualityLevel::Normal);
+
+        // Never below Poor
+        let quality = executor.compute_output_quality(QualityLevel::Poor, &[], 3);
+        assert_eq!(quality, QualityLevel::Poor);
+    }
+
+    #[test]
+    fn test_stage_outputs_use_recorded_input_quality() {
+        let executor = ProcessExecutor::new();
+        let mut data = ProcessData::new();
+        let index = data.add(
+            ProcessId::new(),
+            ProcessType::default(),
+            InstanceId::new(),
+            100,
+        );
+        data.quality[index] = QualityLevel::Normal;
+        data.input_qualities[index] = vec![QualityLevel::Perfect];
+        data.failed_optional[index] = 1;
+
+        // Stage outputs see the inputs before the process finishes
+        assert_eq!(
+            executor.stage_output_quality(index, &data),
+            QualityLevel::Excellent
+        );
+        assert_eq!(data.quality[index], QualityLevel::Normal);
+    }
+
+    #[test]
+    fn test_scheduler_prefers_faster_within_priority() {
+        let mut scheduler = ProcessScheduler::new();
+        let mut data = ProcessData::new();
+
+        for _ in 0..3 {
+            let index = data.add(ProcessId::new(), ProcessType::default(), InstanceId::new(), 100);
+            data.status[index] = ProcessStatus::Active;
+        }
+        data.priority[0] = crate::process::ProcessPriority::Critical;
+        data.set_speed(2, 3.0);
+
+        let order = scheduler.schedule(&data);
+        assert_eq!(order, vec![0, 2, 1]);
+    }
 }
@@ -15,6 +15,7 @@ impl ProcessState {
     pub const PROCESSING: Self = Self(2);
     pub const FINALIZING: Self = Self(3);
     pub const COMPLETE: Self = Self(4);
+    pub const PAUSED: Self = Self(5);
     pub const ERROR: Self = Self(999);
 }
 
@@ -73,6 +74,8 @@ pub struct StateMachine {
     transitions: Vec<StateTransition>,
     /// State callbacks (as indices)
     state_callbacks: HashMap<ProcessState, Vec<usize>>,
+    /// State and time-in-state saved while paused
+    paused_from: Option<(ProcessState, u64)>,
 }
 
 impl StateMachine {
@@ -82,6 +85,7 @@ impl StateMachine {
             state_time: 0,
             transitions: Vec::new(),
             state_callbacks: HashMap::new(),
+            paused_from: None,
         }
     }
 
@@ -95,6 +99,11 @@ impl StateMachine {
 
     /// Update state machine
     pub fn update(&mut self, delta_ticks: u64, progress: f32) -> Vec<TransitionAction> {
+        // Paused machines neither accumulate time nor transition
+        if self.paused_from.is_some() {
+            return Vec::new();
+        }
+
         self.state_time += delta_ticks;
         let mut actions = Vec::new();
 
@@ -129,6 +138,27 @@ impl StateMachine {
         self.state_time = 0;
     }
 
+    /// Pause in the current state, remembering it and the time spent there
+    pub fn pause(&mut self) {
+        if self.paused_from.is_none() {
+            self.paused_from = Some((self.current, self.state_time));
+            self.current = ProcessState::PAUSED;
+        }
+    }
+
+    /// Resume exactly where `pause` left off
+    pub fn resume(&mut self) {
+        if let Some((state, state_time)) = self.paused_from.take() {
+            self.current = state;
+            self.state_time = state_time;
+        }
+    }
+
+    /// Check if paused
+    pub fn is_paused(&self) -> bool {
+        self.paused_from.is_some()
+    }
+
     /// Get current state
     pub fn current_state(&self) -> ProcessState {
         self.current
@@ -312,4 +342,32 @@ mod tests {
         sm.update(10, 0.6);
         assert_eq!(sm.current_state(), ProcessState::COMPLETE);
     }
+
+    #[test]
+    fn test_pause_preserves_state_time() {
+        let mut sm = StateMachine::new();
+
+        sm.add_transition(StateTransition {
+            from: ProcessState::IDLE,
+            to: ProcessState::PROCESSING,
+            condition: TransitionCondition::TimeElapsed(10),
+            priority: 10,
+            actions: vec![],
+        });
+
+        sm.update(6, 0.0);
+        sm.pause();
+        assert_eq!(sm.current_state(), ProcessState::PAUSED);
+
+        // Ticks while paused are not counted
+        sm.update(100, 0.0);
+        assert_eq!(sm.current_state(), ProcessState::PAUSED);
+
+        sm.resume();
+        assert_eq!(sm.current_state(), ProcessState::IDLE);
+        assert_eq!(sm.state_time(), 6);
+
+        sm.update(4, 0.0);
+        assert_eq!(sm.current_state(), ProcessState::PROCESSING);
+    }
 }
@@ -1,10 +1,14 @@
 //! GPU mesh generation dispatch - pure functions for executing mesh generation
 
 use crate::renderer::gpu_meshing::{
-    GpuMeshBuffer, GpuMeshingState, MeshRequest, MeshingParams, MAX_CONCURRENT_MESHES,
-    WORKGROUP_SIZE,
+    allocate_buffer_slot, free_for_chunk, leaked_chunks, BufferAllocator, GpuMeshBuffer,
+    GpuMeshMetadata, GpuMeshingState, LodLevel, MeshRequest, MeshingParams, LOD_LEVEL_COUNT,
+    MAX_CONCURRENT_MESHES, MESH_FLAG_SKIRTS, NO_WORLD_SLOT, WORKGROUP_SIZE,
 };
 use crate::world::core::ChunkPos;
+use crate::world::storage::WorldBuffer;
+use std::collections::HashSet;
+use std::sync::{MutexGuard, PoisonError};
 
 // Import constants properly
 use crate::constants::*;
@@ -13,15 +17,36 @@ use crate::constants::*;
 pub struct MeshGenerationResult {
     pub chunk_pos: ChunkPos,
     pub buffer_index: u32,
+    pub lod: LodLevel,
+    /// Vertices written for this chunk, `None` if the counts could not be read back
+    pub vertex_count: Option<u32>,
+    /// Chunk whose mesh was dropped to free this slot; its mesh is gone
+    pub evicted: Option<ChunkPos>,
     // Indirect commands are stored in the global indirect buffer at offset buffer_index * 20 bytes
 }
 
 /// Generate meshes for a batch of chunks
+///
+/// Above `LodLevel::FULL` the chunk is meshed in coarser cells (see
+/// `lod_cell_size`) with skirts along its boundary, so it can sit next to
+/// chunks of any other LOD without visible gaps.
+///
+/// Each chunk writes into the region of merged buffer 0 given by its slot
+/// from `allocate_buffer_slot`. A chunk keeps its slot until
+/// `free_mesh_buffer` is called on unload; when the pool is full the chunk
+/// farthest from `center` loses its mesh, reported in `evicted`.
+///
+/// Faces are lit from the skylight and colored block light the world buffer
+/// holds for the chunk (`WorldBuffer::upload_block_light`).
+///
+/// Blocks until the GPU is done, then reads back the vertex counts and adds
+/// them to `MeshingStats`.
 pub fn generate_chunk_meshes(
-    state: &GpuMeshingState,
-    world_buffer: &wgpu::Buffer,
+    state: &mut GpuMeshingState,
+    world_buffer: &WorldBuffer,
     chunk_positions: &[ChunkPos],
-    lod_level: u32,
+    lod: LodLevel,
+    center: ChunkPos,
 ) -> Vec<MeshGenerationResult> {
     log::info!(
         "[GPU Meshing] generate_chunk_meshes called with {} chunks",
@@ -35,29 +60,59 @@ pub fn generate_chunk_meshes(
     let batch_size = chunk_positions.len().min(MAX_CONCURRENT_MESHES);
     let chunks = &chunk_positions[..batch_size];
 
-    // Allocate buffer indices and create mesh requests
-    let mut allocated_indices = Vec::new();
-    let mut requests = Vec::new();
+    // Allocate buffer slots and create mesh requests
+    let mut allocated_indices: Vec<(ChunkPos, u32, Option<ChunkPos>)> = Vec::new();
+    let lod_level = lod.0.min(LOD_LEVEL_COUNT as u32 - 1);
+    let flags = if lod_level > 0 { MESH_FLAG_SKIRTS } else { 0 };
 
-    let mut allocator = state.allocator.lock().unwrap();
+    {
+        let mut allocator = lock_allocator(state);
+        for chunk_pos in chunks {
+            let Some(slot) = allocate_buffer_slot(&mut allocator, *chunk_pos, center) else {
+                log::error!("[GPU Meshing] No mesh buffer slots to allocate");
+                return Vec::new();
+            };
+            // A full pool may take the slot of a chunk earlier in this batch
+            if let Some(evicted) = slot.evicted {
+                allocated_indices.retain(|(pos, _, _)| *pos != evicted);
+            }
 
-    for chunk_pos in chunks {
-        // For GPU-driven rendering, all chunks use buffer 0
-        let buffer_index = 0u32;
-        
-        log::debug!(
-            "[generate_chunk_meshes] Using buffer 0 for chunk {:?}",
-            chunk_pos
-        );
+            log::debug!(
+                "[generate_chunk_meshes] Using slot {} for chunk {:?}",
+                slot.buffer_index,
+                chunk_pos
+            );
+            allocated_indices.push((*chunk_pos, slot.buffer_index, slot.evicted));
+        }
+    }
 
-        allocated_indices.push((chunk_pos, buffer_index));
-        requests.push(MeshRequest {
+    let requests: Vec<MeshRequest> = allocated_indices
+        .iter()
+        .map(|(chunk_pos, buffer_index, _)| MeshRequest {
             chunk_pos: [chunk_pos.x, chunk_pos.y, chunk_pos.z],
             lod_level,
-            buffer_index,
-            flags: 0,
-            _padding: [0; 2],
-        });
+            buffer_index: *buffer_index,
+            flags,
+            world_slot: world_buffer.chunk_slot(*chunk_pos).unwrap_or(NO_WORLD_SLOT),
+            _padding: 0,
+        })
+        .collect();
+
+    // Reset the per-mesh counters; the kernel only ever adds to them
+    for request in &requests {
+        let metadata = GpuMeshMetadata {
+            chunk_pos: request.chunk_pos,
+            vertex_count: 0,
+            index_count: 0,
+            lod_level: request.lod_level,
+            flags: request.flags,
+            timestamp: 0,
+        };
+        state.queue.write_buffer(
+            &state.mesh_buffers[0].metadata,
+            (request.buffer_index as usize * std::mem::size_of::<GpuMeshMetadata>()) as u64,
+            bytemuck::bytes_of(&metadata),
+        );
     }
 
     // Create request buffer
@@ -95,12 +150,13 @@ pub fn generate_chunk_meshes(
         .queue
         .write_buffer(&params_buffer, 0, bytemuck::bytes_of(&params));
 
-    // For GPU-driven rendering, we want all chunks to write to buffer 0
-    // This allows us to render all chunks in a single draw call
+    // For GPU-driven rendering, all chunks write to buffer 0, each into the
+    // region of its slot. This allows us to render all chunks in a single draw call
     let bind_group = super::pipeline::create_mesh_bind_group_for_buffer(
         &state.device,
         &state.bind_group_layout,
-        world_buffer,
+        world_buffer.voxel_buffer(),
+        world_buffer.block_light_buffer(),
         &request_buffer,
         &state.mesh_buffers,
         &state.indirect_buffer,
@@ -146,24 +202,97 @@ pub fn generate_chunk_meshes(
 
     log::info!("[GPU Meshing] GPU synchronization complete - meshes should be ready");
 
+    let vertex_counts = read_vertex_counts(state);
+    if let Some(counts) = &vertex_counts {
+        let total: u64 = requests
+            .iter()
+            .filter_map(|request| counts.get(request.buffer_index as usize))
+            .map(|&count| count as u64)
+            .sum();
+        update_mesh_statistics(state, LodLevel(lod_level), requests.len() as u32, total);
+    }
+
     // Return mesh generation results using the allocated buffer indices
     // Note: indirect commands are written to the global indirect buffer by the GPU
     allocated_indices
         .iter()
-        .map(|(chunk_pos, buffer_index)| {
+        .map(|(chunk_pos, buffer_index, evicted)| {
             MeshGenerationResult {
-                chunk_pos: **chunk_pos,
+                chunk_pos: *chunk_pos,
                 buffer_index: *buffer_index,
+                lod: LodLevel(lod_level),
+                vertex_count: vertex_counts
+                    .as_ref()
+                    .and_then(|counts| counts.get(*buffer_index as usize).copied()),
+                evicted: *evicted,
                 // Indirect commands are stored in the global indirect buffer at offset buffer_index * 20
             }
         })
         .collect()
 }
 
-/// Get mesh statistics from GPU
-pub fn update_mesh_statistics(state: &mut GpuMeshingState, generated_count: u32) {
+/// Read back the vertex counts of every mesh slot in buffer 0
+///
+/// Must run after the meshing work has finished. `None` if the readback
+/// failed; the meshes themselves are unaffected.
+fn read_vertex_counts(state: &GpuMeshingState) -> Option<Vec<u32>> {
+    let size = state.mesh_buffers[0].metadata.size();
+    let readback = state.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Mesh Metadata Readback"),
+        size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = state
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Mesh Metadata Readback Encoder"),
+        });
+    encoder.copy_buffer_to_buffer(&state.mesh_buffers[0].metadata, 0, &readback, 0, size);
+    state.queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = readback.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    state.device.poll(wgpu::Maintain::Wait);
+
+    match receiver.recv() {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            log::warn!("[GPU Meshing] Mesh metadata readback failed: {}", e);
+            return None;
+        }
+        Err(_) => {
+            log::warn!("[GPU Meshing] Mesh metadata readback was dropped");
+            return None;
+        }
+    }
+
+    let data = slice.get_mapped_range();
+    let counts = bytemuck::cast_slice::<u8, GpuMeshMetadata>(&data)
+        .iter()
+        .map(|metadata| metadata.vertex_count)
+        .collect();
+    drop(data);
+    readback.unmap();
+    Some(counts)
+}
+
+/// Record meshes generated at one LOD and the vertices they produced
+pub fn update_mesh_statistics(
+    state: &mut GpuMeshingState,
+    lod: LodLevel,
+    generated_count: u32,
+    vertex_count: u64,
+) {
+    let level = lod.0.min(LOD_LEVEL_COUNT as u32 - 1) as usize;
     state.stats.total_meshes += generated_count as u64;
-    // TODO: Read back actual vertex/index counts from GPU
+    state.stats.total_vertices += vertex_count;
+    state.stats.lod_meshes[level] += generated_count as u64;
+    state.stats.lod_vertices[level] += vertex_count;
 }
 
 /// Check if mesh buffer is ready
@@ -180,18 +309,43 @@ pub fn get_mesh_buffer<'a>(
     state.mesh_buffers.get(buffer_index as usize)
 }
 
+/// Lock the slot allocator; its bookkeeping stays valid after a panic elsewhere
+fn lock_allocator(state: &GpuMeshingState) -> MutexGuard<'_, BufferAllocator> {
+    state
+        .allocator
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
 /// Free a mesh buffer when a chunk is unloaded
 pub fn free_mesh_buffer(state: &GpuMeshingState, chunk_pos: &ChunkPos) {
-    let mut allocator = state.allocator.lock().unwrap();
-    if let Some(buffer_index) = allocator.allocated_buffers.remove(chunk_pos) {
-        allocator.free_buffers.push(buffer_index);
-        allocator.free_buffers.sort(); // Keep in order
+    free_for_chunk(&mut lock_allocator(state), *chunk_pos);
+}
+
+/// Flag mesh slots still held by chunks outside `active_chunks`
+///
+/// A slot leaks when its chunk is unloaded without `free_mesh_buffer`, and
+/// leaks eventually exhaust the pool. The count goes to
+/// `MeshingStats::leaked_slots`; the slots stay allocated so the caller can
+/// free them.
+pub fn detect_leaked_slots(
+    state: &mut GpuMeshingState,
+    active_chunks: &HashSet<ChunkPos>,
+) -> Vec<ChunkPos> {
+    let leaked = leaked_chunks(&lock_allocator(state), active_chunks);
+    if !leaked.is_empty() {
+        log::warn!(
+            "[GPU Meshing] {} mesh slots held by unloaded chunks",
+            leaked.len()
+        );
     }
+    state.stats.leaked_slots = leaked.len() as u32;
+    leaked
 }
 
 /// Clear mesh buffer pool
 pub fn clear_mesh_buffers(state: &GpuMeshingState) {
-    let mut allocator = state.allocator.lock().unwrap();
+    let mut allocator = lock_allocator(state);
     // Return all allocated buffers to the free pool
     let buffer_indices: Vec<u32> = allocator
         .allocated_buffers
@@ -43,6 +43,86 @@ pub struct BufferAllocator {
     pub free_buffers: Vec<u32>,
 }
 
+/// Outcome of `allocate_buffer_slot`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotAllocation {
+    pub buffer_index: u32,
+    /// Chunk whose mesh was dropped to make room, if the pool was full
+    pub evicted: Option<crate::ChunkPos>,
+}
+
+/// Create an allocator with every slot free
+pub fn create_buffer_allocator(slot_count: u32) -> BufferAllocator {
+    BufferAllocator {
+        allocated_buffers: std::collections::HashMap::new(),
+        free_buffers: (0..slot_count).collect(),
+    }
+}
+
+/// Slot for `chunk_pos`, reusing its existing one if it has a mesh
+///
+/// When the pool is exhausted the slot of the chunk farthest from `center`
+/// is taken over; that chunk's mesh must be considered gone. Returns `None`
+/// only for an allocator without any slots.
+pub fn allocate_buffer_slot(
+    allocator: &mut BufferAllocator,
+    chunk_pos: crate::ChunkPos,
+    center: crate::ChunkPos,
+) -> Option<SlotAllocation> {
+    if let Some(&buffer_index) = allocator.allocated_buffers.get(&chunk_pos) {
+        return Some(SlotAllocation {
+            buffer_index,
+            evicted: None,
+        });
+    }
+
+    let (buffer_index, evicted) = if allocator.free_buffers.is_empty() {
+        // Position breaks distance ties so eviction is deterministic
+        let farthest = allocator
+            .allocated_buffers
+            .keys()
+            .copied()
+            .max_by_key(|pos| (pos.distance_squared_to(center), pos.x, pos.y, pos.z))?;
+        let buffer_index = allocator.allocated_buffers.remove(&farthest)?;
+        log::debug!(
+            "[BufferAllocator] Pool exhausted, evicting mesh of {:?} (slot {})",
+            farthest,
+            buffer_index
+        );
+        (buffer_index, Some(farthest))
+    } else {
+        // Lowest free index first, matching the sorted free list
+        (allocator.free_buffers.remove(0), None)
+    };
+
+    allocator.allocated_buffers.insert(chunk_pos, buffer_index);
+    Some(SlotAllocation {
+        buffer_index,
+        evicted,
+    })
+}
+
+/// Return the slot of an unloaded chunk to the pool
+pub fn free_for_chunk(allocator: &mut BufferAllocator, chunk_pos: crate::ChunkPos) -> Option<u32> {
+    let buffer_index = allocator.allocated_buffers.remove(&chunk_pos)?;
+    allocator.free_buffers.push(buffer_index);
+    allocator.free_buffers.sort(); // Keep in order
+    Some(buffer_index)
+}
+
+/// Chunks holding a slot although they are no longer active
+pub fn leaked_chunks(
+    allocator: &BufferAllocator,
+    active_chunks: &std::collections::HashSet<crate::ChunkPos>,
+) -> Vec<crate::ChunkPos> {
+    allocator
+        .allocated_buffers
+        .keys()
+        .filter(|pos| !active_chunks.contains(pos))
+        .copied()
+        .collect()
+}
+
 /// Initialize GPU meshing system
 pub fn create_gpu_meshing_state(
     device: Arc<wgpu::Device>,
@@ -71,10 +151,7 @@ pub fn create_gpu_meshing_state(
     });
 
     // Initialize allocator
-    let allocator = std::sync::Mutex::new(BufferAllocator {
-        allocated_buffers: std::collections::HashMap::new(),
-        free_buffers: (0..MAX_CONCURRENT_MESHES as u32).collect(),
-    });
+    let allocator = std::sync::Mutex::new(create_buffer_allocator(MAX_CONCURRENT_MESHES as u32));
 
     GpuMeshingState {
         device,
@@ -93,3 +170,48 @@ pub const MAX_CONCURRENT_MESHES: usize = 256;
 pub const MAX_VERTICES_PER_CHUNK: usize = 65536;
 pub const MAX_INDICES_PER_CHUNK: usize = 98304; // 1.5x vertices
 pub const WORKGROUP_SIZE: u32 = 64; // 4x4x4 voxels per workgroup
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkPos;
+
+    #[test]
+    fn test_exhausted_pool_evicts_farthest_chunk() {
+        let slot_count = 4;
+        let mut allocator = create_buffer_allocator(slot_count);
+        let center = ChunkPos::new(0, 0, 0);
+
+        for x in 0..slot_count as i32 {
+            let slot = allocate_buffer_slot(&mut allocator, ChunkPos::new(x, 0, 0), center);
+            assert_eq!(slot.map(|s| s.evicted), Some(None));
+        }
+        assert!(allocator.free_buffers.is_empty());
+
+        // Re-requesting a chunk keeps its slot instead of evicting
+        let again = allocate_buffer_slot(&mut allocator, ChunkPos::new(1, 0, 0), center);
+        assert_eq!(again.map(|s| (s.buffer_index, s.evicted)), Some((1, None)));
+
+        let farthest = ChunkPos::new(3, 0, 0);
+        let farthest_slot = allocator.allocated_buffers[&farthest];
+        let slot = allocate_buffer_slot(&mut allocator, ChunkPos::new(0, 1, 0), center)
+            .expect("full pool should evict");
+        assert_eq!(slot.evicted, Some(farthest));
+        assert_eq!(slot.buffer_index, farthest_slot);
+        assert!(!allocator.allocated_buffers.contains_key(&farthest));
+        assert_eq!(allocator.allocated_buffers.len(), slot_count as usize);
+
+        // Freeing returns the slot; chunks outside the active set are leaks
+        assert_eq!(
+            free_for_chunk(&mut allocator, ChunkPos::new(0, 1, 0)),
+            Some(farthest_slot)
+        );
+        assert_eq!(allocator.free_buffers, vec![farthest_slot]);
+        let active: std::collections::HashSet<_> =
+            [ChunkPos::new(0, 0, 0), ChunkPos::new(1, 0, 0)].into();
+        assert_eq!(
+            leaked_chunks(&allocator, &active),
+            vec![ChunkPos::new(2, 0, 0)]
+        );
+    }
+}
@@ -1,6 +1,10 @@
 //! GPU mesh generation pipeline - pure functions only
 
+use crate::gpu::automation::safe_pipeline::{create_validated_shader, TypedComputePipelineBuilder};
+use crate::renderer::error::{pipeline_creation_error, RendererErrorContext, RendererResult};
 use crate::renderer::gpu_meshing::types::*;
+use crate::renderer::gpu_meshing::GpuMeshingState;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Create mesh generation compute pipeline
@@ -67,7 +71,8 @@ pub fn create_mesh_generation_pipeline(
         3 => buffer(storage),       // Index buffer output
         4 => buffer(storage),       // Metadata output
         5 => buffer(storage),       // Indirect commands output
-        6 => buffer(uniform)        // Meshing parameters
+        6 => buffer(uniform),       // Meshing parameters
+        7 => buffer(storage_read)   // Packed RGB block light
     );
 
     // Create pipeline layout
@@ -133,11 +138,18 @@ pub fn create_gpu_mesh_buffer(device: &wgpu::Device, buffer_id: u32) -> GpuMeshB
         mapped_at_creation: false,
     });
 
-    // Metadata buffer
+    // Metadata buffer; buffer 0 holds one entry per merged mesh
+    let metadata_entries = if buffer_id == 0 {
+        MAX_CONCURRENT_MESHES
+    } else {
+        1
+    };
     let metadata = device.create_buffer(&wgpu::BufferDescriptor {
         label: Some(&format!("Mesh {} Metadata", buffer_id)),
-        size: std::mem::size_of::<GpuMeshMetadata>() as u64,
-        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        size: (std::mem::size_of::<GpuMeshMetadata>() * metadata_entries) as u64,
+        usage: wgpu::BufferUsages::STORAGE
+            | wgpu::BufferUsages::COPY_SRC
+            | wgpu::BufferUsages::COPY_DST,
         mapped_at_creation: false,
     });
 
@@ -154,6 +166,7 @@ pub fn create_mesh_bind_group(
     device: &wgpu::Device,
     layout: &wgpu::BindGroupLayout,
     world_buffer: &wgpu::Buffer,
+    block_light_buffer: &wgpu::Buffer,
     request_buffer: &wgpu::Buffer,
     mesh_buffers: &[GpuMeshBuffer],
     indirect_buffer: &wgpu::Buffer,
@@ -173,7 +186,8 @@ pub fn create_mesh_bind_group(
         3 => mesh.indices.as_entire_binding(),
         4 => mesh.metadata.as_entire_binding(),
         5 => indirect_buffer.as_entire_binding(),
-        6 => params_buffer.as_entire_binding()
+        6 => params_buffer.as_entire_binding(),
+        7 => block_light_buffer.as_entire_binding()
     )
 }
 
@@ -182,6 +196,7 @@ pub fn create_mesh_bind_group_for_buffer(
     device: &wgpu::Device,
     layout: &wgpu::BindGroupLayout,
     world_buffer: &wgpu::Buffer,
+    block_light_buffer: &wgpu::Buffer,
     request_buffer: &wgpu::Buffer,
     mesh_buffers: &[GpuMeshBuffer],
     indirect_buffer: &wgpu::Buffer,
@@ -200,8 +215,83 @@ pub fn create_mesh_bind_group_for_buffer(
         3 => mesh.indices.as_entire_binding(),
         4 => mesh.metadata.as_entire_binding(),
         5 => indirect_buffer.as_entire_binding(),
-        6 => params_buffer.as_entire_binding()
+        6 => params_buffer.as_entire_binding(),
+        7 => block_light_buffer.as_entire_binding()
     )
 }
 
-use super::{MAX_INDICES_PER_CHUNK, MAX_VERTICES_PER_CHUNK};
+/// Mesh generation shader, relative to the crate root
+pub const MESH_GENERATION_SHADER_PATH: &str = "src/shaders/mesh/mesh_generation.wgsl";
+
+/// Rebuild the pipeline a changed shader file feeds, for shader hot-reload
+///
+/// Returns `Ok(false)` when `shader_path` is not a GPU meshing shader. On
+/// error the running pipeline is kept, so a typo in the shader only costs
+/// a log line.
+pub fn recompile(state: &mut GpuMeshingState, shader_path: &Path) -> RendererResult<bool> {
+    if shader_path.file_name() != Path::new(MESH_GENERATION_SHADER_PATH).file_name() {
+        return Ok(false);
+    }
+
+    match recompile_mesh_pipeline(state, shader_path) {
+        Ok(()) => {
+            log::info!(
+                "[GPU Meshing] Reloaded mesh generation pipeline from {}",
+                shader_path.display()
+            );
+            Ok(true)
+        }
+        Err(e) => {
+            log::error!(
+                "[GPU Meshing] Keeping previous mesh pipeline, reload of {} failed: {}",
+                shader_path.display(),
+                e
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Swap in a mesh generation pipeline built from the shader on disk
+///
+/// The bind group layout, mesh buffers and indirect buffer are reused, so the
+/// edited shader must keep its bindings. Validation errors are captured in
+/// an error scope instead of reaching the device's panicking error handler.
+pub fn recompile_mesh_pipeline(
+    state: &mut GpuMeshingState,
+    shader_path: &Path,
+) -> RendererResult<()> {
+    let source = std::fs::read_to_string(shader_path).renderer_context("read mesh shader")?;
+    let processed = crate::gpu::preprocessor::preprocess_shader_content(&source, shader_path)
+        .renderer_context("preprocess mesh shader")?;
+
+    let device = &state.device;
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+    let built =
+        create_validated_shader(device, Some("mesh_generation"), &processed).and_then(|shader| {
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Mesh Generation Pipeline Layout"),
+                bind_group_layouts: &[&state.bind_group_layout],
+                push_constant_ranges: &[],
+            });
+            TypedComputePipelineBuilder::new(device)
+                .label("Mesh Generation Pipeline")
+                .layout(&pipeline_layout)
+                .shader(shader)
+                .entry_point("generate_mesh")
+                .build()
+        });
+
+    // Always pop the scope, even when the pipeline was never created
+    let validation_error = pollster::block_on(device.pop_error_scope());
+    let pipeline = built.map_err(|e| pipeline_creation_error("mesh_generation", e))?;
+    if let Some(error) = validation_error {
+        return Err(pipeline_creation_error("mesh_generation", error));
+    }
+
+    state.mesh_pipeline = pipeline;
+    Ok(())
+}
+
+use super::{MAX_CONCURRENT_MESHES, MAX_INDICES_PER_CHUNK, MAX_VERTICES_PER_CHUNK};
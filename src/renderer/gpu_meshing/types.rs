@@ -46,10 +46,17 @@ pub struct MeshRequest {
     pub buffer_index: u32,
     /// Mesh flags
     pub flags: u32,
+    /// Slot of the chunk in the world buffer, or `NO_WORLD_SLOT`; lighting
+    /// is read from there
+    pub world_slot: u32,
     /// Padding
-    pub _padding: [u32; 2],
+    pub _padding: u32,
 }
 
+/// `MeshRequest::world_slot` of a chunk that is not in the world buffer;
+/// its faces are meshed fully lit
+pub const NO_WORLD_SLOT: u32 = u32::MAX;
+
 /// Mesh generation parameters
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -70,6 +77,33 @@ pub struct MeshingParams {
     pub _padding: [u32; 2],
 }
 
+/// Request flag: hang skirts from the surface along the chunk's side faces
+///
+/// Set for reduced-detail meshes. A chunk meshed at a coarser LOD than its
+/// neighbour has a different surface along the shared face. Surface cells on
+/// the boundary emit their side face extended one cell downwards, even
+/// against solid neighbours, which covers the resulting cracks.
+pub const MESH_FLAG_SKIRTS: u32 = 1 << 0;
+
+/// Number of supported mesh LOD levels
+pub const LOD_LEVEL_COUNT: usize = 4;
+
+/// Mesh level of detail for GPU meshing
+///
+/// LOD n meshes cells of `2^n` voxels per axis, sampling one voxel per cell,
+/// so each step up cuts the face count of a surface by about four.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LodLevel(pub u32);
+
+impl LodLevel {
+    pub const FULL: LodLevel = LodLevel(0);
+}
+
+/// Edge length in voxels of one meshing cell, clamped to the supported levels
+pub fn lod_cell_size(lod: LodLevel) -> u32 {
+    1 << lod.0.min(LOD_LEVEL_COUNT as u32 - 1)
+}
+
 /// Meshing statistics
 #[derive(Default)]
 pub struct MeshingStats {
@@ -81,6 +115,31 @@ pub struct MeshingStats {
     pub total_indices: u64,
     /// Average mesh generation time (microseconds)
     pub avg_generation_time: u32,
+    /// Meshes generated per LOD level
+    pub lod_meshes: [u64; LOD_LEVEL_COUNT],
+    /// Vertices generated per LOD level
+    pub lod_vertices: [u64; LOD_LEVEL_COUNT],
+    /// Mesh slots held by chunks no longer active (see `detect_leaked_slots`)
+    pub leaked_slots: u32,
+}
+
+/// Fraction of vertices saved by `lod` relative to full detail
+///
+/// Compares average vertices per mesh, so it is only meaningful once both
+/// levels have meshed comparable terrain. `None` until both have meshes.
+pub fn lod_vertex_reduction(stats: &MeshingStats, lod: LodLevel) -> Option<f32> {
+    let level = lod.0.min(LOD_LEVEL_COUNT as u32 - 1) as usize;
+    let average = |level: usize| match stats.lod_meshes[level] {
+        0 => None,
+        meshes => Some(stats.lod_vertices[level] as f64 / meshes as f64),
+    };
+
+    let full = average(0)?;
+    let reduced = average(level)?;
+    if full == 0.0 {
+        return Some(0.0);
+    }
+    Some((1.0 - reduced / full) as f32)
 }
 
 /// Face direction for culling
@@ -138,3 +197,24 @@ pub fn pack_color_light(r: f32, g: f32, b: f32, light: f32) -> u32 {
 
     (r_bits << 24) | (g_bits << 16) | (b_bits << 8) | light_bits
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lod_vertex_reduction() {
+        assert_eq!(lod_cell_size(LodLevel::FULL), 1);
+        assert_eq!(lod_cell_size(LodLevel(1)), 2);
+        assert_eq!(lod_cell_size(LodLevel(9)), 8);
+
+        let mut stats = MeshingStats::default();
+        assert_eq!(lod_vertex_reduction(&stats, LodLevel(1)), None);
+
+        stats.lod_meshes = [4, 2, 0, 0];
+        stats.lod_vertices = [4000, 500, 0, 0];
+        assert_eq!(lod_vertex_reduction(&stats, LodLevel(1)), Some(0.75));
+        assert_eq!(lod_vertex_reduction(&stats, LodLevel::FULL), Some(0.0));
+        assert_eq!(lod_vertex_reduction(&stats, LodLevel(2)), None);
+    }
+}
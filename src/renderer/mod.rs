@@ -24,6 +24,7 @@ mod pipeline;
 mod preallocated_mesh_cache;
 mod preallocated_texture_atlas;
 mod progressive_streaming;
+mod screenshot;
 mod selection_renderer;
 // Removed: simple_async_renderer (placeholder module)
 mod soa_mesh_builder;
@@ -56,6 +57,7 @@ pub use gpu_state_operations::*;
 pub use mesh::ChunkMesh;
 pub use mesh_optimizer::MeshLod;
 pub use mesh_soa::{MeshSoA, MeshStats};
+pub use screenshot::{capture_frame, request_screenshot, save_frame_png, save_pending_screenshot};
 pub use selection_renderer::SelectionRenderer;
 // Removed: SimpleAsyncRenderer (placeholder module)
 pub use soa_mesh_builder::{GreedyMeshBuilderSoA, MeshBuilderSoA, MeshBuilderStats};
@@ -67,6 +69,8 @@ pub use zero_alloc_pools::{
 
 pub struct Renderer {
     // Will be implemented
+    /// Where to save the next frame, set by `request_screenshot`
+    pub pending_screenshot: Option<std::path::PathBuf>,
 }
 
 pub fn run<G: GameData + 'static>(
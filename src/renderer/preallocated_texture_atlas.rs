@@ -1,5 +1,8 @@
+use crate::error::EngineError;
+use crate::renderer::error::RendererResult;
 use cgmath::Vector2;
 use image::{DynamicImage, RgbaImage};
+use std::path::Path;
 /// Pre-allocated texture atlas using fixed-size array for material mappings
 /// Replaces HashMap<MaterialId, AtlasUV> with zero-allocation lookups
 use wgpu::{Device, Queue, Sampler, Texture, TextureView};
@@ -188,6 +191,80 @@ impl PreallocatedTextureAtlas {
         Some(material_id)
     }
 
+    /// Replace a material's pixels and upload only its rect
+    ///
+    /// Used for texture hot-reload: the material keeps its place and UVs, so
+    /// nothing has to be re-packed or re-meshed. The new image must have the
+    /// size the material was packed with.
+    pub fn replace_material(
+        &mut self,
+        queue: &Queue,
+        material_id: MaterialId,
+        image: &DynamicImage,
+    ) -> RendererResult<()> {
+        let rect = self
+            .packed_rects
+            .iter()
+            .find(|rect| rect.material_id == material_id)
+            .copied()
+            .ok_or_else(|| EngineError::TextureNotFound {
+                id: material_id.to_string(),
+            })?;
+
+        let rgba_image = image.to_rgba8();
+        let (width, height) = rgba_image.dimensions();
+        if (width, height) != (rect.width, rect.height) {
+            return Err(EngineError::ValidationFailed(format!(
+                "Texture for material {} is {}x{}, but its atlas slot is {}x{}",
+                material_id, width, height, rect.width, rect.height
+            )));
+        }
+
+        for (x, y, pixel) in rgba_image.enumerate_pixels() {
+            self.atlas_image.put_pixel(rect.x + x, rect.y + y, *pixel);
+        }
+
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: rect.x,
+                    y: rect.y,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &rgba_image,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Reload a material from an image file, see `replace_material`
+    pub fn reload_material_file(
+        &mut self,
+        queue: &Queue,
+        material_id: MaterialId,
+        path: &Path,
+    ) -> RendererResult<()> {
+        let image = image::open(path).map_err(|e| EngineError::AssetWatchError {
+            path: path.display().to_string(),
+            error: e.to_string(),
+        })?;
+        self.replace_material(queue, material_id, &image)
+    }
+
     /// Get UV coordinates for a material
     pub fn get_uv(&self, material_id: MaterialId) -> Option<AtlasUV> {
         if (material_id as usize) < MAX_MATERIALS {
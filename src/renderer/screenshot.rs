@@ -0,0 +1,191 @@
+//! Framebuffer capture for bug reports and thumbnails
+//!
+//! Texel bytes are copied out unchanged. An sRGB texture already stores
+//! gamma-encoded values, which is exactly what PNG expects; encoding them
+//! again is what makes captures look washed out. A plain unorm surface is
+//! displayed as its raw bytes, so copying those is correct as well.
+
+use crate::renderer::error::{gpu_operation_error, RendererErrorContext, RendererResult};
+use crate::renderer::Renderer;
+use std::path::{Path, PathBuf};
+
+/// Formats `capture_frame` can read back
+fn is_bgra(format: wgpu::TextureFormat) -> Option<bool> {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Some(false),
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => Some(true),
+        _ => None,
+    }
+}
+
+/// Row stride of the readback buffer, rounded up to the copy alignment
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
+
+/// Tightly packed RGBA8 pixels from padded readback rows
+pub fn unpad_rows(data: &[u8], width: u32, height: u32, padded_row: u32, bgra: bool) -> Vec<u8> {
+    let row_bytes = (width * 4) as usize;
+    let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+
+    for row in data.chunks(padded_row as usize).take(height as usize) {
+        pixels.extend_from_slice(&row[..row_bytes]);
+    }
+
+    if bgra {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    pixels
+}
+
+/// Copy a rendered texture back to the CPU
+///
+/// The texture must be 8-bit RGBA or BGRA and created with `COPY_SRC`; for a
+/// swapchain texture that means adding `COPY_SRC` to the surface usage.
+/// Blocks until the GPU has finished all submitted work.
+pub fn capture_frame(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> RendererResult<image::RgbaImage> {
+    let bgra = is_bgra(texture.format()).ok_or_else(|| {
+        gpu_operation_error(
+            "capture frame",
+            format!("unsupported texture format {:?}", texture.format()),
+        )
+    })?;
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err(gpu_operation_error(
+            "capture frame",
+            "texture was not created with COPY_SRC",
+        ));
+    }
+
+    let width = texture.width();
+    let height = texture.height();
+    let padded_row = padded_bytes_per_row(width);
+
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Screenshot Readback Buffer"),
+        size: padded_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Screenshot Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &readback,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let buffer_slice = readback.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+
+    receiver
+        .recv()
+        .renderer_context("screenshot map_async channel")?
+        .renderer_context("screenshot map_async")?;
+
+    let data = buffer_slice.get_mapped_range();
+    let pixels = unpad_rows(&data, width, height, padded_row, bgra);
+    drop(data);
+    readback.unmap();
+
+    image::RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| gpu_operation_error("capture frame", "pixel buffer size mismatch"))
+}
+
+/// Capture `texture` and write it to `path` as a PNG
+pub fn save_frame_png(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    path: &Path,
+) -> RendererResult<()> {
+    let image = capture_frame(device, queue, texture)?;
+    image
+        .save_with_format(path, image::ImageFormat::Png)
+        .renderer_context("write screenshot")
+}
+
+/// Ask for the next presented frame to be saved to `path`
+///
+/// A later request before that frame replaces the earlier one.
+pub fn request_screenshot(renderer: &mut Renderer, path: impl Into<PathBuf>) {
+    renderer.pending_screenshot = Some(path.into());
+}
+
+/// Save a pending screenshot from the frame just rendered into `texture`
+///
+/// Call after the frame's work is submitted and before it is presented.
+/// Returns the path written, or `None` when no screenshot was requested.
+pub fn save_pending_screenshot(
+    renderer: &mut Renderer,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> RendererResult<Option<PathBuf>> {
+    let Some(path) = renderer.pending_screenshot.take() else {
+        return Ok(None);
+    };
+    save_frame_png(device, queue, texture, &path)?;
+    log::info!("[Screenshot] Saved frame to {}", path.display());
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpad_rows_strips_padding_and_swizzles() {
+        assert_eq!(padded_bytes_per_row(1), 256);
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+
+        // Two 2-pixel BGRA rows, each padded to 256 bytes with 0xEE
+        let padded_row = padded_bytes_per_row(2);
+        let mut data = vec![0xEE; (padded_row * 2) as usize];
+        data[..8].copy_from_slice(&[1, 2, 3, 255, 4, 5, 6, 128]);
+        data[256..264].copy_from_slice(&[7, 8, 9, 255, 10, 11, 12, 0]);
+
+        let pixels = unpad_rows(&data, 2, 2, padded_row, true);
+        assert_eq!(
+            pixels,
+            vec![3, 2, 1, 255, 6, 5, 4, 128, 9, 8, 7, 255, 12, 11, 10, 0]
+        );
+
+        // RGBA rows are copied untouched - no gamma conversion
+        let pixels = unpad_rows(&data, 2, 1, padded_row, false);
+        assert_eq!(pixels, vec![1, 2, 3, 255, 4, 5, 6, 128]);
+    }
+}
@@ -1,4 +1,8 @@
-use glam::Vec2;
+mod font;
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat4, Vec2};
+use wgpu::util::DeviceExt;
 
 /// UI Color representation
 #[derive(Debug, Clone, Copy)]
@@ -91,21 +95,361 @@ pub enum UIElement {
     },
 }
 
+/// UI vertex in screen pixels, origin at the top-left corner
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct UIVertex {
+    pub position: [f32; 2],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+pub fn ui_vertex_buffer_layout<'a>() -> wgpu::VertexBufferLayout<'a> {
+    wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<UIVertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &[
+            // Position
+            wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2,
+            },
+            // Atlas UV
+            wgpu::VertexAttribute {
+                offset: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                shader_location: 1,
+                format: wgpu::VertexFormat::Float32x2,
+            },
+            // Color
+            wgpu::VertexAttribute {
+                offset: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+                shader_location: 2,
+                format: wgpu::VertexFormat::Float32x4,
+            },
+        ],
+    }
+}
+
+/// Orthographic projection mapping pixels (y down) to clip space
+pub fn ui_projection(screen_size: Vec2) -> Mat4 {
+    Mat4::orthographic_rh(0.0, screen_size.x, screen_size.y, 0.0, -1.0, 1.0)
+}
+
+/// Atlas UV rectangle (min, max) of a font atlas cell
+fn cell_uv(cell: u32, width: u32, height: u32) -> ([f32; 2], [f32; 2]) {
+    let (x, y) = font::cell_origin(cell);
+    let atlas_width = font::ATLAS_WIDTH as f32;
+    let atlas_height = font::ATLAS_HEIGHT as f32;
+    (
+        [x as f32 / atlas_width, y as f32 / atlas_height],
+        [
+            (x + width) as f32 / atlas_width,
+            (y + height) as f32 / atlas_height,
+        ],
+    )
+}
+
+/// Append two triangles covering `min..max` in pixels
+fn push_quad(
+    vertices: &mut Vec<UIVertex>,
+    min: Vec2,
+    max: Vec2,
+    uv: ([f32; 2], [f32; 2]),
+    color: [f32; 4],
+) {
+    let (uv_min, uv_max) = uv;
+    let corner = |x: f32, y: f32, u: f32, v: f32| UIVertex {
+        position: [x, y],
+        uv: [u, v],
+        color,
+    };
+    let top_left = corner(min.x, min.y, uv_min[0], uv_min[1]);
+    let top_right = corner(max.x, min.y, uv_max[0], uv_min[1]);
+    let bottom_left = corner(min.x, max.y, uv_min[0], uv_max[1]);
+    let bottom_right = corner(max.x, max.y, uv_max[0], uv_max[1]);
+
+    vertices.extend_from_slice(&[
+        top_left,
+        bottom_left,
+        top_right,
+        top_right,
+        bottom_left,
+        bottom_right,
+    ]);
+}
+
+/// Triangle list for the queued elements, in draw order
+///
+/// Outlines become four strips of `border_width` (at least one pixel) inside
+/// the rect. Text `size` is the glyph height in pixels; `\n` starts a new line.
+pub fn build_ui_vertices(elements: &[UIElement]) -> Vec<UIVertex> {
+    let mut vertices = Vec::new();
+    let solid = cell_uv(font::SOLID_CELL, 1, 1);
+
+    for element in elements {
+        match element {
+            UIElement::Rect {
+                rect,
+                color,
+                filled,
+                border_width,
+            } => {
+                let min = Vec2::new(rect.x, rect.y);
+                let max = min + Vec2::new(rect.width, rect.height);
+                let color = color.to_array();
+
+                if *filled {
+                    push_quad(&mut vertices, min, max, solid, color);
+                    continue;
+                }
+
+                // Strips overlap rather than cross when the border is thicker than the rect
+                let border = Vec2::splat(border_width.max(1.0))
+                    .min(Vec2::new(rect.width, rect.height) * 0.5);
+                let inner_min = min + border;
+                let inner_max = max - border;
+                push_quad(
+                    &mut vertices,
+                    min,
+                    Vec2::new(max.x, inner_min.y),
+                    solid,
+                    color,
+                );
+                push_quad(
+                    &mut vertices,
+                    Vec2::new(min.x, inner_max.y),
+                    max,
+                    solid,
+                    color,
+                );
+                push_quad(
+                    &mut vertices,
+                    Vec2::new(min.x, inner_min.y),
+                    Vec2::new(inner_min.x, inner_max.y),
+                    solid,
+                    color,
+                );
+                push_quad(
+                    &mut vertices,
+                    Vec2::new(inner_max.x, inner_min.y),
+                    Vec2::new(max.x, inner_max.y),
+                    solid,
+                    color,
+                );
+            }
+            UIElement::Text {
+                text,
+                position,
+                size,
+                color,
+            } => {
+                let scale = size / font::GLYPH_HEIGHT as f32;
+                let glyph_size = Vec2::new(font::GLYPH_WIDTH as f32, font::GLYPH_HEIGHT as f32);
+                let advance = (font::GLYPH_WIDTH + 1) as f32 * scale;
+                let line_height = font::CELL_SIZE as f32 * scale;
+                let color = color.to_array();
+                let mut pen = *position;
+
+                for c in text.chars() {
+                    if c == '\n' {
+                        pen = Vec2::new(position.x, pen.y + line_height);
+                        continue;
+                    }
+                    if !c.is_whitespace() {
+                        let uv =
+                            cell_uv(font::glyph_cell(c), font::GLYPH_WIDTH, font::GLYPH_HEIGHT);
+                        push_quad(&mut vertices, pen, pen + glyph_size * scale, uv, color);
+                    }
+                    pen.x += advance;
+                }
+            }
+        }
+    }
+
+    vertices
+}
+
+/// Uniform block of `ui.wgsl`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct UIUniform {
+    projection: [[f32; 4]; 4],
+}
+
 /// UI Renderer for immediate mode UI
+///
+/// Elements queued during a frame are drawn on top of whatever `render`'s
+/// target already holds, alpha blended in queue order.
 pub struct UIRenderer {
     device: wgpu::Device,
     queue: wgpu::Queue,
     elements: Vec<UIElement>,
     screen_size: Vec2,
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
 }
 
 impl UIRenderer {
-    pub fn new(device: wgpu::Device, queue: wgpu::Queue, width: f32, height: f32) -> Self {
+    /// `format` is the format of the views later passed to `render`
+    pub fn new(
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        format: wgpu::TextureFormat,
+        width: f32,
+        height: f32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("UI Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../shaders/rendering/ui.wgsl").into(),
+            ),
+        });
+
+        let atlas_size = wgpu::Extent3d {
+            width: font::ATLAS_WIDTH,
+            height: font::ATLAS_HEIGHT,
+            depth_or_array_layers: 1,
+        };
+        let atlas = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("UI Font Atlas"),
+            size: atlas_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &atlas,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &font::build_atlas(),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(font::ATLAS_WIDTH),
+                rows_per_image: Some(font::ATLAS_HEIGHT),
+            },
+            atlas_size,
+        );
+        let atlas_view = atlas.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Nearest filtering keeps the pixel font crisp at integer scales
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("UI Font Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("UI Uniform Buffer"),
+            contents: bytemuck::bytes_of(&UIUniform {
+                projection: ui_projection(Vec2::new(width, height)).to_cols_array_2d(),
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("UI Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("UI Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("UI Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("UI Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[ui_vertex_buffer_layout()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                // Overlay quads are never back-facing in a meaningful way
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
         Self {
             device,
             queue,
             elements: Vec::new(),
             screen_size: Vec2::new(width, height),
+            pipeline,
+            uniform_buffer,
+            bind_group,
         }
     }
 
@@ -144,12 +488,122 @@ impl UIRenderer {
         });
     }
 
+    /// Vertices `render` would upload for the elements queued so far
+    pub fn vertices(&self) -> Vec<UIVertex> {
+        build_ui_vertices(&self.elements)
+    }
+
+    /// Draw the queued elements over `view`, keeping its existing contents
     pub fn render(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
-        // TODO: Implement actual rendering
-        // For now, this is a placeholder
-        // In a real implementation, this would:
-        // 1. Create vertex buffers for all UI elements
-        // 2. Set up a 2D orthographic projection
-        // 3. Render all elements to the screen
+        let vertices = self.vertices();
+        if vertices.is_empty() {
+            return;
+        }
+
+        self.queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&UIUniform {
+                projection: ui_projection(self.screen_size).to_cols_array_2d(),
+            }),
+        );
+
+        let vertex_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("UI Vertex Buffer"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("UI Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.draw(0..vertices.len() as u32, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_elements_build_expected_vertices() {
+        let translucent = UIColor::new(0.2, 0.4, 0.6, 0.5);
+        let elements = vec![
+            UIElement::Rect {
+                rect: UIRect::new(10.0, 20.0, 100.0, 50.0),
+                color: translucent,
+                filled: true,
+                border_width: 0.0,
+            },
+            UIElement::Rect {
+                rect: UIRect::new(0.0, 0.0, 40.0, 30.0),
+                color: UIColor::RED,
+                filled: false,
+                border_width: 4.0,
+            },
+            UIElement::Text {
+                text: "Hi !".to_string(),
+                position: Vec2::new(5.0, 6.0),
+                size: 14.0,
+                color: UIColor::WHITE,
+            },
+        ];
+
+        let vertices = build_ui_vertices(&elements);
+        // Filled rect: 1 quad, outline: 4 quads, text: 3 glyphs (space skipped)
+        assert_eq!(vertices.len(), 6 * (1 + 4 + 3));
+
+        let fill = &vertices[..6];
+        assert!(fill.iter().all(|v| v.color == [0.2, 0.4, 0.6, 0.5]));
+        assert_eq!(fill[0].position, [10.0, 20.0]);
+        assert_eq!(fill[5].position, [110.0, 70.0]);
+
+        // Top strip of the outline is exactly border_width tall
+        let top = &vertices[6..12];
+        assert_eq!(top[0].position, [0.0, 0.0]);
+        assert_eq!(top[5].position, [40.0, 4.0]);
+        let covered: f32 = vertices[6..30]
+            .chunks(6)
+            .map(|quad| {
+                (quad[5].position[0] - quad[0].position[0])
+                    * (quad[5].position[1] - quad[0].position[1])
+            })
+            .sum();
+        assert_eq!(covered, 40.0 * 30.0 - 32.0 * 22.0);
+
+        // Glyphs are 5x7 texels scaled to `size`, advancing one column of spacing
+        let h = &vertices[30..36];
+        assert_eq!(h[0].position, [5.0, 6.0]);
+        assert_eq!(h[5].position, [15.0, 20.0]);
+        let bang = &vertices[42..48];
+        assert_eq!(bang[0].position, [5.0 + 3.0 * 12.0, 6.0]);
+        assert_ne!(h[0].uv, bang[0].uv);
+    }
+
+    #[test]
+    fn test_lowercase_and_unknown_glyphs() {
+        assert_eq!(font::glyph_cell('h'), font::glyph_cell('H'));
+        assert_eq!(font::glyph_cell('~'), font::glyph_cell('?'));
+
+        let atlas = font::build_atlas();
+        let (x, y) = font::cell_origin(font::SOLID_CELL);
+        assert_eq!(atlas[(y * font::ATLAS_WIDTH + x) as usize], 255);
     }
 }
@@ -16,7 +16,6 @@ struct BlockQueryResult {
 
 struct PushConstants {
     query_count: u32,
-    chunk_size: u32,
 }
 
 // World buffer binding
@@ -31,11 +30,12 @@ var<storage, read> queries: array<BlockQueryRequest>;
 @group(0) @binding(2)
 var<storage, read_write> results: array<BlockQueryResult>;
 
-var<push_constant> constants: PushConstants;
+// Voxel buffer index of each request, resolved on the CPU through the
+// WorldBuffer chunk slot map; 0xFFFFFFFF for chunks that are not resident
+@group(0) @binding(3)
+var<storage, read> buffer_indices: array<u32>;
 
-// Constants matching CPU side
-const WORLD_SIZE: u32 = 256u;
-const MAX_HEIGHT: u32 = 256u;
+var<push_constant> constants: PushConstants;
 
 // Extract block ID from packed voxel data
 fn extract_block_id(voxel_data: u32) -> u32 {
@@ -57,41 +57,6 @@ fn extract_metadata(voxel_data: u32) -> u32 {
     return (voxel_data >> 24u) & 0xFu;
 }
 
-// Morton encoding functions from unified GPU system
-#include "morton.wgsl"
-
-// Calculate world buffer index for a world position
-fn world_position_to_buffer_index(world_pos: vec3<i32>) -> u32 {
-    // Convert world position to chunk position
-    let chunk_pos = world_pos / i32(constants.chunk_size);
-    
-    // Check bounds
-    if (chunk_pos.x < 0 || chunk_pos.x >= i32(WORLD_SIZE) ||
-        chunk_pos.y < 0 || chunk_pos.y >= i32(MAX_HEIGHT / constants.chunk_size) ||
-        chunk_pos.z < 0 || chunk_pos.z >= i32(WORLD_SIZE)) {
-        return 0xFFFFFFFFu; // Invalid index
-    }
-    
-    // Calculate chunk index in world
-    let chunk_index = u32(chunk_pos.x) + 
-                     u32(chunk_pos.z) * WORLD_SIZE + 
-                     u32(chunk_pos.y) * WORLD_SIZE * WORLD_SIZE;
-    
-    // Calculate local position within chunk
-    let local_pos = world_pos - (chunk_pos * i32(constants.chunk_size));
-    
-    // Morton encode local position
-    let local_index = morton_encode_3d(
-        u32(local_pos.x),
-        u32(local_pos.y),
-        u32(local_pos.z)
-    );
-    
-    // Calculate final buffer index
-    let voxels_per_chunk = constants.chunk_size * constants.chunk_size * constants.chunk_size;
-    return chunk_index * voxels_per_chunk + local_index;
-}
-
 @compute @workgroup_size(256)
 fn query_blocks(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let query_idx = global_id.x;
@@ -111,8 +76,8 @@ fn query_blocks(@builtin(global_invocation_id) global_id: vec3<u32>) {
     result.value = 0u;
     result.success = 0u;
     
-    // Calculate buffer index
-    let buffer_idx = world_position_to_buffer_index(request.position);
+    // Buffer index resolved on the CPU
+    let buffer_idx = buffer_indices[query_idx];
     
     // Check if valid
     if (buffer_idx != 0xFFFFFFFFu && buffer_idx < arrayLength(&world_voxels)) {
@@ -17,23 +17,26 @@ struct BvhNode {
     prim_count: u32,
 }
 
+// Field order keeps each vec3 at the start of a 16-byte row, matching
+// PhysicsQuery and QueryResult in hierarchical_physics.rs
 struct PhysicsQuery {
-    query_type: u32,
     origin: vec3<f32>,
-    max_distance: f32,
+    query_type: u32,
     direction: vec3<f32>,
-    radius: f32,
+    max_distance: f32,
     half_extents: vec3<f32>,
+    radius: f32,
     flags: u32,
+    padding: array<u32, 3>,
 }
 
 struct QueryResult {
-    hit_distance: f32,
     hit_position: vec3<f32>,
+    hit_distance: f32,
     hit_normal: vec3<f32>,
     block_id: u32,
+    hit_voxel: vec3<i32>,
     chunk_index: u32,
-    padding: array<u32, 3>,
 }
 
 @group(0) @binding(0) var<storage, read> world_voxels: array<VoxelData>;
@@ -42,8 +45,24 @@ struct QueryResult {
 @group(0) @binding(3) var<storage, read> queries: array<PhysicsQuery>;
 @group(0) @binding(4) var<storage, read_write> results: array<QueryResult>;
 
+// WorldBuffer slots of the chunk box the batch can reach, row-major in x,
+// then y, then z; filled on the CPU from the WorldBuffer slot map
+struct SlotTable {
+    min_chunk: vec3<i32>,
+    _pad0: u32,
+    dims: vec3<u32>,
+    _pad1: u32,
+    slots: array<u32>,
+}
+
+@group(0) @binding(5) var<storage, read> slot_table: SlotTable;
+
 // CHUNK_SIZE is auto-generated from constants.rs
 const EPSILON: f32 = 0.0001;
+// Ray parameter for axes the ray never crosses
+const RAY_NEVER: f32 = 1.0e30;
+// Slot table entry of a chunk that is not resident
+const NO_SLOT: u32 = 0xFFFFFFFFu;
 
 // DDA voxel traversal for ray casting
 struct DDAState {
@@ -54,30 +73,29 @@ struct DDAState {
     normal: vec3<f32>,
 }
 
+// Ray parameter of the first voxel boundary crossed along one axis
+fn axis_t_max(origin: f32, pos: i32, direction: f32) -> f32 {
+    if direction > 0.0 {
+        return (f32(pos + 1) - origin) / direction;
+    }
+    if direction < 0.0 {
+        return (origin - f32(pos)) / -direction;
+    }
+    return RAY_NEVER;
+}
+
 fn init_dda(origin: vec3<f32>, direction: vec3<f32>) -> DDAState {
     let pos = vec3<i32>(floor(origin));
     let step = vec3<i32>(sign(direction));
     
-    let t_delta = abs(1.0 / direction);
-    
-    var t_max: vec3<f32>;
-    if direction.x > 0.0 {
-        t_max.x = (f32(pos.x + 1) - origin.x) / direction.x;
-    } else {
-        t_max.x = (origin.x - f32(pos.x)) / -direction.x;
-    }
+    // Axis-parallel rays never step along their zero components
+    let t_delta = select(abs(1.0 / direction), vec3(RAY_NEVER), direction == vec3(0.0));
     
-    if direction.y > 0.0 {
-        t_max.y = (f32(pos.y + 1) - origin.y) / direction.y;
-    } else {
-        t_max.y = (origin.y - f32(pos.y)) / -direction.y;
-    }
-    
-    if direction.z > 0.0 {
-        t_max.z = (f32(pos.z + 1) - origin.z) / direction.z;
-    } else {
-        t_max.z = (origin.z - f32(pos.z)) / -direction.z;
-    }
+    let t_max = vec3<f32>(
+        axis_t_max(origin.x, pos.x, direction.x),
+        axis_t_max(origin.y, pos.y, direction.y),
+        axis_t_max(origin.z, pos.z, direction.z),
+    );
     
     return DDAState(pos, t_max, t_delta, step, vec3(0.0));
 }
@@ -102,16 +120,28 @@ fn step_dda(state: ptr<function, DDAState>) -> f32 {
     return t_min;
 }
 
-// Get voxel using Morton encoding
+// Read a voxel from its chunk's WorldBuffer slot
+//
+// Voxels sit at `slot * VOXELS_PER_CHUNK + x + y * CHUNK_SIZE + z * CHUNK_SIZE²`.
+// Chunks outside the slot table or without a slot read as air.
 fn get_voxel(world_pos: vec3<i32>) -> VoxelData {
-    let chunk_pos = world_pos / i32(CHUNK_SIZE);
-    let local_pos = world_pos % i32(CHUNK_SIZE);
-    
-    let chunk_morton = morton_encode_3d(u32(chunk_pos.x), u32(chunk_pos.y), u32(chunk_pos.z));
-    let voxel_morton = morton_encode_3d(u32(local_pos.x), u32(local_pos.y), u32(local_pos.z));
-    
-    let global_index = chunk_morton * (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) + voxel_morton;
+    let size = i32(CHUNK_SIZE);
+    // Floor division, so negative coordinates land in the chunk below
+    let chunk_pos = (world_pos - select(vec3(0), vec3(size - 1), world_pos < vec3(0))) / size;
+    let local_pos = world_pos - chunk_pos * size;
+    
+    let cell = chunk_pos - slot_table.min_chunk;
+    let dims = slot_table.dims;
+    if any(cell < vec3(0)) || any(vec3<u32>(cell) >= dims) {
+        return VoxelData(0u);
+    }
+    let slot = slot_table.slots[u32(cell.x) + (u32(cell.y) + u32(cell.z) * dims.y) * dims.x];
+    if slot == NO_SLOT {
+        return VoxelData(0u);
+    }
     
+    let local_index = u32(local_pos.x + (local_pos.y + local_pos.z * size) * size);
+    let global_index = slot * VOXELS_PER_CHUNK + local_index;
     if global_index < arrayLength(&world_voxels) {
         return world_voxels[global_index];
     }
@@ -119,9 +149,6 @@ fn get_voxel(world_pos: vec3<i32>) -> VoxelData {
     return VoxelData(0u);
 }
 
-// Morton encoding functions from unified GPU system
-#include "morton.wgsl"
-
 // Ray-AABB intersection
 fn ray_aabb_intersect(
     ray_origin: vec3<f32>,
@@ -234,9 +261,9 @@ fn raycast_query(@builtin(global_invocation_id) global_id: vec3<u32>) {
     result.hit_distance = -1.0;
     result.block_id = 0u;
     
-    // Early rejection using octree
+    // Early rejection using octree, skipped while the octree has no root
     let ray_inv_dir = 1.0 / query.direction;
-    if !octree_traverse(query.origin, query.direction, ray_inv_dir, query.max_distance) {
+    if octree_nodes[0].metadata != 0u && !octree_traverse(query.origin, query.direction, ray_inv_dir, query.max_distance) {
         results[query_id] = result;
         return;
     }
@@ -255,6 +282,7 @@ fn raycast_query(@builtin(global_invocation_id) global_id: vec3<u32>) {
             result.hit_position = query.origin + query.direction * distance;
             result.hit_normal = dda.normal;
             result.block_id = block_id;
+            result.hit_voxel = dda.pos;
             result.chunk_index = u32(dda.pos.x / i32(CHUNK_SIZE) + 
                                    dda.pos.y / i32(CHUNK_SIZE) * 16 + 
                                    dda.pos.z / i32(CHUNK_SIZE) * 256);
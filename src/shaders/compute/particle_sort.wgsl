@@ -0,0 +1,84 @@
+// Back-to-front depth sort of live particles
+// Keys are squared camera distances; a bitonic network over a power-of-two
+// entry count orders them far to near. Dead particles and padding entries get
+// a negative key so they end up after every live particle.
+
+struct ParticleData {
+    position: vec3<f32>,
+    size: f32,
+    velocity: vec3<f32>,
+    lifetime: f32,
+    acceleration: vec3<f32>,
+    max_lifetime: f32,
+    color: vec4<f32>,
+    gravity_multiplier: f32,
+    drag: f32,
+    bounce: f32,
+    rotation: f32,
+    rotation_speed: f32,
+    particle_type: u32,
+    texture_frame: u32,
+    size_curve_type: u32,
+    color_curve_type: u32,
+}
+
+struct SortEntry {
+    distance_sq: f32,
+    index: u32,
+}
+
+struct SortParams {
+    camera_position: vec3<f32>,
+    particle_count: u32,
+    entry_count: u32,
+}
+
+// One compare-exchange pass of the network, selected by dynamic offset
+struct BitonicStep {
+    j: u32,
+    k: u32,
+}
+
+@group(0) @binding(0) var<storage, read> particles: array<ParticleData>;
+@group(0) @binding(1) var<storage, read_write> entries: array<SortEntry>;
+@group(0) @binding(2) var<uniform> sort: SortParams;
+@group(0) @binding(3) var<uniform> network_pass: BitonicStep;
+
+@compute @workgroup_size(64, 1, 1)
+fn compute_sort_keys(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let i = global_id.x;
+    if i >= sort.entry_count {
+        return;
+    }
+
+    var entry: SortEntry;
+    entry.index = i;
+    entry.distance_sq = -1.0;
+    if i < sort.particle_count && particles[i].lifetime > 0.0 {
+        let offset = particles[i].position - sort.camera_position;
+        entry.distance_sq = dot(offset, offset);
+    }
+    entries[i] = entry;
+}
+
+@compute @workgroup_size(64, 1, 1)
+fn bitonic_step(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let i = global_id.x;
+    if i >= sort.entry_count {
+        return;
+    }
+
+    let partner = i ^ network_pass.j;
+    if partner <= i {
+        return;
+    }
+
+    // Blocks with bit k clear sort descending, so the final order is far to near
+    let a = entries[i];
+    let b = entries[partner];
+    let descending = (i & network_pass.k) == 0u;
+    if (a.distance_sq < b.distance_sq) == descending {
+        entries[i] = b;
+        entries[partner] = a;
+    }
+}
@@ -79,6 +79,33 @@ fn get_weather_surface_block(base_block: u32, world_y: f32, temperature: i32) ->
     return base_block;
 }
 
+// Surface wave in thousandths - line-for-line copy of terrain_wave_milli in
+// world/generation/stages.rs. Integer-only so CPU and GPU heights are identical;
+// change both together.
+fn terrain_wave_milli(phase: i32) -> i32 {
+    let half_period = TERRAIN_WAVE_PERIOD / 2;
+    let r = phase % TERRAIN_WAVE_PERIOD;
+    let t = select(r, r + TERRAIN_WAVE_PERIOD, r < 0);
+    var u = t;
+    var wave_sign = 1;
+    if (t >= half_period) {
+        u = t - half_period;
+        wave_sign = -1;
+    }
+    let p = u * (half_period - u);
+    return wave_sign * 16000 * p / (5 * half_period * half_period - 4 * p);
+}
+
+// Y of the grass block at a column - copy of terrain_surface_y in stages.rs
+fn terrain_surface_y(world_x: i32, world_z: i32) -> i32 {
+    // Quarter-period offset turns the z wave into a cosine
+    let quarter = TERRAIN_WAVE_PERIOD / 4;
+    let waves = terrain_wave_milli(world_x) + terrain_wave_milli(world_z + quarter);
+    let scaled = TERRAIN_WAVE_AMPLITUDE * waves;
+    // Floor division (i32 `/` truncates toward zero)
+    return TERRAIN_THRESHOLD + select(scaled / 1000, (scaled - 999) / 1000, scaled < 0);
+}
+
 // Wrapper for 3D noise (using 3D perlin from included file)
 fn noise3d(x: f32, y: f32, z: f32) -> f32 {
     return perlin3d(x, y, z);
@@ -169,9 +196,8 @@ fn generate_terrain(
                 var skylight = 15u; // Full skylight by default
                 
                 // Improved terrain generation with height variation and proper surface topology
-                // Calculate terrain height with variation (matching CPU fallback algorithm)
-                let height_variation = sin(world_x * 0.05) * 5.0 + cos(world_z * 0.05) * 5.0;
-                let surface_height = f32(TERRAIN_THRESHOLD) + height_variation;
+                // Integer surface height shared with the CPU terrain stage
+                let surface_height = f32(terrain_surface_y(chunk_world_x + i32(x), chunk_world_z + i32(z)));
                 
                 if (world_y < surface_height - 3.0) {
                     // Deep underground: stone
@@ -368,9 +394,8 @@ fn generate_terrain_vectorized(
                     var block_id = BLOCK_AIR;
                     var skylight = 15u;
                     
-                    // Calculate terrain height with variation (matching CPU fallback algorithm)
-                    let height_variation = sin(world_x * 0.05) * 5.0 + cos(world_z * 0.05) * 5.0;
-                    let surface_height = f32(TERRAIN_THRESHOLD) + height_variation;
+                    // Integer surface height shared with the CPU terrain stage
+                    let surface_height = f32(terrain_surface_y(i32(world_x), i32(world_z)));
                     
                     if (world_y < surface_height - 3.0) {
                         // Deep underground: stone
@@ -460,9 +485,8 @@ fn generate_terrain_vectorized(
                     var block_id = BLOCK_AIR;
                     var skylight = 15u;
                     
-                    // Calculate terrain height with variation (matching CPU fallback algorithm)
-                    let height_variation = sin(world_x * 0.05) * 5.0 + cos(world_z_offset * 0.05) * 5.0;
-                    let surface_height = 64.0 + height_variation;
+                    // Integer surface height shared with the CPU terrain stage
+                    let surface_height = f32(terrain_surface_y(i32(world_x), i32(world_z_offset)));
                     
                     if (world_y < surface_height - 3.0) {
                         // Deep underground: stone
@@ -5,6 +5,18 @@
 // CHUNK_SIZE is auto-generated from constants.rs
 const WORKGROUP_SIZE: u32 = 64u; // 4x4x4 voxels
 
+// Highest LOD level; cells are (1 << lod_level) voxels per axis
+const MAX_LOD_LEVEL: u32 = 3u;
+
+// Request flags (see gpu_meshing/types.rs)
+const MESH_FLAG_SKIRTS: u32 = 1u;
+
+// World slot of a chunk that is not in the world buffer (see gpu_meshing/types.rs)
+const NO_WORLD_SLOT: u32 = 0xFFFFFFFFu;
+
+// Highest light level of a channel
+const MAX_LIGHT_LEVEL: f32 = 15.0;
+
 // Face constants for clarity
 // Faces are encoded as: 0=+X, 1=-X, 2=+Y, 3=-Y, 4=+Z, 5=-Z
 
@@ -14,7 +26,8 @@ struct MeshRequest {
     lod_level: u32,
     buffer_index: u32,
     flags: u32,
-    _padding: vec2<u32>,
+    world_slot: u32,
+    _padding: u32,
 }
 
 // Meshing parameters
@@ -55,6 +68,9 @@ struct Vertex {
 @group(0) @binding(4) var<storage, read_write> metadata: array<MeshMetadata>;
 @group(0) @binding(5) var<storage, read_write> indirect_commands: array<u32>;
 @group(0) @binding(6) var<uniform> params: MeshingParams;
+// Block light per voxel as 0x0RGB u16s, two per word, in the world buffer's
+// slot layout (see WorldBuffer::block_light_buffer)
+@group(0) @binding(7) var<storage, read> block_light: array<u32>;
 
 // Shared memory for face culling
 var<workgroup> voxel_cache: array<u32, 512>; // 8x8x8 with padding
@@ -84,6 +100,28 @@ fn is_transparent(voxel: u32) -> bool {
     return voxel == 0u || voxel == 6u; // AIR (0) or WATER (6)
 }
 
+// Light of a voxel in the request's chunk, per channel 0-1: the brighter of
+// its skylight and each block light channel. Voxels outside the chunk, or in
+// a chunk without a world slot, are treated as open sky.
+fn sample_light(world_slot: u32, local: vec3<i32>) -> vec3<f32> {
+    let size = params.chunk_size;
+    if (world_slot == NO_WORLD_SLOT ||
+        any(local < vec3<i32>(0)) || any(local >= vec3<i32>(i32(size)))) {
+        return vec3<f32>(1.0);
+    }
+    
+    let voxel = vec3<u32>(local);
+    let index = world_slot * size * size * size + voxel.x + voxel.y * size + voxel.z * size * size;
+    let sky = f32((world_data[index] >> 20u) & 0xFu);
+    let packed = (block_light[index / 2u] >> ((index & 1u) * 16u)) & 0xFFFu;
+    let block = vec3<f32>(
+        f32((packed >> 8u) & 0xFu),
+        f32((packed >> 4u) & 0xFu),
+        f32(packed & 0xFu)
+    );
+    return max(vec3<f32>(sky), block) / MAX_LIGHT_LEVEL;
+}
+
 // Compute face vertex position algorithmically
 // Face encoding: 0=+X, 1=-X, 2=+Y, 3=-Y, 4=+Z, 5=-Z
 // Vertex encoding follows quad winding: 0=BL, 1=BR, 2=TR, 3=TL
@@ -123,26 +161,48 @@ fn compute_face_normal(face: u32) -> vec3<f32> {
 }
 
 // Add a face to the mesh
+// `extent` is the cell size in voxels per axis; `skirt_depth` lowers the
+// bottom edge of a side face so it hangs below the cell as a skirt.
+// `light` is the per-channel light in front of the face (see sample_light).
+// Faces that no longer fit this slot's region of the shared buffers are
+// dropped; the counters are clamped back once the chunk is done.
 fn add_face(
-    request_idx: u32,
+    slot: u32,
     local_pos: vec3<f32>,
     face: u32,
-    voxel_type: u32
+    voxel_type: u32,
+    extent: vec3<f32>,
+    skirt_depth: f32,
+    light: vec3<f32>
 ) {
-    let base_vertex_offset = request_idx * params.max_vertices;
-    let base_index_offset = request_idx * params.max_indices;
+    let base_vertex_offset = slot * params.max_vertices;
+    let base_index_offset = slot * params.max_indices;
     
-    // Get current counts
-    let vertex_idx = atomicAdd(&metadata[request_idx].vertex_count, 4u);
-    let index_idx = atomicAdd(&metadata[request_idx].index_count, 6u);
+    // Reserve vertices first, so a face that fails to get indices only
+    // leaves unreferenced vertices behind, never unwritten indices
+    let vertex_idx = atomicAdd(&metadata[slot].vertex_count, 4u);
+    if (vertex_idx + 4u > params.max_vertices) {
+        return;
+    }
+    let index_idx = atomicAdd(&metadata[slot].index_count, 6u);
+    if (index_idx + 6u > params.max_indices) {
+        return;
+    }
     
-    // Get face color
-    let color = get_voxel_color(voxel_type);
+    // Brightness goes to the vertex light, the hue of colored light tints
+    // the face color
+    let brightness = max(light.r, max(light.g, light.b));
+    let tint = select(vec3<f32>(1.0), light / brightness, brightness > 0.0);
+    let color = get_voxel_color(voxel_type) * tint;
     let normal = compute_face_normal(face);
     
     // Add vertices
     for (var i = 0u; i < 4u; i = i + 1u) {
-        let vertex_pos = local_pos + compute_face_vertex(face, i);
+        var vertex_pos = local_pos + compute_face_vertex(face, i) * extent;
+        // Bottom edge of side faces (BL, BR)
+        if (face != 2u && face != 3u && i < 2u) {
+            vertex_pos.y = vertex_pos.y - skirt_depth;
+        }
         let vertex_offset = base_vertex_offset + vertex_idx + i;
         
         // Create vertex with all attributes
@@ -150,7 +210,7 @@ fn add_face(
         vertex.position = vertex_pos;
         vertex.color = color;
         vertex.normal = normal;
-        vertex.light = 1.0;  // Full light for now
+        vertex.light = brightness;
         vertex.ao = 1.0;     // No ambient occlusion for now
         
         vertices[vertex_offset] = vertex;
@@ -191,6 +251,7 @@ fn get_voxel_color(voxel_type: u32) -> vec3<f32> {
 fn generate_mesh(
     @builtin(global_invocation_id) global_id: vec3<u32>,
     @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
     @builtin(workgroup_id) workgroup_id: vec3<u32>
 ) {
     // Each workgroup processes one chunk
@@ -200,59 +261,101 @@ fn generate_mesh(
     }
     
     let request = requests[request_idx];
+    // Each chunk owns the region of the shared buffers given by its slot
+    let slot = request.buffer_index;
     let chunk_origin = vec3<i32>(request.chunk_pos) * i32(params.chunk_size);
     
-    // Each thread processes one voxel
-    let voxel_offset = vec3<i32>(local_id);
+    // LOD n meshes cells of 2^n voxels, sampling the voxel at each cell origin.
+    // Cells are rounded up so the last, partial cell still reaches the chunk edge.
+    let cell_size = 1u << min(request.lod_level, MAX_LOD_LEVEL);
+    let cells_per_axis = (params.chunk_size + cell_size - 1u) / cell_size;
+    let cell_count = cells_per_axis * cells_per_axis * cells_per_axis;
+    let skirts = (request.flags & MESH_FLAG_SKIRTS) != 0u;
     
-    // Only process voxels within chunk bounds
-    if (voxel_offset.x < i32(params.chunk_size) &&
-        voxel_offset.y < i32(params.chunk_size) &&
-        voxel_offset.z < i32(params.chunk_size)) {
-        
+    // Threads stride over the cells of the chunk
+    for (var cell_index = local_index; cell_index < cell_count; cell_index = cell_index + WORKGROUP_SIZE) {
+        let cell = vec3<i32>(
+            i32(cell_index % cells_per_axis),
+            i32((cell_index / cells_per_axis) % cells_per_axis),
+            i32(cell_index / (cells_per_axis * cells_per_axis))
+        );
+        let voxel_offset = cell * i32(cell_size);
         let world_pos = chunk_origin + voxel_offset;
         let voxel = get_voxel(world_pos);
         
         // Skip air voxels
         if (!is_transparent(voxel)) {
             let local_pos = vec3<f32>(voxel_offset);
+            // Partial cells at the far edge stop at the chunk boundary
+            let extent = vec3<f32>(min(
+                vec3<u32>(cell_size),
+                vec3<u32>(params.chunk_size) - vec3<u32>(voxel_offset)
+            ));
+            let above = get_voxel(world_pos + vec3<i32>(0, i32(cell_size), 0));
             
             // Check all 6 faces
             for (var face = 0u; face < 6u; face = face + 1u) {
-                let normal = compute_face_normal(face);
-                let neighbor_pos = world_pos + vec3<i32>(normal);
+                let normal = vec3<i32>(compute_face_normal(face));
+                let neighbor_cell = cell + normal;
+                // Neighbours beyond a partial cell start at the chunk edge
+                let neighbor_pos = chunk_origin + min(
+                    neighbor_cell * i32(cell_size),
+                    vec3<i32>(i32(params.chunk_size))
+                );
                 let neighbor = get_voxel(neighbor_pos);
                 
-                // Only add face if neighbor is transparent
-                if (is_transparent(neighbor)) {
-                    add_face(request_idx, local_pos, face, voxel);
+                let on_boundary = any(neighbor_cell < vec3<i32>(0)) ||
+                    any(neighbor_cell >= vec3<i32>(i32(cells_per_axis)));
+                let is_side = face != 2u && face != 3u;
+                
+                // Faces are lit by the voxel just outside them
+                let light_voxel = voxel_offset + select(normal, normal * i32(cell_size), (face & 1u) == 0u);
+                let light = sample_light(request.world_slot, light_voxel);
+                
+                // Skirts close cracks against neighbour chunks at another LOD:
+                // surface cells on a side boundary hang their face one cell
+                // further down, covering a neighbour whose surface is lower
+                if (skirts && on_boundary && is_side && is_transparent(above)) {
+                    add_face(slot, local_pos, face, voxel, extent, f32(cell_size), light);
+                } else if (is_transparent(neighbor)) {
+                    add_face(slot, local_pos, face, voxel, extent, 0.0, light);
                 }
             }
         }
     }
     
     // Synchronize before writing final counts
+    storageBarrier();
     workgroupBarrier();
     
     // Thread 0 writes indirect command
     if (local_id.x == 0u && local_id.y == 0u && local_id.z == 0u) {
-        let vertex_count = atomicLoad(&metadata[request_idx].vertex_count);
-        let index_count = atomicLoad(&metadata[request_idx].index_count);
+        let vertex_count = atomicLoad(&metadata[slot].vertex_count);
+        let index_count = atomicLoad(&metadata[slot].index_count);
         
         // For debugging: If no geometry was generated, create a simple cube
         if (index_count == 0u) {
             // Add a debug cube at chunk origin
-            add_face(request_idx, vec3<f32>(25.0, 64.0, 25.0), 0u, 1u); // +X face
-            add_face(request_idx, vec3<f32>(25.0, 64.0, 25.0), 1u, 1u); // -X face
-            add_face(request_idx, vec3<f32>(25.0, 64.0, 25.0), 2u, 1u); // +Y face
-            add_face(request_idx, vec3<f32>(25.0, 64.0, 25.0), 3u, 1u); // -Y face
-            add_face(request_idx, vec3<f32>(25.0, 64.0, 25.0), 4u, 1u); // +Z face
-            add_face(request_idx, vec3<f32>(25.0, 64.0, 25.0), 5u, 1u); // -Z face
+            add_face(slot, vec3<f32>(25.0, 64.0, 25.0), 0u, 1u, vec3<f32>(1.0), 0.0, vec3<f32>(1.0)); // +X face
+            add_face(slot, vec3<f32>(25.0, 64.0, 25.0), 1u, 1u, vec3<f32>(1.0), 0.0, vec3<f32>(1.0)); // -X face
+            add_face(slot, vec3<f32>(25.0, 64.0, 25.0), 2u, 1u, vec3<f32>(1.0), 0.0, vec3<f32>(1.0)); // +Y face
+            add_face(slot, vec3<f32>(25.0, 64.0, 25.0), 3u, 1u, vec3<f32>(1.0), 0.0, vec3<f32>(1.0)); // -Y face
+            add_face(slot, vec3<f32>(25.0, 64.0, 25.0), 4u, 1u, vec3<f32>(1.0), 0.0, vec3<f32>(1.0)); // +Z face
+            add_face(slot, vec3<f32>(25.0, 64.0, 25.0), 5u, 1u, vec3<f32>(1.0), 0.0, vec3<f32>(1.0)); // -Z face
         }
         
-        // Re-read counts after potential debug cube addition
-        let final_vertex_count = atomicLoad(&metadata[request_idx].vertex_count);
-        let final_index_count = atomicLoad(&metadata[request_idx].index_count);
+        // Re-read counts after potential debug cube addition, dropping the
+        // reservations of faces that did not fit
+        let final_vertex_count = min(
+            atomicLoad(&metadata[slot].vertex_count),
+            params.max_vertices / 4u * 4u
+        );
+        let final_index_count = min(
+            atomicLoad(&metadata[slot].index_count),
+            params.max_indices / 6u * 6u
+        );
+        atomicStore(&metadata[slot].vertex_count, final_vertex_count);
+        atomicStore(&metadata[slot].index_count, final_index_count);
         
         // Write indirect draw indexed command
         // Format for DrawIndexedIndirect requires 5 u32 values:
@@ -24,9 +24,9 @@ struct MeshOutput {
 
 // Block type constants
 const BLOCK_AIR: u32 = 0u;
-const BLOCK_STONE: u32 = 1u;
+const BLOCK_GRASS: u32 = 1u;
 const BLOCK_DIRT: u32 = 2u;
-const BLOCK_GRASS: u32 = 3u;
+const BLOCK_STONE: u32 = 3u;
 
 // Face directions
 const FACE_RIGHT: u32 = 0u;
@@ -0,0 +1,43 @@
+// Immediate mode UI overlay
+// Vertices are in screen pixels; the atlas holds glyph coverage plus one
+// solid cell that rects sample, so both share a single pipeline.
+
+struct UIUniform {
+    projection: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> ui: UIUniform;
+
+@group(0) @binding(1)
+var atlas_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var atlas_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = ui.projection * vec4<f32>(input.position, 0.0, 1.0);
+    out.uv = input.uv;
+    out.color = input.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(atlas_texture, atlas_sampler, in.uv).r;
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,252 @@
+//! Block ticks - periodic updates for block behaviour
+//!
+//! Every tick the scheduler runs two kinds of updates through the per-block
+//! handlers registered in `BlockRegistry`:
+//! - random ticks: a fixed number of random voxels per loaded chunk, for slow
+//!   ambient behaviour such as grass spreading or crop growth
+//! - scheduled ticks: positions queued for a given tick, for deterministic
+//!   delays such as fluid settling
+
+use crate::world::core::{BlockId, BlockRegistry, ChunkPos, VoxelPos};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+
+/// Block access for block behaviour handlers
+pub trait BlockAccess {
+    fn get_block(&self, pos: VoxelPos) -> BlockId;
+    fn set_block(&mut self, pos: VoxelPos, block: BlockId);
+
+    /// Chunks eligible for random ticks
+    fn loaded_chunks(&self) -> Vec<ChunkPos>;
+}
+
+/// Tick handler for one block type, called with the ticked position
+///
+/// Handlers may read and modify any voxel, including their neighbours, and
+/// queue follow-up ticks through `ticks`.
+pub type BlockTickHandler =
+    fn(world: &mut dyn BlockAccess, ticks: &mut TickRequests, pos: VoxelPos);
+
+/// Follow-up ticks queued by handlers while a tick runs
+#[derive(Debug, Default)]
+pub struct TickRequests {
+    /// (position, delay) in queueing order
+    requests: Vec<(VoxelPos, u64)>,
+}
+
+impl TickRequests {
+    /// Queue a tick for `pos` `delay` ticks after the current one (0 = the next tick)
+    pub fn schedule_tick(&mut self, pos: VoxelPos, delay: u64) {
+        self.requests.push((pos, delay));
+    }
+}
+
+/// Random and scheduled block tick dispatcher
+pub struct BlockTickScheduler {
+    chunk_size: u32,
+    random_ticks_per_chunk: u32,
+    /// Tick being processed next
+    current_tick: u64,
+    /// Positions queued per due tick, in queueing order
+    scheduled: BTreeMap<u64, Vec<VoxelPos>>,
+    rng: StdRng,
+}
+
+impl BlockTickScheduler {
+    /// Seeded scheduler; the same seed and world give the same random ticks
+    pub fn new(chunk_size: u32, random_ticks_per_chunk: u32, seed: u64) -> Self {
+        Self {
+            chunk_size,
+            random_ticks_per_chunk,
+            current_tick: 0,
+            scheduled: BTreeMap::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    pub fn set_random_ticks_per_chunk(&mut self, random_ticks_per_chunk: u32) {
+        self.random_ticks_per_chunk = random_ticks_per_chunk;
+    }
+
+    /// Queue a tick for `pos` `delay` ticks from now (0 = the next `tick` call)
+    ///
+    /// The handler of whatever block is there when the tick is due runs.
+    pub fn schedule_tick(&mut self, pos: VoxelPos, delay: u64) {
+        self.scheduled
+            .entry(self.current_tick + delay)
+            .or_default()
+            .push(pos);
+    }
+
+    /// Number of scheduled ticks still waiting
+    pub fn pending_scheduled(&self) -> usize {
+        self.scheduled.values().map(Vec::len).sum()
+    }
+
+    /// Run one tick: due scheduled ticks first, then random ticks
+    ///
+    /// Returns how many handlers ran.
+    pub fn tick(&mut self, world: &mut dyn BlockAccess, registry: &BlockRegistry) -> usize {
+        let mut handled = 0;
+        let mut requests = TickRequests::default();
+
+        let later = self.scheduled.split_off(&(self.current_tick + 1));
+        let due = std::mem::replace(&mut self.scheduled, later);
+        for pos in due.into_values().flatten() {
+            handled += run_handler(world, registry, &mut requests, pos) as usize;
+        }
+
+        let size = self.chunk_size as i32;
+        for chunk in world.loaded_chunks() {
+            for _ in 0..self.random_ticks_per_chunk {
+                let pos = VoxelPos::new(
+                    chunk.x * size + self.rng.gen_range(0..size),
+                    chunk.y * size + self.rng.gen_range(0..size),
+                    chunk.z * size + self.rng.gen_range(0..size),
+                );
+                handled += run_handler(world, registry, &mut requests, pos) as usize;
+            }
+        }
+
+        // Delays count from the next tick, like `schedule_tick` between ticks
+        self.current_tick += 1;
+        for (pos, delay) in requests.requests {
+            self.schedule_tick(pos, delay);
+        }
+        handled
+    }
+}
+
+/// Run the tick handler of the block at `pos`, if it has one
+fn run_handler(
+    world: &mut dyn BlockAccess,
+    registry: &BlockRegistry,
+    requests: &mut TickRequests,
+    pos: VoxelPos,
+) -> bool {
+    match registry.tick_handler(world.get_block(pos)) {
+        Some(handler) => {
+            handler(world, requests, pos);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct TestWorld {
+        blocks: HashMap<VoxelPos, BlockId>,
+    }
+
+    impl BlockAccess for TestWorld {
+        fn get_block(&self, pos: VoxelPos) -> BlockId {
+            self.blocks.get(&pos).copied().unwrap_or(BlockId::AIR)
+        }
+
+        fn set_block(&mut self, pos: VoxelPos, block: BlockId) {
+            self.blocks.insert(pos, block);
+        }
+
+        fn loaded_chunks(&self) -> Vec<ChunkPos> {
+            vec![ChunkPos::new(0, 0, 0)]
+        }
+    }
+
+    fn grow_grass(world: &mut dyn BlockAccess, _ticks: &mut TickRequests, pos: VoxelPos) {
+        let above = VoxelPos::new(pos.x, pos.y + 1, pos.z);
+        if world.get_block(above) == BlockId::AIR {
+            world.set_block(pos, BlockId::GRASS);
+        }
+    }
+
+    /// Falls one voxel per tick until it lands on something
+    fn fall(world: &mut dyn BlockAccess, ticks: &mut TickRequests, pos: VoxelPos) {
+        let below = VoxelPos::new(pos.x, pos.y - 1, pos.z);
+        if world.get_block(below) == BlockId::AIR {
+            world.set_block(pos, BlockId::AIR);
+            world.set_block(below, BlockId::SAND);
+            ticks.schedule_tick(below, 0);
+        }
+    }
+
+    fn dirt_world() -> TestWorld {
+        // Two exposed dirt blocks and one covered by stone, in a 2x2x2 chunk
+        TestWorld {
+            blocks: HashMap::from([
+                (VoxelPos::new(0, 0, 0), BlockId::DIRT),
+                (VoxelPos::new(1, 0, 0), BlockId::DIRT),
+                (VoxelPos::new(0, 0, 1), BlockId::DIRT),
+                (VoxelPos::new(0, 1, 1), BlockId::STONE),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_random_ticks_turn_exposed_dirt_into_grass() {
+        let mut registry = BlockRegistry::new();
+        registry.register_tick_handler(BlockId::DIRT, grow_grass);
+        let mut world = dirt_world();
+        let mut scheduler = BlockTickScheduler::new(2, 200, 7);
+
+        let handled = scheduler.tick(&mut world, &registry);
+
+        assert!(handled > 0);
+        assert_eq!(world.get_block(VoxelPos::new(0, 0, 0)), BlockId::GRASS);
+        assert_eq!(world.get_block(VoxelPos::new(1, 0, 0)), BlockId::GRASS);
+        assert_eq!(world.get_block(VoxelPos::new(0, 0, 1)), BlockId::DIRT);
+    }
+
+    #[test]
+    fn test_scheduled_tick_fires_after_its_delay() {
+        let mut registry = BlockRegistry::new();
+        registry.register_tick_handler(BlockId::DIRT, grow_grass);
+        let mut world = dirt_world();
+        let mut scheduler = BlockTickScheduler::new(2, 0, 7);
+        let pos = VoxelPos::new(1, 0, 0);
+
+        scheduler.schedule_tick(pos, 2);
+        assert_eq!(scheduler.tick(&mut world, &registry), 0);
+        assert_eq!(scheduler.tick(&mut world, &registry), 0);
+        assert_eq!(world.get_block(pos), BlockId::DIRT);
+
+        assert_eq!(scheduler.tick(&mut world, &registry), 1);
+        assert_eq!(world.get_block(pos), BlockId::GRASS);
+        assert_eq!(scheduler.pending_scheduled(), 0);
+    }
+
+    #[test]
+    fn test_handler_schedules_follow_up_ticks() {
+        let mut registry = BlockRegistry::new();
+        registry.register_tick_handler(BlockId::SAND, fall);
+        let mut world = TestWorld {
+            blocks: HashMap::from([
+                (VoxelPos::new(0, 3, 0), BlockId::SAND),
+                (VoxelPos::new(0, 0, 0), BlockId::STONE),
+            ]),
+        };
+        let mut scheduler = BlockTickScheduler::new(2, 0, 7);
+        scheduler.schedule_tick(VoxelPos::new(0, 3, 0), 0);
+
+        // A follow-up with delay 0 runs on the next tick, not the current one
+        assert_eq!(scheduler.tick(&mut world, &registry), 1);
+        assert_eq!(world.get_block(VoxelPos::new(0, 2, 0)), BlockId::SAND);
+        assert_eq!(scheduler.pending_scheduled(), 1);
+
+        assert_eq!(scheduler.tick(&mut world, &registry), 1);
+        assert_eq!(world.get_block(VoxelPos::new(0, 1, 0)), BlockId::SAND);
+
+        // Landed on stone: the last tick queues nothing
+        assert_eq!(scheduler.tick(&mut world, &registry), 1);
+        assert_eq!(world.get_block(VoxelPos::new(0, 1, 0)), BlockId::SAND);
+        assert_eq!(scheduler.pending_scheduled(), 0);
+    }
+}
@@ -0,0 +1,201 @@
+//! Neighbour updates - lets blocks react when an adjacent voxel changes
+//!
+//! After a `set_block`, `notify_neighbors` tells the six face-adjacent voxels
+//! through the `on_neighbor_changed` handlers registered in `BlockRegistry`.
+//! A handler that changes its own block notifies its neighbours in turn, so
+//! updates can cascade; a per-tick budget stops runaway cascades, and
+//! whatever is left over is delivered on the next tick.
+
+use crate::world::block_tick::BlockAccess;
+use crate::world::core::{BlockRegistry, VoxelPos};
+use std::collections::VecDeque;
+
+/// Face neighbours that are notified
+const NEIGHBOURS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Neighbour change handler for one block type
+///
+/// Called with the notified position and the neighbour that changed. Returns
+/// whether the handler changed the block at `pos`, which notifies its own
+/// neighbours.
+pub type NeighborChangedHandler =
+    fn(world: &mut dyn BlockAccess, pos: VoxelPos, changed_neighbor: VoxelPos) -> bool;
+
+/// Queue of pending neighbour notifications with a per-tick budget
+pub struct NeighborUpdater {
+    /// (notified position, changed neighbour) in delivery order
+    pending: VecDeque<(VoxelPos, VoxelPos)>,
+    budget_per_tick: usize,
+    remaining_budget: usize,
+}
+
+impl NeighborUpdater {
+    pub fn new(budget_per_tick: usize) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            budget_per_tick,
+            remaining_budget: budget_per_tick,
+        }
+    }
+
+    /// Refill the budget and deliver notifications left over from last tick
+    pub fn begin_tick(&mut self, world: &mut dyn BlockAccess, registry: &BlockRegistry) -> usize {
+        self.remaining_budget = self.budget_per_tick;
+        self.process(world, registry)
+    }
+
+    /// Tell the six neighbours of `pos` that it changed
+    ///
+    /// Returns how many notifications were delivered within the budget.
+    pub fn notify_neighbors(
+        &mut self,
+        world: &mut dyn BlockAccess,
+        registry: &BlockRegistry,
+        pos: VoxelPos,
+    ) -> usize {
+        self.queue_neighbors(pos);
+        self.process(world, registry)
+    }
+
+    /// Notifications waiting for budget
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn queue_neighbors(&mut self, pos: VoxelPos) {
+        for (dx, dy, dz) in NEIGHBOURS {
+            let neighbour = VoxelPos::new(pos.x + dx, pos.y + dy, pos.z + dz);
+            self.pending.push_back((neighbour, pos));
+        }
+    }
+
+    /// Deliver queued notifications until the queue or the budget runs out
+    ///
+    /// Every delivered notification costs one unit of budget, whether or not
+    /// the notified block has a handler.
+    fn process(&mut self, world: &mut dyn BlockAccess, registry: &BlockRegistry) -> usize {
+        let mut delivered = 0;
+
+        while self.remaining_budget > 0 {
+            let Some((pos, changed)) = self.pending.pop_front() else {
+                break;
+            };
+            self.remaining_budget -= 1;
+            delivered += 1;
+
+            if let Some(handler) = registry.neighbor_handler(world.get_block(pos)) {
+                if handler(world, pos, changed) {
+                    self.queue_neighbors(pos);
+                }
+            }
+        }
+
+        if !self.pending.is_empty() {
+            log::debug!(
+                "[NeighborUpdater] Budget spent, {} notifications deferred",
+                self.pending.len()
+            );
+        }
+        delivered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::core::{BlockId, ChunkPos};
+    use std::collections::HashMap;
+
+    struct TestWorld {
+        blocks: HashMap<VoxelPos, BlockId>,
+    }
+
+    impl BlockAccess for TestWorld {
+        fn get_block(&self, pos: VoxelPos) -> BlockId {
+            self.blocks.get(&pos).copied().unwrap_or(BlockId::STONE)
+        }
+
+        fn set_block(&mut self, pos: VoxelPos, block: BlockId) {
+            self.blocks.insert(pos, block);
+        }
+
+        fn loaded_chunks(&self) -> Vec<ChunkPos> {
+            Vec::new()
+        }
+    }
+
+    /// Marks the notified stone as glass without changing anything else
+    fn mark_notified(world: &mut dyn BlockAccess, pos: VoxelPos, _changed: VoxelPos) -> bool {
+        world.set_block(pos, BlockId::GLASS);
+        false
+    }
+
+    /// Flips between dirt and grass on every notification - never settles
+    fn flip(world: &mut dyn BlockAccess, pos: VoxelPos, _changed: VoxelPos) -> bool {
+        let next = if world.get_block(pos) == BlockId::DIRT {
+            BlockId::GRASS
+        } else {
+            BlockId::DIRT
+        };
+        world.set_block(pos, next);
+        true
+    }
+
+    #[test]
+    fn test_placing_a_block_notifies_exactly_six_neighbours() {
+        let mut registry = BlockRegistry::new();
+        registry.register_neighbor_handler(BlockId::STONE, mark_notified);
+        let mut world = TestWorld {
+            blocks: HashMap::new(),
+        };
+        let mut updater = NeighborUpdater::new(1000);
+
+        let placed = VoxelPos::new(4, 4, 4);
+        world.set_block(placed, BlockId::SAND);
+        let delivered = updater.notify_neighbors(&mut world, &registry, placed);
+
+        assert_eq!(delivered, 6);
+        assert_eq!(updater.pending(), 0);
+        let notified: Vec<VoxelPos> = world
+            .blocks
+            .iter()
+            .filter(|(_, block)| **block == BlockId::GLASS)
+            .map(|(pos, _)| *pos)
+            .collect();
+        assert_eq!(notified.len(), 6);
+        for pos in notified {
+            let distance = (pos.x - 4).abs() + (pos.y - 4).abs() + (pos.z - 4).abs();
+            assert_eq!(distance, 1);
+        }
+    }
+
+    #[test]
+    fn test_runaway_cascade_stops_at_the_budget() {
+        let mut registry = BlockRegistry::new();
+        registry.register_neighbor_handler(BlockId::DIRT, flip);
+        registry.register_neighbor_handler(BlockId::GRASS, flip);
+        let mut world = TestWorld {
+            blocks: HashMap::from([
+                (VoxelPos::new(0, 0, 0), BlockId::DIRT),
+                (VoxelPos::new(1, 0, 0), BlockId::DIRT),
+            ]),
+        };
+        let mut updater = NeighborUpdater::new(50);
+
+        assert_eq!(
+            updater.notify_neighbors(&mut world, &registry, VoxelPos::new(0, 0, 0)),
+            50
+        );
+        assert!(updater.pending() > 0);
+
+        // Leftovers continue next tick, again within the budget
+        assert_eq!(updater.begin_tick(&mut world, &registry), 50);
+    }
+}
@@ -14,6 +14,7 @@ pub fn create_grass_properties() -> BlockProperties {
             color: [0.3, 0.8, 0.2], // Green grass color
             texture_id: 1,
             light_emission: 0,
+            light_color: [1.0, 1.0, 1.0],
         },
         physics: PhysicsProperties {
             solid: true,
@@ -34,6 +35,7 @@ pub fn create_dirt_properties() -> BlockProperties {
             color: [0.5, 0.3, 0.1], // Brown dirt color
             texture_id: 2,
             light_emission: 0,
+            light_color: [1.0, 1.0, 1.0],
         },
         physics: PhysicsProperties {
             solid: true,
@@ -54,6 +56,7 @@ pub fn create_stone_properties() -> BlockProperties {
             color: [0.5, 0.5, 0.5], // Gray stone color
             texture_id: 3,
             light_emission: 0,
+            light_color: [1.0, 1.0, 1.0],
         },
         physics: PhysicsProperties {
             solid: true,
@@ -74,6 +77,7 @@ pub fn create_water_properties() -> BlockProperties {
             color: [0.2, 0.3, 0.8], // Blue water color
             texture_id: 4,
             light_emission: 0,
+            light_color: [1.0, 1.0, 1.0],
         },
         physics: PhysicsProperties {
             solid: false,
@@ -94,6 +98,7 @@ pub fn create_sand_properties() -> BlockProperties {
             color: [0.9, 0.8, 0.6], // Sandy color
             texture_id: 5,
             light_emission: 0,
+            light_color: [1.0, 1.0, 1.0],
         },
         physics: PhysicsProperties {
             solid: true,
@@ -114,6 +119,7 @@ pub fn create_glowstone_properties() -> BlockProperties {
             color: [1.0, 0.9, 0.6], // Bright yellow color
             texture_id: 6,
             light_emission: 15, // Maximum light level
+            light_color: [1.0, 0.85, 0.55],
         },
         physics: PhysicsProperties {
             solid: true,
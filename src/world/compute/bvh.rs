@@ -1,4 +1,5 @@
 use crate::memory::MemoryManager;
+use crate::physics::AABB;
 use crate::world::core::ChunkPos;
 use bytemuck::{Pod, Zeroable};
 use cgmath::{Point3, Vector3};
@@ -57,6 +58,20 @@ impl BvhNode {
     }
 }
 
+/// Full rebuild is triggered once refits degrade the SAH cost by this factor
+pub const DEFAULT_REFIT_DEGRADATION_THRESHOLD: f32 = 1.5;
+
+/// Sentinel parent index of the root node
+const NO_PARENT: u32 = u32::MAX;
+
+/// CPU-side tree links, kept so refits can walk from a leaf to the root
+#[derive(Copy, Clone, Debug)]
+struct NodeLinks {
+    parent: u32,
+    /// Right child index (internal nodes only; the left child is `left_first`)
+    right: u32,
+}
+
 /// Primitive reference for BVH construction
 #[derive(Clone, Debug)]
 struct Primitive {
@@ -76,18 +91,44 @@ pub struct VoxelBvh {
     /// GPU buffer containing primitive indices
     primitive_buffer: Buffer,
 
+    /// CPU mirror of the uploaded nodes and primitive indices, used for refits
+    nodes: Vec<BvhNode>,
+    node_links: Vec<NodeLinks>,
+    primitive_indices: Vec<u32>,
+
+    /// Current bounds of each primitive, indexed by primitive index
+    primitive_aabbs: Vec<AABB>,
+
+    /// Leaf node containing each primitive
+    leaf_of_primitive: Vec<u32>,
+
+    /// SAH cost right after the last full build
+    built_sah_cost: f32,
+
+    /// Root surface area at the last full build; SAH costs are relative to it
+    built_root_area: f32,
+
+    /// Rebuild once `sah_cost / built_sah_cost` exceeds this
+    refit_threshold: f32,
+
     /// BVH statistics
     node_count: u32,
     primitive_count: u32,
     max_depth: u32,
+    refits_since_rebuild: u32,
 }
 
 impl VoxelBvh {
     pub fn new(
         device: Arc<Device>,
-        memory_manager: &mut MemoryManager,
+        _memory_manager: &mut MemoryManager,
         max_primitives: u32,
     ) -> Self {
+        Self::with_capacity(device, max_primitives)
+    }
+
+    /// Create an empty BVH with GPU buffers sized for `max_primitives`
+    fn with_capacity(device: Arc<Device>, max_primitives: u32) -> Self {
         // Allocate buffers for worst-case BVH size
         let max_nodes = max_primitives * 2;
         let node_buffer_size = max_nodes as u64 * std::mem::size_of::<BvhNode>() as u64;
@@ -111,9 +152,18 @@ impl VoxelBvh {
             device,
             node_buffer,
             primitive_buffer,
+            nodes: Vec::new(),
+            node_links: Vec::new(),
+            primitive_indices: Vec::new(),
+            primitive_aabbs: Vec::new(),
+            leaf_of_primitive: Vec::new(),
+            built_sah_cost: 0.0,
+            built_root_area: 0.0,
+            refit_threshold: DEFAULT_REFIT_DEGRADATION_THRESHOLD,
             node_count: 0,
             primitive_count: 0,
             max_depth: 0,
+            refits_since_rebuild: 0,
         }
     }
 
@@ -124,17 +174,42 @@ impl VoxelBvh {
         chunk_positions: &[ChunkPos],
         chunk_size: f32,
     ) {
-        // Convert chunks to primitives
-        let mut primitives: Vec<Primitive> = chunk_positions
+        // Convert chunks to primitive bounds
+        let aabbs: Vec<AABB> = chunk_positions
             .iter()
-            .enumerate()
-            .map(|(i, pos)| {
-                let min = Point3::new(
+            .map(|pos| {
+                let min = [
                     pos.x as f32 * chunk_size,
                     pos.y as f32 * chunk_size,
                     pos.z as f32 * chunk_size,
-                );
-                let max = min + Vector3::new(chunk_size, chunk_size, chunk_size);
+                ];
+                let max = [
+                    min[0] + chunk_size,
+                    min[1] + chunk_size,
+                    min[2] + chunk_size,
+                ];
+                AABB::new(min, max)
+            })
+            .collect();
+
+        self.build_from_aabbs(queue, &aabbs);
+    }
+
+    /// Build BVH from arbitrary primitive bounds (primitive index = slice index)
+    pub fn build_from_aabbs(&mut self, queue: &Queue, aabbs: &[AABB]) {
+        self.primitive_aabbs = aabbs.to_vec();
+        self.rebuild(queue);
+    }
+
+    /// Rebuild the whole tree from the current primitive bounds
+    fn rebuild(&mut self, queue: &Queue) {
+        let mut primitives: Vec<Primitive> = self
+            .primitive_aabbs
+            .iter()
+            .enumerate()
+            .map(|(i, aabb)| {
+                let min = Point3::from(aabb.min);
+                let max = Point3::from(aabb.max);
                 let center = Point3::new(
                     (min.x + max.x) * 0.5,
                     (min.y + max.y) * 0.5,
@@ -155,29 +230,147 @@ impl VoxelBvh {
         // Build BVH using SAH (Surface Area Heuristic)
         let mut nodes = Vec::new();
         let mut primitive_indices = Vec::new();
+        self.node_links.clear();
         self.max_depth = 0;
 
-        let primitives_len = primitives.len();
-        self.build_recursive(
-            &mut nodes,
-            &mut primitive_indices,
-            &mut primitives,
-            0,
-            primitives_len,
-            0,
-        );
+        if !primitives.is_empty() {
+            let primitives_len = primitives.len();
+            self.build_recursive(
+                &mut nodes,
+                &mut primitive_indices,
+                &mut primitives,
+                0,
+                primitives_len,
+                0,
+            );
+        }
 
         self.node_count = nodes.len() as u32;
 
+        // Map each primitive to its leaf for refits
+        self.leaf_of_primitive = vec![0; self.primitive_aabbs.len()];
+        for (node_index, node) in nodes.iter().enumerate() {
+            if node.is_leaf() {
+                let first = node.left_first as usize;
+                for &prim in &primitive_indices[first..first + node.prim_count as usize] {
+                    self.leaf_of_primitive[prim as usize] = node_index as u32;
+                }
+            }
+        }
+
+        self.nodes = nodes;
+        self.primitive_indices = primitive_indices;
+        self.built_root_area = self.nodes.first().map_or(0.0, |root| {
+            self.surface_area(&Point3::from(root.aabb_min), &Point3::from(root.aabb_max))
+        });
+        self.built_sah_cost = self.sah_cost();
+        self.refits_since_rebuild = 0;
+
         // Upload to GPU
-        queue.write_buffer(&self.node_buffer, 0, bytemuck::cast_slice(&nodes));
+        queue.write_buffer(&self.node_buffer, 0, bytemuck::cast_slice(&self.nodes));
         queue.write_buffer(
             &self.primitive_buffer,
             0,
-            bytemuck::cast_slice(&primitive_indices),
+            bytemuck::cast_slice(&self.primitive_indices),
         );
     }
 
+    /// Update primitive bounds in place without re-partitioning the tree
+    ///
+    /// Each `(primitive_index, aabb)` replaces that primitive's bounds; the
+    /// leaves holding changed primitives and their ancestors are re-fitted.
+    /// Falls back to a full rebuild once the SAH cost has degraded past the
+    /// refit threshold. Returns `true` if a full rebuild happened.
+    pub fn refit(&mut self, queue: &Queue, changed_aabbs: &[(u32, AABB)]) -> bool {
+        let mut dirty_leaves = Vec::new();
+        for &(primitive, aabb) in changed_aabbs {
+            let Some(slot) = self.primitive_aabbs.get_mut(primitive as usize) else {
+                continue;
+            };
+            *slot = aabb;
+            if let Some(&leaf) = self.leaf_of_primitive.get(primitive as usize) {
+                dirty_leaves.push(leaf);
+            }
+        }
+
+        if dirty_leaves.is_empty() || self.nodes.is_empty() {
+            return false;
+        }
+        dirty_leaves.sort_unstable();
+        dirty_leaves.dedup();
+
+        for leaf in dirty_leaves {
+            // Leaf bounds from its primitives
+            let node = self.nodes[leaf as usize];
+            let first = node.left_first as usize;
+            let mut aabb_min = [f32::MAX; 3];
+            let mut aabb_max = [f32::MIN; 3];
+            for &prim in &self.primitive_indices[first..first + node.prim_count as usize] {
+                let bounds = &self.primitive_aabbs[prim as usize];
+                for axis in 0..3 {
+                    aabb_min[axis] = aabb_min[axis].min(bounds.min[axis]);
+                    aabb_max[axis] = aabb_max[axis].max(bounds.max[axis]);
+                }
+            }
+            self.nodes[leaf as usize].aabb_min = aabb_min;
+            self.nodes[leaf as usize].aabb_max = aabb_max;
+
+            // Walk up, re-fitting each ancestor to its two children
+            let mut current = self.node_links[leaf as usize].parent;
+            while current != NO_PARENT {
+                let left = self.nodes[self.nodes[current as usize].left_first as usize];
+                let right = self.nodes[self.node_links[current as usize].right as usize];
+                let node = &mut self.nodes[current as usize];
+                for axis in 0..3 {
+                    node.aabb_min[axis] = left.aabb_min[axis].min(right.aabb_min[axis]);
+                    node.aabb_max[axis] = left.aabb_max[axis].max(right.aabb_max[axis]);
+                }
+                current = self.node_links[current as usize].parent;
+            }
+        }
+
+        self.refits_since_rebuild += 1;
+
+        let degraded = self.sah_cost() > self.built_sah_cost * self.refit_threshold;
+        if self.built_sah_cost > 0.0 && degraded {
+            self.rebuild(queue);
+            return true;
+        }
+
+        queue.write_buffer(&self.node_buffer, 0, bytemuck::cast_slice(&self.nodes));
+        false
+    }
+
+    /// Set the SAH degradation factor that triggers a full rebuild during refits
+    pub fn set_refit_threshold(&mut self, threshold: f32) {
+        self.refit_threshold = threshold.max(1.0);
+    }
+
+    /// SAH cost of the current tree, relative to the root's surface area at the last build
+    ///
+    /// Normalizing by the current root instead would hide degradation: a
+    /// primitive moving far away grows the root more than the rest of the tree.
+    fn sah_cost(&self) -> f32 {
+        let root_area = self.built_root_area;
+        if root_area <= 0.0 {
+            return 0.0;
+        }
+
+        self.nodes
+            .iter()
+            .map(|node| {
+                let area =
+                    self.surface_area(&Point3::from(node.aabb_min), &Point3::from(node.aabb_max));
+                if node.is_leaf() {
+                    area * node.prim_count as f32
+                } else {
+                    area
+                }
+            })
+            .sum::<f32>()
+            / root_area
+    }
+
     /// Recursive BVH construction
     fn build_recursive(
         &mut self,
@@ -197,6 +390,11 @@ impl VoxelBvh {
             left_first: 0,
             prim_count: 0,
         });
+        // The parent links its children once they are built
+        self.node_links.push(NodeLinks {
+            parent: NO_PARENT,
+            right: 0,
+        });
 
         // Calculate bounds for this node
         let mut aabb_min = Point3::new(f32::MAX, f32::MAX, f32::MAX);
@@ -259,6 +457,9 @@ impl VoxelBvh {
             self.build_recursive(nodes, primitive_indices, primitives, start, mid, depth + 1);
         let right_child =
             self.build_recursive(nodes, primitive_indices, primitives, mid, end, depth + 1);
+        self.node_links[left_child as usize].parent = node_index;
+        self.node_links[right_child as usize].parent = node_index;
+        self.node_links[node_index as usize].right = right_child;
 
         // Update node
         nodes[node_index as usize].aabb_min = aabb_min.into();
@@ -370,6 +571,10 @@ impl VoxelBvh {
             node_count: self.node_count,
             primitive_count: self.primitive_count,
             max_depth: self.max_depth,
+            sah_cost: self.sah_cost(),
+            built_sah_cost: self.built_sah_cost,
+            refit_threshold: self.refit_threshold,
+            refits_since_rebuild: self.refits_since_rebuild,
             memory_usage_mb: (self.node_count as f32 * std::mem::size_of::<BvhNode>() as f32
                 + self.primitive_count as f32 * 4.0)
                 / (1024.0 * 1024.0),
@@ -383,5 +588,104 @@ pub struct BvhStats {
     pub node_count: u32,
     pub primitive_count: u32,
     pub max_depth: u32,
+    /// Current SAH cost, relative to the root area at the last build (grows as refits loosen the bounds)
+    pub sah_cost: f32,
+    /// SAH cost right after the last full build
+    pub built_sah_cost: f32,
+    /// Degradation factor (`sah_cost / built_sah_cost`) that triggers a rebuild
+    pub refit_threshold: f32,
+    /// Refits applied since the last full build
+    pub refits_since_rebuild: u32,
     pub memory_usage_mb: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_device() -> Option<(Arc<Device>, Queue)> {
+        let instance = wgpu::Instance::default();
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("BVH Test Device"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+            },
+            None,
+        ))
+        .ok()?;
+        Some((Arc::new(device), queue))
+    }
+
+    /// 4x4x4 grid of unit boxes, two units apart
+    fn grid_aabbs() -> Vec<AABB> {
+        let mut aabbs = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                for z in 0..4 {
+                    let min = [x as f32 * 2.0, y as f32 * 2.0, z as f32 * 2.0];
+                    aabbs.push(AABB::new(min, [min[0] + 1.0, min[1] + 1.0, min[2] + 1.0]));
+                }
+            }
+        }
+        aabbs
+    }
+
+    fn shifted(aabb: &AABB, dx: f32) -> AABB {
+        AABB::new(
+            [aabb.min[0] + dx, aabb.min[1], aabb.min[2]],
+            [aabb.max[0] + dx, aabb.max[1], aabb.max[2]],
+        )
+    }
+
+    fn contains(node: &BvhNode, aabb: &AABB) -> bool {
+        (0..3).all(|axis| {
+            node.aabb_min[axis] <= aabb.min[axis] && node.aabb_max[axis] >= aabb.max[axis]
+        })
+    }
+
+    #[test]
+    fn test_refit_grows_ancestors_and_rebuilds_past_threshold() {
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No GPU adapter, skipping BVH refit test");
+            return;
+        };
+        let aabbs = grid_aabbs();
+        let mut bvh = VoxelBvh::with_capacity(device.clone(), aabbs.len() as u32);
+        bvh.build_from_aabbs(&queue, &aabbs);
+
+        // A small move is absorbed by a refit
+        let moved = shifted(&aabbs[5], 0.5);
+        assert!(!bvh.refit(&queue, &[(5, moved)]));
+        let mut node = bvh.leaf_of_primitive[5];
+        while node != NO_PARENT {
+            assert!(contains(&bvh.nodes[node as usize], &moved));
+            node = bvh.node_links[node as usize].parent;
+        }
+        let stats = bvh.get_stats();
+        assert_eq!(stats.refits_since_rebuild, 1);
+        assert!(stats.sah_cost <= stats.built_sah_cost * DEFAULT_REFIT_DEGRADATION_THRESHOLD);
+
+        // The same far move degrades the tree past 1.5x...
+        let far = shifted(&aabbs[5], 100.0);
+        let mut lenient = VoxelBvh::with_capacity(device, aabbs.len() as u32);
+        lenient.set_refit_threshold(f32::MAX);
+        lenient.build_from_aabbs(&queue, &aabbs);
+        assert!(!lenient.refit(&queue, &[(5, far)]));
+        let stats = lenient.get_stats();
+        assert!(stats.sah_cost > stats.built_sah_cost * DEFAULT_REFIT_DEGRADATION_THRESHOLD);
+
+        // ...so with the default threshold it triggers a rebuild
+        assert!(bvh.refit(&queue, &[(5, far)]));
+        let stats = bvh.get_stats();
+        assert_eq!(stats.refits_since_rebuild, 0);
+        assert_eq!(stats.sah_cost, stats.built_sah_cost);
+        assert!(contains(&bvh.nodes[0], &far));
+        assert!(contains(
+            &bvh.nodes[bvh.leaf_of_primitive[5] as usize],
+            &far
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::world::core::{BlockId, ChunkPos, VoxelPos};
-use crate::world::storage::WorldBuffer;
+use crate::world::storage::{VoxelData, WorldBuffer};
 use bytemuck::{Pod, Zeroable};
+use std::collections::HashMap;
 /// GPU-accelerated block queries
 ///
 /// This module provides high-performance block queries that run entirely on GPU,
@@ -9,6 +10,7 @@ use std::sync::Arc;
 use wgpu::util::DeviceExt;
 
 // Import constants properly
+use crate::constants::core::{CHUNK_SIZE, VOXELS_PER_CHUNK};
 use crate::constants::*;
 
 /// A batch query request for multiple blocks
@@ -17,7 +19,7 @@ use crate::constants::*;
 pub struct BlockQueryRequest {
     /// World position to query
     pub position: [i32; 3],
-    /// Query type (0 = get block, 1 = get light, 2 = get metadata)
+    /// Query type (0 = block, 1 = light, 2 = sky light, 3 = metadata, 4 = raw voxel)
     pub query_type: u32,
 }
 
@@ -37,6 +39,12 @@ pub struct BlockQueryResult {
     pub _padding: [u32; 2],
 }
 
+/// Maximum queries per batch (64K)
+const MAX_BATCH_SIZE: u32 = 65536;
+
+/// Buffer index the shader treats as "no voxel" (must match the shader)
+const INVALID_BUFFER_INDEX: u32 = u32::MAX;
+
 /// GPU block query system
 pub struct GpuBlockQuery {
     device: Arc<wgpu::Device>,
@@ -50,6 +58,7 @@ pub struct GpuBlockQuery {
 
     /// Staging buffers for queries
     query_staging_buffer: wgpu::Buffer,
+    index_staging_buffer: wgpu::Buffer,
     result_staging_buffer: wgpu::Buffer,
 
     /// Maximum queries per batch
@@ -78,7 +87,8 @@ impl GpuBlockQuery {
             "Block Query Bind Group Layout",
             0 => buffer(storage_read),  // World buffer (read-only)
             1 => buffer(storage_read),  // Query requests
-            2 => buffer(storage)        // Query results
+            2 => buffer(storage),       // Query results
+            3 => buffer(storage_read)   // Resolved voxel buffer indices
         );
 
         // Create pipeline layout
@@ -87,7 +97,7 @@ impl GpuBlockQuery {
             bind_group_layouts: &[&bind_group_layout],
             push_constant_ranges: &[wgpu::PushConstantRange {
                 stages: wgpu::ShaderStages::COMPUTE,
-                range: 0..8, // query_count, chunk_size
+                range: 0..4, // query_count
             }],
         });
 
@@ -99,8 +109,6 @@ impl GpuBlockQuery {
             entry_point: "query_blocks",
         });
 
-        const MAX_BATCH_SIZE: u32 = 65536; // 64K queries per batch
-
         // Create staging buffers
         let query_staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Query Staging Buffer"),
@@ -109,12 +117,19 @@ impl GpuBlockQuery {
             mapped_at_creation: false,
         });
 
+        let index_staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Query Index Staging Buffer"),
+            size: (std::mem::size_of::<u32>() * MAX_BATCH_SIZE as usize) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let result_staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Result Staging Buffer"),
             size: (std::mem::size_of::<BlockQueryResult>() * MAX_BATCH_SIZE as usize) as u64,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::MAP_READ,
+            // Read back through a separate download buffer; MAP_READ cannot be
+            // combined with STORAGE without MAPPABLE_PRIMARY_BUFFERS
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
             mapped_at_creation: false,
         });
 
@@ -124,6 +139,7 @@ impl GpuBlockQuery {
             query_pipeline,
             bind_group_layout,
             query_staging_buffer,
+            index_staging_buffer,
             result_staging_buffer,
             max_batch_size: MAX_BATCH_SIZE,
         }
@@ -142,9 +158,24 @@ impl GpuBlockQuery {
         let query_count = queries.len().min(self.max_batch_size as usize);
         let queries = &queries[..query_count];
 
+        // Chunk slots live in the WorldBuffer's CPU-side map, so resolve
+        // each position to its voxel index here
+        let indices: Vec<u32> = queries
+            .iter()
+            .map(|request| {
+                block_query_buffer_index(request.position, |chunk| world_buffer.chunk_slot(chunk))
+                    .unwrap_or(INVALID_BUFFER_INDEX)
+            })
+            .collect();
+
         // Upload queries to GPU
         self.queue
             .write_buffer(&self.query_staging_buffer, 0, bytemuck::cast_slice(queries));
+        self.queue.write_buffer(
+            &self.index_staging_buffer,
+            0,
+            bytemuck::cast_slice(&indices),
+        );
 
         // Create bind group using macro
         let bind_group = crate::create_bind_group!(
@@ -153,7 +184,8 @@ impl GpuBlockQuery {
             &self.bind_group_layout,
             0 => world_buffer.voxel_buffer().as_entire_binding(),
             1 => self.query_staging_buffer.as_entire_binding(),
-            2 => self.result_staging_buffer.as_entire_binding()
+            2 => self.result_staging_buffer.as_entire_binding(),
+            3 => self.index_staging_buffer.as_entire_binding()
         );
 
         // Create command encoder
@@ -172,10 +204,7 @@ impl GpuBlockQuery {
 
             compute_pass.set_pipeline(&self.query_pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            compute_pass.set_push_constants(
-                0,
-                bytemuck::cast_slice(&[query_count as u32, crate::constants::core::CHUNK_SIZE]),
-            );
+            compute_pass.set_push_constants(0, bytemuck::cast_slice(&[query_count as u32]));
 
             // One workgroup per MAX_WORKGROUP_SIZE queries
             let workgroups = (query_count as u32 + gpu_limits::MAX_WORKGROUP_SIZE - 1)
@@ -256,9 +285,210 @@ impl GpuBlockQuery {
     }
 }
 
+/// Voxel buffer index of a world position, in the layout of `WorldBuffer`
+///
+/// `chunk_slot` maps a chunk to its buffer slot (`WorldBuffer::chunk_slot` on
+/// the GPU path); chunks without a slot are not resident and give `None`.
+/// Within a slot voxels are stored at `x + y * CHUNK_SIZE + z * CHUNK_SIZE²`,
+/// as `WorldBuffer::upload_chunk` and the terrain kernel write them.
+pub fn block_query_buffer_index(
+    position: [i32; 3],
+    chunk_slot: impl FnOnce(ChunkPos) -> Option<u32>,
+) -> Option<u32> {
+    let voxel = VoxelPos::new(position[0], position[1], position[2]);
+    let slot = chunk_slot(voxel.to_chunk_pos(CHUNK_SIZE))?;
+
+    let (x, y, z) = voxel.to_local_pos(CHUNK_SIZE);
+    let local_index = x + y * CHUNK_SIZE + z * CHUNK_SIZE * CHUNK_SIZE;
+    let index = slot as u64 * VOXELS_PER_CHUNK as u64 + local_index as u64;
+
+    u32::try_from(index)
+        .ok()
+        .filter(|index| *index != INVALID_BUFFER_INDEX)
+}
+
+/// Build the result the shader writes for a request
+///
+/// `voxel` is the packed voxel read at the request's buffer index, or `None`
+/// if the index was invalid or past the end of the buffer.
+pub fn block_query_result(request: &BlockQueryRequest, voxel: Option<u32>) -> BlockQueryResult {
+    let mut result = BlockQueryResult {
+        position: request.position,
+        query_type: request.query_type,
+        value: 0,
+        success: 0,
+        _padding: [0; 2],
+    };
+
+    if let Some(voxel) = voxel {
+        let value = match request.query_type {
+            0 => Some(voxel & 0xFFFF),
+            1 => Some((voxel >> 16) & 0xF),
+            2 => Some((voxel >> 20) & 0xF),
+            3 => Some((voxel >> 24) & 0xF),
+            4 => Some(voxel),
+            _ => None,
+        };
+        if let Some(value) = value {
+            result.value = value;
+            result.success = 1;
+        }
+    }
+
+    result
+}
+
+/// CPU block query system for machines without a usable GPU
+///
+/// Keeps a copy of the voxel buffer the shader reads, with chunks assigned
+/// slots in upload order like `WorldBuffer`, and answers requests through the
+/// same `block_query_buffer_index` and bit extraction. Positions in chunks that
+/// were never uploaded fail, as they do on the GPU.
+pub struct CpuBlockQuery {
+    /// Length of the voxel buffer the shader would see
+    total_voxels: u64,
+    storage: parking_lot::RwLock<CpuVoxelBuffer>,
+}
+
+/// Chunk slot map and the voxels of every allocated slot
+#[derive(Default)]
+struct CpuVoxelBuffer {
+    chunk_slots: HashMap<ChunkPos, u32>,
+    /// `chunk_slots.len() * VOXELS_PER_CHUNK` packed voxels
+    voxels: Vec<u32>,
+}
+
+impl CpuBlockQuery {
+    /// Create an empty CPU voxel store
+    ///
+    /// Pass `WorldBuffer::total_voxels()` to hold as many chunks as a GPU
+    /// world of that size.
+    pub fn new(total_voxels: u64) -> Self {
+        Self {
+            total_voxels,
+            storage: parking_lot::RwLock::new(CpuVoxelBuffer::default()),
+        }
+    }
+
+    /// Store a chunk in its slot, allocating the next free slot on first upload
+    ///
+    /// `voxels` uses the same layout as `WorldBuffer::upload_chunk`. Returns
+    /// false if the store is full or `voxels` is not a whole chunk.
+    pub fn upload_chunk(&self, chunk_pos: ChunkPos, voxels: &[VoxelData]) -> bool {
+        if voxels.len() != VOXELS_PER_CHUNK as usize {
+            log::warn!(
+                "[CpuBlockQuery] Chunk {:?} has {} voxels, expected {}",
+                chunk_pos,
+                voxels.len(),
+                VOXELS_PER_CHUNK
+            );
+            return false;
+        }
+
+        let mut storage = self.storage.write();
+        let slot = match storage.chunk_slots.get(&chunk_pos) {
+            Some(&slot) => slot,
+            None => {
+                let slot = storage.chunk_slots.len() as u32;
+                if (slot as u64 + 1) * VOXELS_PER_CHUNK as u64 > self.total_voxels {
+                    log::warn!("[CpuBlockQuery] No free slot for chunk {:?}", chunk_pos);
+                    return false;
+                }
+                storage.chunk_slots.insert(chunk_pos, slot);
+                storage
+                    .voxels
+                    .resize((slot as usize + 1) * VOXELS_PER_CHUNK as usize, 0);
+                slot
+            }
+        };
+
+        let start = slot as usize * VOXELS_PER_CHUNK as usize;
+        for (dst, voxel) in storage.voxels[start..].iter_mut().zip(voxels) {
+            *dst = voxel.0;
+        }
+        true
+    }
+
+    /// Slot a chunk occupies, if it was uploaded
+    pub fn chunk_slot(&self, chunk_pos: ChunkPos) -> Option<u32> {
+        self.storage.read().chunk_slots.get(&chunk_pos).copied()
+    }
+
+    /// Set a single voxel, returning false if its chunk was never uploaded
+    pub fn set_voxel(&self, position: VoxelPos, voxel: VoxelData) -> bool {
+        let mut storage = self.storage.write();
+        let request = [position.x, position.y, position.z];
+        let index =
+            block_query_buffer_index(request, |chunk| storage.chunk_slots.get(&chunk).copied());
+        match index.and_then(|index| storage.voxels.get_mut(index as usize)) {
+            Some(slot_voxel) => {
+                *slot_voxel = voxel.0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Execute a batch of block queries on CPU
+    ///
+    /// Batches are truncated to the same maximum size as the GPU path.
+    pub fn query_blocks(&self, queries: &[BlockQueryRequest]) -> Vec<BlockQueryResult> {
+        let query_count = queries.len().min(MAX_BATCH_SIZE as usize);
+        let storage = self.storage.read();
+
+        queries[..query_count]
+            .iter()
+            .map(|request| {
+                let voxel = block_query_buffer_index(request.position, |chunk| {
+                    storage.chunk_slots.get(&chunk).copied()
+                })
+                .and_then(|index| storage.voxels.get(index as usize).copied());
+                block_query_result(request, voxel)
+            })
+            .collect()
+    }
+
+    /// Query a single block (convenience method)
+    pub fn query_block(&self, position: VoxelPos) -> Option<BlockId> {
+        let request = BlockQueryRequest {
+            position: [position.x, position.y, position.z],
+            query_type: 0,
+        };
+
+        self.query_blocks(&[request])
+            .first()
+            .filter(|result| result.success != 0)
+            .map(|result| BlockId(result.value as u16))
+    }
+}
+
+/// Backend used by a `BlockQueryHandle`
+pub enum BlockQueryBackend {
+    /// Queries run on the GPU against the WorldBuffer (primary)
+    Gpu(Arc<GpuBlockQuery>),
+    /// Queries run on the CPU voxel store (fallback)
+    Cpu(Arc<CpuBlockQuery>),
+}
+
+impl BlockQueryBackend {
+    /// Use the GPU when a device is available, otherwise fall back to the CPU store
+    pub fn select(
+        gpu: Option<(Arc<wgpu::Device>, Arc<wgpu::Queue>)>,
+        cpu: Arc<CpuBlockQuery>,
+    ) -> Self {
+        match gpu {
+            Some((device, queue)) => Self::Gpu(Arc::new(GpuBlockQuery::new(device, queue))),
+            None => {
+                log::info!("[BlockQuery] No GPU device available, using CPU block queries");
+                Self::Cpu(cpu)
+            }
+        }
+    }
+}
+
 /// Async block query handle for batching
 pub struct BlockQueryHandle {
-    query_system: Arc<GpuBlockQuery>,
+    backend: BlockQueryBackend,
     pending_queries: parking_lot::Mutex<
         Vec<(
             BlockQueryRequest,
@@ -269,14 +499,28 @@ pub struct BlockQueryHandle {
 
 impl BlockQueryHandle {
     pub fn new(query_system: Arc<GpuBlockQuery>) -> Self {
+        Self::with_backend(BlockQueryBackend::Gpu(query_system))
+    }
+
+    pub fn with_backend(backend: BlockQueryBackend) -> Self {
         Self {
-            query_system,
+            backend,
             pending_queries: parking_lot::Mutex::new(Vec::new()),
         }
     }
 
+    /// Backend answering this handle's queries
+    pub fn backend(&self) -> &BlockQueryBackend {
+        &self.backend
+    }
+
     /// Queue a block query
     pub async fn query_block(&self, position: VoxelPos) -> Option<BlockId> {
+        // The CPU store has no batching latency to hide, so answer immediately
+        if let BlockQueryBackend::Cpu(cpu) = &self.backend {
+            return cpu.query_block(position);
+        }
+
         let (tx, rx) = tokio::sync::oneshot::channel();
 
         let request = BlockQueryRequest {
@@ -298,3 +542,155 @@ impl BlockQueryHandle {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::storage::WorldBufferDescriptor;
+
+    /// A chunk of air with a few voxels set, and the world positions of those voxels
+    fn test_chunk(chunk_pos: ChunkPos, block: u16) -> (Vec<VoxelData>, Vec<VoxelPos>) {
+        let mut voxels = vec![VoxelData::default(); VOXELS_PER_CHUNK as usize];
+        let mut positions = Vec::new();
+        for (i, (x, y, z)) in [(0, 0, 0), (3, 5, 7), (CHUNK_SIZE - 1, 1, CHUNK_SIZE - 1)]
+            .into_iter()
+            .enumerate()
+        {
+            voxels[(x + y * CHUNK_SIZE + z * CHUNK_SIZE * CHUNK_SIZE) as usize] =
+                VoxelData::new(block + i as u16, 12, 3, 5);
+            let size = CHUNK_SIZE as i32;
+            positions.push(VoxelPos::new(
+                chunk_pos.x * size + x as i32,
+                chunk_pos.y * size + y as i32,
+                chunk_pos.z * size + z as i32,
+            ));
+        }
+        (voxels, positions)
+    }
+
+    fn requests_for(positions: &[VoxelPos]) -> Vec<BlockQueryRequest> {
+        positions
+            .iter()
+            .flat_map(|pos| {
+                (0..=5).map(move |query_type| BlockQueryRequest {
+                    position: [pos.x, pos.y, pos.z],
+                    query_type,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cpu_queries_use_the_world_buffer_layout() {
+        let cpu = CpuBlockQuery::new(4 * VOXELS_PER_CHUNK as u64);
+        let first = ChunkPos::new(0, 0, 0);
+        let second = ChunkPos::new(-1, 2, 3);
+        let (voxels, _) = test_chunk(first, 1);
+        assert!(cpu.upload_chunk(first, &voxels));
+        let (voxels, positions) = test_chunk(second, 20);
+        assert!(cpu.upload_chunk(second, &voxels));
+
+        // Slots follow upload order; voxels sit at x + y*CS + z*CS² within a slot
+        assert_eq!(cpu.chunk_slot(second), Some(1));
+        let pos = positions[1];
+        assert_eq!(
+            block_query_buffer_index([pos.x, pos.y, pos.z], |chunk| cpu.chunk_slot(chunk)),
+            Some(VOXELS_PER_CHUNK + 3 + 5 * CHUNK_SIZE + 7 * CHUNK_SIZE * CHUNK_SIZE)
+        );
+
+        let results = cpu.query_blocks(&requests_for(&positions[1..2]));
+        let values: Vec<(u32, u32)> = results.iter().map(|r| (r.value, r.success)).collect();
+        let packed = VoxelData::new(21, 12, 3, 5).0;
+        assert_eq!(
+            values,
+            vec![(21, 1), (12, 1), (3, 1), (5, 1), (packed, 1), (0, 0)]
+        );
+
+        // Air inside a resident chunk succeeds; chunks never uploaded fail
+        assert_eq!(
+            cpu.query_block(VoxelPos::new(-1, 100, 150)),
+            Some(BlockId(0))
+        );
+        assert_eq!(cpu.query_block(VoxelPos::new(500, 0, 0)), None);
+
+        assert!(cpu.set_voxel(pos, VoxelData::new(7, 0, 0, 0)));
+        assert_eq!(cpu.query_block(pos), Some(BlockId(7)));
+        assert!(!cpu.set_voxel(VoxelPos::new(500, 0, 0), VoxelData::new(7, 0, 0, 0)));
+    }
+
+    #[test]
+    fn test_cpu_store_rejects_chunks_past_capacity() {
+        let cpu = CpuBlockQuery::new(VOXELS_PER_CHUNK as u64);
+        let (voxels, _) = test_chunk(ChunkPos::new(0, 0, 0), 1);
+        assert!(cpu.upload_chunk(ChunkPos::new(0, 0, 0), &voxels));
+        assert!(cpu.upload_chunk(ChunkPos::new(0, 0, 0), &voxels));
+        assert!(!cpu.upload_chunk(ChunkPos::new(1, 0, 0), &voxels));
+    }
+
+    /// Device with the features the query pipeline needs, or `None` on machines
+    /// without a suitable adapter
+    ///
+    /// The shared bind group layout macro also exposes storage buffers to the
+    /// vertex stage, hence `VERTEX_WRITABLE_STORAGE`.
+    fn test_device() -> Option<(Arc<wgpu::Device>, Arc<wgpu::Queue>)> {
+        let instance = wgpu::Instance::default();
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        let features = wgpu::Features::PUSH_CONSTANTS | wgpu::Features::VERTEX_WRITABLE_STORAGE;
+        if !adapter.features().contains(features) {
+            return None;
+        }
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("Block Query Test Device"),
+                required_features: features,
+                required_limits: adapter.limits(),
+            },
+            None,
+        ))
+        .ok()?;
+        Some((Arc::new(device), Arc::new(queue)))
+    }
+
+    #[test]
+    fn test_gpu_and_cpu_results_are_identical() {
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No suitable GPU adapter, skipping GPU comparison");
+            return;
+        };
+
+        let mut world_buffer = WorldBuffer::new(
+            device.clone(),
+            &WorldBufferDescriptor {
+                view_distance: 1,
+                enable_atomics: false,
+                enable_readback: true,
+            },
+        );
+        let cpu = CpuBlockQuery::new(world_buffer.total_voxels());
+
+        let mut positions = Vec::new();
+        for (i, chunk_pos) in [ChunkPos::new(0, 0, 0), ChunkPos::new(-2, 1, 4)]
+            .into_iter()
+            .enumerate()
+        {
+            let (voxels, chunk_positions) = test_chunk(chunk_pos, 10 * (i as u16 + 1));
+            world_buffer.upload_chunk(&queue, chunk_pos, &voxels);
+            assert!(cpu.upload_chunk(chunk_pos, &voxels));
+            positions.extend(chunk_positions);
+        }
+        // Air in a resident chunk, and a chunk that is not resident at all
+        positions.push(VoxelPos::new(-99, 60, 210));
+        positions.push(VoxelPos::new(1000, 0, 0));
+
+        let requests = requests_for(&positions);
+        let gpu = GpuBlockQuery::new(device, queue);
+        let gpu_results =
+            pollster::block_on(gpu.query_blocks(&world_buffer, &requests)).expect("GPU readback");
+        let cpu_results = cpu.query_blocks(&requests);
+
+        let gpu_bytes: &[u8] = bytemuck::cast_slice(&gpu_results);
+        let cpu_bytes: &[u8] = bytemuck::cast_slice(&cpu_results);
+        assert_eq!(gpu_bytes, cpu_bytes);
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
+    constants::core::CHUNK_SIZE,
     memory::BandwidthProfiler,
     world::compute::GpuLighting,
-    world::core::{BlockId, ChunkPos, VoxelPos},
-    world::lighting::{BlockProvider, LightUpdate, LightingStats},
-    world::storage::WorldBuffer,
+    world::core::{BlockId, BlockRegistry, ChunkPos, VoxelPos},
+    world::lighting::{
+        emission_rgb, BlockLightCalculator, BlockProvider, ChunkLightData, LightType, LightUpdate,
+        LightingStats,
+    },
+    world::storage::{VoxelData, WorldBuffer},
 };
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 /// GPU Lighting Propagation
 ///
 /// Provides GPU-accelerated light propagation that replaces the CPU version.
@@ -19,6 +23,9 @@ pub struct GpuLightPropagator {
     gpu_lighting: Arc<GpuLighting>,
     world_buffer: Arc<std::sync::Mutex<WorldBuffer>>,
 
+    /// Block source for colored block light, which is computed on the CPU
+    block_provider: GpuBlockProvider,
+
     /// Pending light updates to be processed
     pending_updates: Arc<parking_lot::Mutex<VecDeque<LightUpdate>>>,
 
@@ -37,12 +44,14 @@ impl GpuLightPropagator {
         world_buffer: Arc<std::sync::Mutex<WorldBuffer>>,
     ) -> Self {
         let gpu_lighting = Arc::new(GpuLighting::new(device.clone()));
+        let block_provider = GpuBlockProvider::new(world_buffer.clone());
 
         Self {
             device,
             queue,
             gpu_lighting,
             world_buffer,
+            block_provider,
             pending_updates: Arc::new(parking_lot::Mutex::new(VecDeque::new())),
             stats: Arc::new(parking_lot::RwLock::new(LightingStats::default())),
             profiler: None,
@@ -55,6 +64,12 @@ impl GpuLightPropagator {
         self
     }
 
+    /// Take light emitters from the block registry; without them no block glows
+    pub fn with_block_registry(mut self, registry: &BlockRegistry) -> Self {
+        self.block_provider = self.block_provider.with_emitters(registry);
+        self
+    }
+
     /// Add a light update to the queue
     pub fn add_update(&self, update: LightUpdate) {
         self.pending_updates.lock().push_back(update);
@@ -73,7 +88,8 @@ impl GpuLightPropagator {
 
         // Group updates by chunk
         let chunk_size = crate::constants::core::CHUNK_SIZE as i32;
-        let mut chunks_to_update = std::collections::HashSet::new();
+        let mut chunks_to_update = HashSet::new();
+        let mut block_light_chunks = HashSet::new();
         for update in updates {
             let chunk_pos = ChunkPos {
                 x: update.pos.x / chunk_size,
@@ -81,6 +97,19 @@ impl GpuLightPropagator {
                 z: update.pos.z / chunk_size,
             };
             chunks_to_update.insert(chunk_pos);
+            if update.light_type == LightType::Block {
+                // Block light crosses borders, so neighbours in reach change too
+                block_light_chunks.extend(BlockLightCalculator::chunks_in_reach(
+                    update.pos, update.pos, CHUNK_SIZE,
+                ));
+            }
+        }
+
+        if !block_light_chunks.is_empty() {
+            let chunks: Vec<ChunkPos> = block_light_chunks.into_iter().collect();
+            if let Err(e) = self.update_block_light_chunks(&chunks) {
+                log::warn!("[GpuLightPropagator] Block light not updated: {}", e);
+            }
         }
 
         // Create command encoder
@@ -133,6 +162,85 @@ impl GpuLightPropagator {
         Ok(())
     }
 
+    /// Recompute a chunk's colored block light and upload it for meshing
+    ///
+    /// The chunk and the neighbours within `BLOCK_LIGHT_REACH` are read back
+    /// from the world buffer, which therefore needs `enable_readback`, so
+    /// emitters next door light across the border. Emission comes from the
+    /// registry given to `with_block_registry`.
+    pub fn update_block_light(&self, chunk_pos: ChunkPos) -> anyhow::Result<()> {
+        let resident = self
+            .world_buffer
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock world buffer: {}", e))?
+            .chunk_slot(chunk_pos)
+            .is_some();
+        if !resident {
+            anyhow::bail!("chunk {:?} is not in the world buffer", chunk_pos);
+        }
+        self.update_block_light_chunks(&[chunk_pos])
+    }
+
+    /// `update_block_light` for several chunks, reading each chunk back once
+    ///
+    /// Chunks that are not in the world buffer are skipped.
+    pub fn update_block_light_chunks(&self, chunks: &[ChunkPos]) -> anyhow::Result<()> {
+        let (targets, sources) = {
+            let world_buffer = self
+                .world_buffer
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Failed to lock world buffer: {}", e))?;
+            let targets: Vec<ChunkPos> = chunks
+                .iter()
+                .copied()
+                .filter(|&chunk| world_buffer.chunk_slot(chunk).is_some())
+                .collect();
+            let sources: HashSet<ChunkPos> = targets
+                .iter()
+                .copied()
+                .flat_map(chunk_light_sources)
+                .filter(|&chunk| world_buffer.chunk_slot(chunk).is_some())
+                .collect();
+            (targets, sources)
+        };
+
+        let result = self.light_loaded_chunks(&targets, &sources);
+        for &chunk in &sources {
+            self.block_provider.unload_chunk(chunk);
+        }
+        result
+    }
+
+    /// Read back `sources`, then light and upload each of `targets`
+    fn light_loaded_chunks(
+        &self,
+        targets: &[ChunkPos],
+        sources: &HashSet<ChunkPos>,
+    ) -> anyhow::Result<()> {
+        for &chunk in sources {
+            self.block_provider
+                .load_chunk(&self.device, &self.queue, chunk)?;
+        }
+
+        for &chunk_pos in targets {
+            let light = ChunkLightData::new(chunk_pos, CHUNK_SIZE);
+            BlockLightCalculator::recompute_chunk(&light, &self.block_provider);
+
+            let world_buffer = self
+                .world_buffer
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Failed to lock world buffer: {}", e))?;
+            if !world_buffer.upload_block_light(
+                &self.queue,
+                chunk_pos,
+                &light.block_light_rgb.read(),
+            ) {
+                anyhow::bail!("chunk {:?} is not in the world buffer", chunk_pos);
+            }
+        }
+        Ok(())
+    }
+
     /// Get current statistics
     pub fn get_stats(&self) -> LightingStats {
         self.stats.read().clone()
@@ -144,23 +252,83 @@ impl GpuLightPropagator {
     }
 }
 
+/// Chunks whose emitters can light `chunk_pos`: itself and its neighbours in reach
+fn chunk_light_sources(chunk_pos: ChunkPos) -> Vec<ChunkPos> {
+    let size = CHUNK_SIZE as i32;
+    let min = VoxelPos::new(chunk_pos.x * size, chunk_pos.y * size, chunk_pos.z * size);
+    let max = VoxelPos::new(min.x + size - 1, min.y + size - 1, min.z + size - 1);
+    BlockLightCalculator::chunks_in_reach(min, max, CHUNK_SIZE)
+}
+
 /// Block provider implementation for GPU world buffer
+///
+/// Blocks are only known for chunks copied back with `load_chunk`; everything
+/// else reads as air.
 pub struct GpuBlockProvider {
     world_buffer: Arc<std::sync::Mutex<WorldBuffer>>,
+    /// Per-channel emission of each light-emitting block
+    emitters: HashMap<BlockId, [u8; 3]>,
+    /// Voxels read back from the world buffer, in its chunk layout
+    chunks: parking_lot::RwLock<HashMap<ChunkPos, Vec<VoxelData>>>,
 }
 
 impl GpuBlockProvider {
     pub fn new(world_buffer: Arc<std::sync::Mutex<WorldBuffer>>) -> Self {
-        Self { world_buffer }
+        Self {
+            world_buffer,
+            emitters: HashMap::new(),
+            chunks: parking_lot::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Report the emission of the registry's blocks from `light_emission`
+    pub fn with_emitters(mut self, registry: &BlockRegistry) -> Self {
+        self.emitters = registry
+            .iter_properties()
+            .filter(|(_, properties)| properties.render_data.light_emission > 0)
+            .map(|(id, properties)| {
+                let render = &properties.render_data;
+                (id, emission_rgb(render.light_emission, render.light_color))
+            })
+            .collect();
+        self
+    }
+
+    /// Copy a chunk back from the world buffer so its blocks can be read
+    pub fn load_chunk(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        chunk_pos: ChunkPos,
+    ) -> anyhow::Result<()> {
+        let voxels = self
+            .world_buffer
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock world buffer: {}", e))?
+            .read_chunk(device, queue, chunk_pos)
+            .map_err(|e| anyhow::anyhow!("Failed to read back chunk {:?}: {}", chunk_pos, e))?;
+        self.chunks.write().insert(chunk_pos, voxels);
+        Ok(())
+    }
+
+    /// Drop a chunk copied by `load_chunk`
+    pub fn unload_chunk(&self, chunk_pos: ChunkPos) {
+        self.chunks.write().remove(&chunk_pos);
     }
 }
 
 impl BlockProvider for GpuBlockProvider {
     fn get_block(&self, pos: VoxelPos) -> BlockId {
-        // This is a placeholder - in practice, GPU light propagation
-        // doesn't need CPU-side block queries as all data is on GPU
-        // Return AIR as default for GPU-based provider
-        BlockId::AIR
+        let chunks = self.chunks.read();
+        let Some(voxels) = chunks.get(&pos.to_chunk_pos(CHUNK_SIZE)) else {
+            return BlockId::AIR;
+        };
+        let (x, y, z) = pos.to_local_pos(CHUNK_SIZE);
+        let index = x + y * CHUNK_SIZE + z * CHUNK_SIZE * CHUNK_SIZE;
+        voxels
+            .get(index as usize)
+            .map(|voxel| BlockId(voxel.block_id()))
+            .unwrap_or(BlockId::AIR)
     }
 
     fn is_transparent(&self, pos: VoxelPos) -> bool {
@@ -169,4 +337,103 @@ impl BlockProvider for GpuBlockProvider {
         // Basic transparency check - could be expanded
         block == BlockId::AIR || block == BlockId::WATER
     }
+
+    fn light_emission(&self, pos: VoxelPos) -> [u8; 3] {
+        self.emitters
+            .get(&self.get_block(pos))
+            .copied()
+            .unwrap_or([0; 3])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::core::VOXELS_PER_CHUNK;
+    use crate::world::blocks::register_basic_blocks;
+    use crate::world::storage::WorldBufferDescriptor;
+
+    /// Device for a `WorldBuffer`, or `None` on machines without a suitable adapter
+    ///
+    /// The world buffer exposes its voxels to the vertex stage as writable
+    /// storage, hence `VERTEX_WRITABLE_STORAGE`.
+    fn test_device() -> Option<(Arc<Device>, Arc<Queue>)> {
+        let instance = wgpu::Instance::default();
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        let features = wgpu::Features::VERTEX_WRITABLE_STORAGE;
+        if !adapter.features().contains(features) {
+            return None;
+        }
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("Block Light Test Device"),
+                required_features: features,
+                required_limits: adapter.limits(),
+            },
+            None,
+        ))
+        .ok()?;
+        Some((Arc::new(device), Arc::new(queue)))
+    }
+
+    #[test]
+    fn test_gpu_provider_lights_chunks_from_registry_emitters() {
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No suitable GPU adapter, skipping GPU block provider test");
+            return;
+        };
+
+        let mut registry = BlockRegistry::new();
+        register_basic_blocks(&mut registry);
+        let glowstone = registry
+            .get_id("engine:glowstone")
+            .expect("glowstone is a basic block");
+        let glow_color = registry
+            .get_properties(glowstone)
+            .expect("registered block has properties")
+            .render_data
+            .light_color;
+
+        let world_buffer = Arc::new(std::sync::Mutex::new(WorldBuffer::new(
+            device.clone(),
+            &WorldBufferDescriptor {
+                view_distance: 1,
+                enable_atomics: false,
+                enable_readback: true,
+            },
+        )));
+        let chunk_pos = ChunkPos::new(0, 0, 0);
+        let mut voxels = vec![VoxelData::AIR; VOXELS_PER_CHUNK as usize];
+        voxels[(5 + 6 * CHUNK_SIZE + 7 * CHUNK_SIZE * CHUNK_SIZE) as usize] =
+            VoxelData::new(glowstone.0, 0, 0, 0);
+        world_buffer
+            .lock()
+            .expect("world buffer lock")
+            .upload_chunk(&queue, chunk_pos, &voxels);
+
+        let provider = GpuBlockProvider::new(world_buffer.clone()).with_emitters(&registry);
+        let source = VoxelPos::new(5, 6, 7);
+        assert_eq!(provider.light_emission(source), [0; 3]);
+
+        provider
+            .load_chunk(&device, &queue, chunk_pos)
+            .expect("chunk reads back");
+        assert_eq!(provider.get_block(source), glowstone);
+        assert_eq!(
+            provider.light_emission(source),
+            emission_rgb(15, glow_color)
+        );
+        assert_eq!(provider.light_emission(VoxelPos::new(6, 6, 7)), [0; 3]);
+
+        // The warm glow floods out with more red than blue and uploads in place
+        let light = ChunkLightData::new(chunk_pos, CHUNK_SIZE);
+        BlockLightCalculator::recompute_chunk(&light, &provider);
+        let [r, _, b] = BlockLightCalculator::block_light_at(&light, 6, 6, 7);
+        assert!(r > b);
+        let packed = light.block_light_rgb.read();
+        let world_buffer = world_buffer.lock().expect("world buffer lock");
+        assert!(world_buffer.upload_block_light(&queue, chunk_pos, &packed));
+        assert!(!world_buffer.upload_block_light(&queue, ChunkPos::new(3, 0, 0), &packed));
+    }
 }
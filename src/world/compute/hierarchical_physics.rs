@@ -1,6 +1,8 @@
 use super::{SparseVoxelOctree, VoxelBvh};
+use crate::constants::core::CHUNK_SIZE;
 use crate::error::EngineError;
 use crate::memory::MemoryManager;
+use crate::world::core::{ChunkPos, Ray, VoxelPos};
 use crate::world::error::WorldGpuResult;
 use crate::world::storage::WorldBuffer;
 use bytemuck::{Pod, Zeroable};
@@ -8,6 +10,7 @@ use bytemuck::{Pod, Zeroable};
 ///
 /// Sprint 34: GPU-accelerated physics using hierarchical structures
 use std::sync::Arc;
+use wgpu::util::DeviceExt;
 use wgpu::{Buffer, ComputePipeline, Device, Queue};
 
 /// Physics query types
@@ -27,41 +30,157 @@ pub enum QueryType {
 }
 
 /// Physics query on GPU
+///
+/// Each `[f32; 3]` opens a 16-byte row so the layout matches the WGSL struct,
+/// where `vec3<f32>` is 16-byte aligned.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct PhysicsQuery {
-    /// Query type
-    pub query_type: u32,
     /// Origin or center
     pub origin: [f32; 3],
-    /// Max distance for casts
-    pub max_distance: f32,
+    /// Query type
+    pub query_type: u32,
     /// Direction for casts (normalized)
     pub direction: [f32; 3],
-    /// Radius for sphere cast
-    pub radius: f32,
+    /// Max distance for casts
+    pub max_distance: f32,
     /// Box half-extents
     pub half_extents: [f32; 3],
+    /// Radius for sphere cast
+    pub radius: f32,
     /// Query flags
     pub flags: u32,
+    /// Padding
+    pub _padding: [u32; 3],
 }
 
 /// Physics query result
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct QueryResult {
-    /// Hit distance (-1 if no hit)
-    pub hit_distance: f32,
     /// Hit position
     pub hit_position: [f32; 3],
+    /// Hit distance (-1 if no hit)
+    pub hit_distance: f32,
     /// Hit normal
     pub hit_normal: [f32; 3],
     /// Hit block ID
     pub block_id: u32,
+    /// Solid voxel that was hit (ray casts only)
+    pub hit_voxel: [i32; 3],
     /// Hit chunk index
     pub chunk_index: u32,
-    /// Padding
-    pub _padding: [u32; 3],
+}
+
+/// Ray cast query for `HierarchicalPhysics::execute_queries`
+///
+/// GPU counterpart of `cast_ray`; read the answer back with `ray_hit`.
+pub fn ray_query(ray: Ray, max_distance: f32) -> PhysicsQuery {
+    PhysicsQuery {
+        origin: [ray.origin.x, ray.origin.y, ray.origin.z],
+        query_type: QueryType::RayCast as u32,
+        direction: [ray.direction.x, ray.direction.y, ray.direction.z],
+        max_distance,
+        ..PhysicsQuery::zeroed()
+    }
+}
+
+/// First solid voxel and its distance along the ray, if the ray hit one
+pub fn ray_hit(result: &QueryResult) -> Option<(VoxelPos, f32)> {
+    if result.hit_distance < 0.0 {
+        return None;
+    }
+    let [x, y, z] = result.hit_voxel;
+    Some((VoxelPos::new(x, y, z), result.hit_distance))
+}
+
+/// Slot table entry of a chunk that is not resident
+const NO_SLOT: u32 = u32::MAX;
+
+/// Chunk slot table for one query batch, as read by the query kernels
+///
+/// `WorldBuffer` keeps its chunk-to-slot map on the CPU, so each dispatch
+/// uploads the slots of every chunk its queries can reach: a header of the
+/// minimum chunk and the box size (each padded to four words), then one slot
+/// per chunk in x, then y, then z order. The box is clipped to `resident`, the
+/// bounds of the chunks that have a slot; `chunk_slot` resolves each chunk.
+pub fn physics_slot_table(
+    queries: &[PhysicsQuery],
+    resident: Option<(ChunkPos, ChunkPos)>,
+    chunk_slot: impl Fn(ChunkPos) -> Option<u32>,
+) -> Vec<u32> {
+    let mut reach: Option<([i32; 3], [i32; 3])> = None;
+    for (low, high) in queries.iter().filter_map(query_chunk_bounds) {
+        let (reach_min, reach_max) = reach.get_or_insert((low, high));
+        for axis in 0..3 {
+            reach_min[axis] = reach_min[axis].min(low[axis]);
+            reach_max[axis] = reach_max[axis].max(high[axis]);
+        }
+    }
+
+    let mut min = [0i32; 3];
+    let mut dims = [0u32; 3];
+    if let (Some((reach_min, reach_max)), Some((resident_min, resident_max))) = (reach, resident) {
+        let resident_min = [resident_min.x, resident_min.y, resident_min.z];
+        let resident_max = [resident_max.x, resident_max.y, resident_max.z];
+        for axis in 0..3 {
+            min[axis] = reach_min[axis].max(resident_min[axis]);
+            let max = reach_max[axis].min(resident_max[axis]);
+            dims[axis] = (max - min[axis] + 1).max(0) as u32;
+        }
+    }
+
+    let mut table = vec![
+        min[0] as u32,
+        min[1] as u32,
+        min[2] as u32,
+        0,
+        dims[0],
+        dims[1],
+        dims[2],
+        0,
+    ];
+    for z in 0..dims[2] as i32 {
+        for y in 0..dims[1] as i32 {
+            for x in 0..dims[0] as i32 {
+                let chunk = ChunkPos::new(min[0] + x, min[1] + y, min[2] + z);
+                table.push(chunk_slot(chunk).unwrap_or(NO_SLOT));
+            }
+        }
+    }
+    // The WGSL struct needs at least one slot and a size that is a multiple of 16 bytes
+    let padded_len = table.len().max(9).next_multiple_of(4);
+    table.resize(padded_len, NO_SLOT);
+    table
+}
+
+/// Chunks a query can read voxels from, as inclusive (min, max) chunk coordinates
+fn query_chunk_bounds(query: &PhysicsQuery) -> Option<([i32; 3], [i32; 3])> {
+    // Rays read one voxel past their last boundary; overlaps round outwards.
+    // Box casts read no voxels yet.
+    let padding = match query.query_type {
+        0 | 4 => 1.0,
+        1 => query.radius + 1.0,
+        _ => return None,
+    };
+
+    let mut min = [0i32; 3];
+    let mut max = [0i32; 3];
+    for axis in 0..3 {
+        let (low, high) = if query.query_type == 4 {
+            (
+                query.origin[axis] - query.half_extents[axis],
+                query.origin[axis] + query.half_extents[axis],
+            )
+        } else {
+            let end = query.origin[axis] + query.direction[axis] * query.max_distance;
+            (query.origin[axis].min(end), query.origin[axis].max(end))
+        };
+        let size = CHUNK_SIZE as i32;
+        min[axis] = ((low - padding).floor() as i32).div_euclid(size);
+        max[axis] = ((high + padding).floor() as i32).div_euclid(size);
+    }
+    Some((min, max))
 }
 
 /// Hierarchical physics system
@@ -166,6 +285,17 @@ impl HierarchicalPhysics {
                     },
                     count: None,
                 },
+                // Chunk slot table
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -251,6 +381,18 @@ impl HierarchicalPhysics {
         // Upload queries
         queue.write_buffer(&self.query_buffer, 0, bytemuck::cast_slice(queries));
 
+        // Chunk slots live in the WorldBuffer's CPU-side map
+        let resident = world_buffer.resident_chunk_bounds();
+        let slot_table =
+            physics_slot_table(queries, resident, |chunk| world_buffer.chunk_slot(chunk));
+        let slot_table_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Physics Slot Table"),
+                contents: bytemuck::cast_slice(&slot_table),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+
         // Create bind group
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Physics Query Bind Group"),
@@ -276,10 +418,16 @@ impl HierarchicalPhysics {
                     binding: 4,
                     resource: self.result_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: slot_table_buffer.as_entire_binding(),
+                },
             ],
         });
 
-        // Group queries by type for better GPU utilization
+        // Each kernel filters by type at its own index, so every pass covers the
+        // whole batch; passes for types not in the batch are skipped
+        let workgroups = (queries.len() as u32 + 63) / 64;
         let mut raycast_count = 0;
         let mut spherecast_count = 0;
         let mut boxcast_count = 0;
@@ -304,7 +452,7 @@ impl HierarchicalPhysics {
 
             compute_pass.set_pipeline(&self.raycast_pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            compute_pass.dispatch_workgroups((raycast_count + 63) / 64, 1, 1);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
         }
 
         if spherecast_count > 0 {
@@ -315,7 +463,7 @@ impl HierarchicalPhysics {
 
             compute_pass.set_pipeline(&self.spherecast_pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            compute_pass.dispatch_workgroups((spherecast_count + 63) / 64, 1, 1);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
         }
 
         if boxcast_count > 0 {
@@ -326,7 +474,7 @@ impl HierarchicalPhysics {
 
             compute_pass.set_pipeline(&self.boxcast_pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            compute_pass.dispatch_workgroups((boxcast_count + 63) / 64, 1, 1);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
         }
 
         if overlap_count > 0 {
@@ -337,7 +485,7 @@ impl HierarchicalPhysics {
 
             compute_pass.set_pipeline(&self.overlap_pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            compute_pass.dispatch_workgroups((overlap_count + 63) / 64, 1, 1);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
         }
     }
 
@@ -399,3 +547,40 @@ impl HierarchicalPhysics {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::{Point3, Vector3};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_slot_table_covers_ray_reach_across_negative_chunks() {
+        let slots = HashMap::from([(ChunkPos::new(-1, 0, 0), 3), (ChunkPos::new(0, 0, 0), 1)]);
+        let resident = Some((ChunkPos::new(-1, 0, 0), ChunkPos::new(0, 0, 0)));
+        let ray = ray_query(
+            Ray::new(Point3::new(5.5, 5.5, 10.5), Vector3::new(-1.0, 0.0, 0.0)),
+            10.0,
+        );
+
+        let table = physics_slot_table(&[ray], resident, |chunk| slots.get(&chunk).copied());
+
+        // min chunk (-1, 0, 0), a 2x1x1 box, then the slots of x = -1 and x = 0
+        assert_eq!(table[..10], [-1i32 as u32, 0, 0, 0, 2, 1, 1, 0, 3, 1]);
+        assert_eq!(table[10..], [NO_SLOT, NO_SLOT]);
+    }
+
+    #[test]
+    fn test_slot_table_without_reachable_chunks_is_empty() {
+        let resident = Some((ChunkPos::new(0, 0, 0), ChunkPos::new(0, 0, 0)));
+        let box_cast = PhysicsQuery {
+            query_type: QueryType::BoxCast as u32,
+            ..PhysicsQuery::zeroed()
+        };
+
+        let table = physics_slot_table(&[box_cast], resident, |_| Some(0));
+
+        assert_eq!(table[4..8], [0, 0, 0, 0]);
+        assert_eq!(table[8..], [NO_SLOT; 4]);
+    }
+}
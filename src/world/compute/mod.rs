@@ -22,8 +22,11 @@ pub use chunk_modifier::{ChunkModifier, ModificationCommand};
 pub use kernels::{SystemFlags, UnifiedKernelConfig, UnifiedWorldKernel};
 
 // GPU optimization structures
-pub use bvh::{BvhNode, BvhStats, VoxelBvh};
-pub use hierarchical_physics::{HierarchicalPhysics, PhysicsQuery, QueryResult, QueryType};
+pub use bvh::{BvhNode, BvhStats, VoxelBvh, DEFAULT_REFIT_DEGRADATION_THRESHOLD};
+pub use hierarchical_physics::{
+    physics_slot_table, ray_hit, ray_query, HierarchicalPhysics, PhysicsQuery, QueryResult,
+    QueryType,
+};
 pub use sparse_octree::{OctreeNode, OctreeStats, OctreeUpdater, SparseVoxelOctree};
 
 // Memory management
@@ -39,7 +42,10 @@ pub use effects::{
 pub use skylight::{SkylightCalculator, MAX_SKY_LIGHT};
 
 // GPU block queries
-pub use gpu_block_query::{BlockQueryHandle, BlockQueryRequest, BlockQueryResult, GpuBlockQuery};
+pub use gpu_block_query::{
+    block_query_buffer_index, block_query_result, BlockQueryBackend, BlockQueryHandle,
+    BlockQueryRequest, BlockQueryResult, CpuBlockQuery, GpuBlockQuery,
+};
 
 /// Unified compute backend for GPU world processing
 pub struct UnifiedCompute {
@@ -47,6 +53,8 @@ pub struct UnifiedCompute {
     queue: std::sync::Arc<wgpu::Queue>,
     kernel: UnifiedWorldKernel,
     memory_manager: UnifiedMemoryManager,
+    /// The memory manager is the reduced stand-in, not a full-world layout
+    memory_is_placeholder: bool,
 }
 
 impl UnifiedCompute {
@@ -70,6 +78,7 @@ impl UnifiedCompute {
             queue,
             kernel,
             memory_manager,
+            memory_is_placeholder: true,
         })
     }
 
@@ -83,15 +92,13 @@ impl UnifiedCompute {
     }
 
     /// Get memory statistics
+    ///
+    /// Reports the per-region sizes of the unified buffer actually allocated.
+    /// `is_placeholder` is set while the reduced stand-in manager is in use.
     pub fn memory_stats(&self) -> MemoryStats {
-        // TODO: Implement proper memory stats
         MemoryStats {
-            total_allocated: 0,
-            voxel_data: 0,
-            chunk_metadata: 0,
-            lighting_data: 0,
-            entity_data: 0,
-            particle_data: 0,
+            is_placeholder: self.memory_is_placeholder,
+            ..self.memory_manager.get_memory_stats()
         }
     }
 
@@ -1,10 +1,11 @@
 use crate::memory::MemoryManager;
-use crate::world::core::ChunkPos;
+use crate::world::core::{ChunkPos, VoxelPos};
 use crate::world::storage::WorldBuffer;
 use bytemuck::{Pod, Zeroable};
 /// Sparse Voxel Octree for Empty Space Skipping
 ///
 /// Sprint 34: Hierarchical acceleration structure for the unified kernel
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use wgpu::{Buffer, Device, Queue};
 
@@ -67,9 +68,16 @@ pub struct SparseVoxelOctree {
     node_capacity: u32,
     next_free_node: u32,
 
+    /// CPU copy of the allocated nodes, used for range queries
+    nodes: Vec<OctreeNode>,
+
     /// Octree configuration
     world_size: u32,
     max_depth: u32,
+
+    /// Range query counters
+    nodes_visited: AtomicU64,
+    leaves_visited: AtomicU64,
 }
 
 impl SparseVoxelOctree {
@@ -92,8 +100,11 @@ impl SparseVoxelOctree {
             node_buffer,
             node_capacity,
             next_free_node: 1, // 0 is reserved for null
+            nodes: Vec::new(),
             world_size,
             max_depth,
+            nodes_visited: AtomicU64::new(0),
+            leaves_visited: AtomicU64::new(0),
         }
     }
 
@@ -101,140 +112,50 @@ impl SparseVoxelOctree {
     pub fn build_from_world(
         &mut self,
         queue: &Queue,
-        world_buffer: &WorldBuffer,
+        _world_buffer: &WorldBuffer,
         active_chunks: &[ChunkPos],
     ) {
         // This would typically be done on GPU, but for initial implementation
         // we'll build a simple structure
-
-        let mut nodes = vec![OctreeNode::EMPTY; self.node_capacity as usize];
-
-        // Root node
-        nodes[0] = OctreeNode {
-            children: [0; 8],
-            metadata: self.max_depth, // Set level
-            bbox_min: [0.0, 0.0, 0.0],
-            bbox_max: [self.world_size as f32; 3],
-        };
-
-        // Build octree for active chunks
-        for chunk_pos in active_chunks {
-            self.insert_chunk(&mut nodes, chunk_pos);
+        let nodes = build_octree_nodes(self.world_size, self.max_depth, active_chunks);
+
+        if nodes.len() > self.node_capacity as usize {
+            log::error!(
+                "[SparseVoxelOctree] {} nodes exceed the capacity of {}, octree not uploaded",
+                nodes.len(),
+                self.node_capacity
+            );
+        } else {
+            queue.write_buffer(&self.node_buffer, 0, bytemuck::cast_slice(&nodes));
         }
 
-        // Upload to GPU
-        queue.write_buffer(
-            &self.node_buffer,
-            0,
-            bytemuck::cast_slice(&nodes[..self.next_free_node as usize]),
-        );
+        self.next_free_node = nodes.len() as u32;
+        self.nodes = nodes;
     }
 
-    /// Insert a chunk into the octree
-    fn insert_chunk(&mut self, nodes: &mut [OctreeNode], chunk_pos: &ChunkPos) {
-        let mut current_node = 0;
-        let mut current_level = self.max_depth;
-        let mut current_size = self.world_size;
-        let mut current_pos = [0u32; 3];
-
-        // Traverse down the octree
-        while current_level > 0 {
-            let half_size = current_size / 2;
-
-            // Determine which octant the chunk belongs to
-            let octant = self.calculate_octant(chunk_pos, current_pos, half_size);
-
-            // Get or create child node
-            if nodes[current_node].children[octant] == 0 {
-                // Allocate new node
-                let new_node = self.next_free_node;
-                self.next_free_node += 1;
-
-                nodes[current_node].set_child(octant, new_node);
-
-                // Initialize child node
-                nodes[new_node as usize] = OctreeNode {
-                    children: [0; 8],
-                    metadata: current_level - 1,
-                    bbox_min: [
-                        current_pos[0] as f32
-                            + if octant & 1 != 0 {
-                                half_size as f32
-                            } else {
-                                0.0
-                            },
-                        current_pos[1] as f32
-                            + if octant & 2 != 0 {
-                                half_size as f32
-                            } else {
-                                0.0
-                            },
-                        current_pos[2] as f32
-                            + if octant & 4 != 0 {
-                                half_size as f32
-                            } else {
-                                0.0
-                            },
-                    ],
-                    bbox_max: [
-                        current_pos[0] as f32
-                            + if octant & 1 != 0 {
-                                current_size as f32
-                            } else {
-                                half_size as f32
-                            },
-                        current_pos[1] as f32
-                            + if octant & 2 != 0 {
-                                current_size as f32
-                            } else {
-                                half_size as f32
-                            },
-                        current_pos[2] as f32
-                            + if octant & 4 != 0 {
-                                current_size as f32
-                            } else {
-                                half_size as f32
-                            },
-                    ],
-                };
-            }
-
-            // Move to child
-            current_node = nodes[current_node].children[octant] as usize;
-            current_level -= 1;
-            current_size = half_size;
-
-            // Update position
-            if octant & 1 != 0 {
-                current_pos[0] += half_size;
-            }
-            if octant & 2 != 0 {
-                current_pos[1] += half_size;
-            }
-            if octant & 4 != 0 {
-                current_pos[2] += half_size;
-            }
-        }
-
-        // Mark leaf as occupied
-        nodes[current_node].metadata |= 0xFF00; // Full occupancy
+    /// Visit every occupied leaf whose cell intersects the inclusive box `min..=max`
+    ///
+    /// Coordinates are in octree units (one leaf cell per chunk position).
+    /// Subtrees whose bounds miss the box are skipped entirely, and empty
+    /// (never allocated) cells are never visited.
+    pub fn for_each_leaf_in_box(
+        &self,
+        min: VoxelPos,
+        max: VoxelPos,
+        f: impl FnMut(VoxelPos, &OctreeNode),
+    ) {
+        let (nodes_visited, leaves_visited) =
+            for_each_occupied_leaf_in_box(&self.nodes, min, max, f);
+        self.nodes_visited
+            .fetch_add(nodes_visited, Ordering::Relaxed);
+        self.leaves_visited
+            .fetch_add(leaves_visited, Ordering::Relaxed);
     }
 
-    /// Calculate which octant a position belongs to
-    fn calculate_octant(&self, chunk_pos: &ChunkPos, base_pos: [u32; 3], half_size: u32) -> usize {
-        let mut octant = 0;
-
-        if chunk_pos.x as u32 >= base_pos[0] + half_size {
-            octant |= 1;
-        }
-        if chunk_pos.y as u32 >= base_pos[1] + half_size {
-            octant |= 2;
-        }
-        if chunk_pos.z as u32 >= base_pos[2] + half_size {
-            octant |= 4;
-        }
-
-        octant
+    /// Reset the range query counters reported in `OctreeStats`
+    pub fn reset_query_stats(&self) {
+        self.nodes_visited.store(0, Ordering::Relaxed);
+        self.leaves_visited.store(0, Ordering::Relaxed);
     }
 
     /// Get the GPU buffer containing octree nodes
@@ -248,6 +169,8 @@ impl SparseVoxelOctree {
             total_nodes: self.next_free_node,
             node_capacity: self.node_capacity,
             max_depth: self.max_depth,
+            nodes_visited: self.nodes_visited.load(Ordering::Relaxed),
+            leaves_visited: self.leaves_visited.load(Ordering::Relaxed),
             memory_usage_mb: (self.next_free_node as f32
                 * std::mem::size_of::<OctreeNode>() as f32)
                 / (1024.0 * 1024.0),
@@ -261,9 +184,196 @@ pub struct OctreeStats {
     pub total_nodes: u32,
     pub node_capacity: u32,
     pub max_depth: u32,
+    /// Nodes touched by range queries since the last reset
+    pub nodes_visited: u64,
+    /// Occupied leaves reported by range queries since the last reset
+    pub leaves_visited: u64,
     pub memory_usage_mb: f32,
 }
 
+/// Build the octree nodes for the occupied chunk cells; node 0 is the root
+fn build_octree_nodes(
+    world_size: u32,
+    max_depth: u32,
+    active_chunks: &[ChunkPos],
+) -> Vec<OctreeNode> {
+    let mut nodes = vec![OctreeNode {
+        children: [0; 8],
+        metadata: max_depth, // Set level
+        bbox_min: [0.0, 0.0, 0.0],
+        bbox_max: [world_size as f32; 3],
+    }];
+
+    for chunk_pos in active_chunks {
+        insert_chunk(&mut nodes, world_size, max_depth, chunk_pos);
+    }
+
+    nodes
+}
+
+/// Insert a chunk into the octree
+fn insert_chunk(
+    nodes: &mut Vec<OctreeNode>,
+    world_size: u32,
+    max_depth: u32,
+    chunk_pos: &ChunkPos,
+) {
+    let mut current_node = 0;
+    let mut current_level = max_depth;
+    let mut current_size = world_size;
+    let mut current_pos = [0u32; 3];
+
+    // Traverse down the octree
+    while current_level > 0 {
+        let half_size = current_size / 2;
+
+        // Determine which octant the chunk belongs to
+        let octant = calculate_octant(chunk_pos, current_pos, half_size);
+
+        // Get or create child node
+        if nodes[current_node].children[octant] == 0 {
+            // Allocate and initialize the child node
+            let new_node = nodes.len() as u32;
+            nodes.push(OctreeNode {
+                children: [0; 8],
+                metadata: current_level - 1,
+                bbox_min: [
+                    current_pos[0] as f32
+                        + if octant & 1 != 0 {
+                            half_size as f32
+                        } else {
+                            0.0
+                        },
+                    current_pos[1] as f32
+                        + if octant & 2 != 0 {
+                            half_size as f32
+                        } else {
+                            0.0
+                        },
+                    current_pos[2] as f32
+                        + if octant & 4 != 0 {
+                            half_size as f32
+                        } else {
+                            0.0
+                        },
+                ],
+                bbox_max: [
+                    current_pos[0] as f32
+                        + if octant & 1 != 0 {
+                            current_size as f32
+                        } else {
+                            half_size as f32
+                        },
+                    current_pos[1] as f32
+                        + if octant & 2 != 0 {
+                            current_size as f32
+                        } else {
+                            half_size as f32
+                        },
+                    current_pos[2] as f32
+                        + if octant & 4 != 0 {
+                            current_size as f32
+                        } else {
+                            half_size as f32
+                        },
+                ],
+            });
+            nodes[current_node].set_child(octant, new_node);
+        }
+
+        // Move to child
+        current_node = nodes[current_node].children[octant] as usize;
+        current_level -= 1;
+        current_size = half_size;
+
+        // Update position
+        if octant & 1 != 0 {
+            current_pos[0] += half_size;
+        }
+        if octant & 2 != 0 {
+            current_pos[1] += half_size;
+        }
+        if octant & 4 != 0 {
+            current_pos[2] += half_size;
+        }
+    }
+
+    // Mark leaf as occupied
+    nodes[current_node].metadata |= 0xFF00; // Full occupancy
+}
+
+/// Calculate which octant a position belongs to
+fn calculate_octant(chunk_pos: &ChunkPos, base_pos: [u32; 3], half_size: u32) -> usize {
+    let mut octant = 0;
+
+    if chunk_pos.x as u32 >= base_pos[0] + half_size {
+        octant |= 1;
+    }
+    if chunk_pos.y as u32 >= base_pos[1] + half_size {
+        octant |= 2;
+    }
+    if chunk_pos.z as u32 >= base_pos[2] + half_size {
+        octant |= 4;
+    }
+
+    octant
+}
+
+/// Walk `nodes` from the root, calling `f` for occupied leaves inside the box
+/// Returns `(nodes_visited, leaves_visited)`.
+fn for_each_occupied_leaf_in_box(
+    nodes: &[OctreeNode],
+    min: VoxelPos,
+    max: VoxelPos,
+    mut f: impl FnMut(VoxelPos, &OctreeNode),
+) -> (u64, u64) {
+    let mut nodes_visited = 0;
+    let mut leaves_visited = 0;
+    if nodes.is_empty() {
+        return (0, 0);
+    }
+
+    let query_min = [min.x as f32, min.y as f32, min.z as f32];
+    let query_max = [max.x as f32, max.y as f32, max.z as f32];
+    let intersects = |node: &OctreeNode| {
+        (0..3).all(|axis| {
+            node.bbox_min[axis] <= query_max[axis] && node.bbox_max[axis] > query_min[axis]
+        })
+    };
+
+    let mut stack = vec![0usize];
+    while let Some(index) = stack.pop() {
+        let Some(node) = nodes.get(index) else {
+            continue;
+        };
+        nodes_visited += 1;
+        if !intersects(node) {
+            continue;
+        }
+
+        if node.is_leaf() {
+            if node.occupancy_mask() != 0 {
+                leaves_visited += 1;
+                let pos = VoxelPos::new(
+                    node.bbox_min[0] as i32,
+                    node.bbox_min[1] as i32,
+                    node.bbox_min[2] as i32,
+                );
+                f(pos, node);
+            }
+            continue;
+        }
+
+        for &child in &node.children {
+            if child != 0 {
+                stack.push(child as usize);
+            }
+        }
+    }
+
+    (nodes_visited, leaves_visited)
+}
+
 /// GPU compute shader for octree updates
 pub struct OctreeUpdater {
     device: Arc<Device>,
@@ -366,3 +476,98 @@ impl OctreeUpdater {
         compute_pass.dispatch_workgroups(octree.next_free_node / 64 + 1, 1, 1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_SIZE: u32 = 16;
+    const MAX_DEPTH: u32 = 4;
+
+    /// Every third cell of a 16^3 world, plus an empty corner
+    fn sample_chunks() -> Vec<ChunkPos> {
+        let mut chunks = Vec::new();
+        for x in 0..WORLD_SIZE as i32 {
+            for y in 0..WORLD_SIZE as i32 {
+                for z in 0..WORLD_SIZE as i32 {
+                    let in_empty_corner = x >= 12 && y >= 12 && z >= 12;
+                    if (x + y + z) % 3 == 0 && !in_empty_corner {
+                        chunks.push(ChunkPos::new(x, y, z));
+                    }
+                }
+            }
+        }
+        chunks
+    }
+
+    /// Naive baseline: look at every node, keep occupied leaves inside the box
+    fn full_traversal(nodes: &[OctreeNode], min: VoxelPos, max: VoxelPos) -> Vec<VoxelPos> {
+        let mut leaves: Vec<VoxelPos> = nodes
+            .iter()
+            .filter(|node| node.is_leaf() && node.occupancy_mask() != 0)
+            .map(|node| {
+                VoxelPos::new(
+                    node.bbox_min[0] as i32,
+                    node.bbox_min[1] as i32,
+                    node.bbox_min[2] as i32,
+                )
+            })
+            .filter(|pos| {
+                (min.x..=max.x).contains(&pos.x)
+                    && (min.y..=max.y).contains(&pos.y)
+                    && (min.z..=max.z).contains(&pos.z)
+            })
+            .collect();
+        leaves.sort_by_key(|pos| (pos.x, pos.y, pos.z));
+        leaves
+    }
+
+    fn query(nodes: &[OctreeNode], min: VoxelPos, max: VoxelPos) -> (Vec<VoxelPos>, u64, u64) {
+        let mut leaves = Vec::new();
+        let (nodes_visited, leaves_visited) =
+            for_each_occupied_leaf_in_box(nodes, min, max, |pos, _| leaves.push(pos));
+        leaves.sort_by_key(|pos| (pos.x, pos.y, pos.z));
+        (leaves, nodes_visited, leaves_visited)
+    }
+
+    #[test]
+    fn test_box_query_matches_full_traversal() {
+        let chunks = sample_chunks();
+        let nodes = build_octree_nodes(WORLD_SIZE, MAX_DEPTH, &chunks);
+
+        let boxes = [
+            (VoxelPos::new(0, 0, 0), VoxelPos::new(15, 15, 15)),
+            (VoxelPos::new(2, 3, 4), VoxelPos::new(6, 5, 9)),
+            (VoxelPos::new(7, 7, 7), VoxelPos::new(7, 7, 7)),
+            (VoxelPos::new(-5, -5, -5), VoxelPos::new(1, 1, 1)),
+        ];
+        for (min, max) in boxes {
+            let (leaves, _, leaves_visited) = query(&nodes, min, max);
+            let expected = full_traversal(&nodes, min, max);
+            assert_eq!(leaves, expected);
+            assert_eq!(leaves_visited, expected.len() as u64);
+        }
+
+        let (leaves, _, _) = query(&nodes, VoxelPos::new(0, 0, 0), VoxelPos::new(15, 15, 15));
+        assert_eq!(leaves.len(), chunks.len());
+    }
+
+    #[test]
+    fn test_box_query_prunes_subtrees() {
+        let nodes = build_octree_nodes(WORLD_SIZE, MAX_DEPTH, &sample_chunks());
+
+        // A small box touches a handful of nodes, not the whole tree
+        let (leaves, nodes_visited, _) =
+            query(&nodes, VoxelPos::new(2, 3, 4), VoxelPos::new(3, 4, 5));
+        assert!(!leaves.is_empty());
+        assert!(nodes_visited * 10 < nodes.len() as u64);
+
+        // The empty corner's cell was never allocated, so the walk stops at
+        // the root's children and grandchildren
+        let (leaves, nodes_visited, leaves_visited) =
+            query(&nodes, VoxelPos::new(12, 12, 12), VoxelPos::new(15, 15, 15));
+        assert!(leaves.is_empty());
+        assert_eq!(leaves_visited, 0);
+        assert!(nodes_visited <= 1 + 8 + 8);
+    }
+}
@@ -146,6 +146,7 @@ impl UnifiedMemoryManager {
             lighting_data: self.layout.lighting_data_size,
             entity_data: self.layout.entity_data_size,
             particle_data: self.layout.particle_data_size,
+            is_placeholder: false,
         }
     }
 
@@ -276,11 +277,17 @@ pub struct MemoryStats {
     pub lighting_data: u64,
     pub entity_data: u64,
     pub particle_data: u64,
+    /// True when the numbers come from a reduced stand-in allocation rather
+    /// than a layout sized for the configured world
+    pub is_placeholder: bool,
 }
 
 impl MemoryStats {
     pub fn print_summary(&self) {
         println!("=== GPU Memory Usage ===");
+        if self.is_placeholder {
+            println!("(placeholder allocation - not sized for the full world)");
+        }
         println!(
             "Total: {:.2} GB",
             self.total_allocated as f64 / (1024.0 * 1024.0 * 1024.0)
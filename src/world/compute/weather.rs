@@ -299,6 +299,22 @@ impl WeatherGpu {
         &self.particle_buffer
     }
 
+    /// Upload the weather transition state for a single region
+    pub fn upload_region_transition(&self, region: u32, transition: &WeatherTransition) {
+        if region >= self.config.region_count {
+            log::warn!(
+                "[WeatherGpu] Region {} out of range (region_count = {})",
+                region,
+                self.config.region_count
+            );
+            return;
+        }
+
+        let offset = region as u64 * std::mem::size_of::<WeatherTransition>() as u64;
+        self.queue
+            .write_buffer(&self.weather_buffer, offset, bytemuck::bytes_of(transition));
+    }
+
     /// Get weather buffer for reading current conditions
     pub fn weather_buffer(&self) -> &wgpu::Buffer {
         &self.weather_buffer
@@ -130,6 +130,9 @@ pub struct RenderData {
     pub color: [f32; 3],
     pub texture_id: u32,
     pub light_emission: u8,
+    /// Tint of the emitted light, per channel 0.0 - 1.0; unused when
+    /// `light_emission` is 0
+    pub light_color: [f32; 3],
 }
 
 /// Physical properties of a block
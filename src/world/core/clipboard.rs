@@ -0,0 +1,262 @@
+//! Voxel clipboard for copying and pasting block regions
+//!
+//! A clipboard is a dense box of block ids, indexed
+//! `x + y * size_x + z * size_x * size_y`. It holds no world state, so it can
+//! be serialized, saved to disk and pasted into any world.
+
+use super::{BlockId, VoxelPos};
+use crate::world::interfaces::WorldError;
+use crate::WorldInterface;
+use serde::{Deserialize, Serialize};
+
+/// Rotation around the Y axis applied when pasting
+///
+/// Turns are clockwise seen from above: a quarter turn swings +X onto +Z.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClipboardRotation {
+    None,
+    Quarter,
+    Half,
+    ThreeQuarter,
+}
+
+impl ClipboardRotation {
+    fn quarter_turns(self) -> u32 {
+        match self {
+            ClipboardRotation::None => 0,
+            ClipboardRotation::Quarter => 1,
+            ClipboardRotation::Half => 2,
+            ClipboardRotation::ThreeQuarter => 3,
+        }
+    }
+}
+
+/// Copied block ids of a box-shaped region
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoxelClipboard {
+    /// Extent in voxels (x, y, z)
+    pub size: [u32; 3],
+    pub blocks: Vec<BlockId>,
+}
+
+impl VoxelClipboard {
+    /// Copy the inclusive box `min..=max` through `get_block`
+    ///
+    /// Fails if `min` exceeds `max` on any axis, or if the box holds more
+    /// voxels than fit in memory.
+    pub fn capture(
+        min: VoxelPos,
+        max: VoxelPos,
+        get_block: impl Fn(VoxelPos) -> BlockId,
+    ) -> Result<Self, WorldError> {
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            return Err(WorldError::OperationFailed {
+                message: format!("clipboard min {:?} exceeds max {:?}", min, max),
+            });
+        }
+
+        let too_large = || WorldError::OperationFailed {
+            message: format!("clipboard region {:?}..={:?} is too large", min, max),
+        };
+        let extent = |low: i32, high: i32| high.abs_diff(low).checked_add(1);
+        let size = [
+            extent(min.x, max.x).ok_or_else(too_large)?,
+            extent(min.y, max.y).ok_or_else(too_large)?,
+            extent(min.z, max.z).ok_or_else(too_large)?,
+        ];
+        let volume = (size[0] as usize)
+            .checked_mul(size[1] as usize)
+            .and_then(|area| area.checked_mul(size[2] as usize))
+            .ok_or_else(too_large)?;
+        let mut blocks = Vec::with_capacity(volume);
+        for z in min.z..=max.z {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    blocks.push(get_block(VoxelPos::new(x, y, z)));
+                }
+            }
+        }
+
+        Ok(Self { size, blocks })
+    }
+
+    /// Block at a position inside the clipboard, `None` outside it
+    pub fn get(&self, x: u32, y: u32, z: u32) -> Option<BlockId> {
+        let [size_x, size_y, size_z] = self.size;
+        if x >= size_x || y >= size_y || z >= size_z {
+            return None;
+        }
+        self.blocks
+            .get(voxel_index(x, y, z, size_x, size_y))
+            .copied()
+    }
+
+    /// Copy of the clipboard turned around the Y axis
+    pub fn rotated(&self, rotation: ClipboardRotation) -> Self {
+        let mut rotated = self.clone();
+        for _ in 0..rotation.quarter_turns() {
+            rotated = rotated.quarter_turn();
+        }
+        rotated
+    }
+
+    /// Write every voxel through `set_block`, with the rotated clipboard's
+    /// minimum corner at `anchor`
+    ///
+    /// Air is written too, so pasting clears whatever was in the box.
+    pub fn paste(
+        &self,
+        anchor: VoxelPos,
+        rotation: ClipboardRotation,
+        mut set_block: impl FnMut(VoxelPos, BlockId),
+    ) {
+        let rotated = self.rotated(rotation);
+        let [size_x, size_y, _] = rotated.size;
+        let (size_x, size_y) = (size_x as usize, size_y as usize);
+        for (index, block) in rotated.blocks.iter().enumerate() {
+            let x = index % size_x;
+            let y = (index / size_x) % size_y;
+            let z = index / (size_x * size_y);
+            set_block(
+                VoxelPos::new(
+                    anchor.x + x as i32,
+                    anchor.y + y as i32,
+                    anchor.z + z as i32,
+                ),
+                *block,
+            );
+        }
+    }
+
+    /// One clockwise quarter turn: (x, z) -> (size_z - 1 - z, x)
+    fn quarter_turn(&self) -> Self {
+        let [size_x, size_y, size_z] = self.size;
+        let size = [size_z, size_y, size_x];
+        let mut blocks = vec![BlockId::AIR; self.blocks.len()];
+
+        for z in 0..size_z {
+            for y in 0..size_y {
+                for x in 0..size_x {
+                    let (new_x, new_z) = (size_z - 1 - z, x);
+                    let from = voxel_index(x, y, z, size_x, size_y);
+                    let to = voxel_index(new_x, y, new_z, size[0], size_y);
+                    blocks[to] = self.blocks[from];
+                }
+            }
+        }
+
+        Self { size, blocks }
+    }
+}
+
+/// Index of (x, y, z) in a clipboard's block array, computed in `usize`
+fn voxel_index(x: u32, y: u32, z: u32, size_x: u32, size_y: u32) -> usize {
+    let (size_x, size_y) = (size_x as usize, size_y as usize);
+    x as usize + y as usize * size_x + z as usize * size_x * size_y
+}
+
+/// Copy the inclusive box `min..=max` of a world into a clipboard
+pub fn copy_region<W: WorldInterface + ?Sized>(
+    world: &W,
+    min: VoxelPos,
+    max: VoxelPos,
+) -> Result<VoxelClipboard, WorldError> {
+    VoxelClipboard::capture(min, max, |pos| world.get_block(pos))
+}
+
+/// Paste a clipboard into a world, rotated, with its minimum corner at `anchor`
+///
+/// Stops at the first failed write; voxels already written stay written.
+pub fn paste_region<W: WorldInterface + ?Sized>(
+    world: &mut W,
+    clipboard: &VoxelClipboard,
+    anchor: VoxelPos,
+    rotation: ClipboardRotation,
+) -> Result<(), WorldError> {
+    let mut result = Ok(());
+    clipboard.paste(anchor, rotation, |pos, block| {
+        if result.is_ok() {
+            result = world.set_block(pos, block);
+        }
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_quarter_turn_paste_keeps_orientation() {
+        // Stone at the origin, dirt east of it, glass two blocks south
+        let source = HashMap::from([
+            (VoxelPos::new(10, 5, 10), BlockId::STONE),
+            (VoxelPos::new(11, 5, 10), BlockId::DIRT),
+            (VoxelPos::new(10, 5, 12), BlockId::GLASS),
+        ]);
+        let clipboard =
+            VoxelClipboard::capture(VoxelPos::new(10, 5, 10), VoxelPos::new(11, 5, 12), |pos| {
+                source.get(&pos).copied().unwrap_or(BlockId::AIR)
+            })
+            .expect("min <= max");
+        assert_eq!(clipboard.size, [2, 1, 3]);
+
+        // Saved clipboards load back unchanged
+        let bytes = bincode::serialize(&clipboard).expect("clipboard serializes");
+        let loaded: VoxelClipboard = bincode::deserialize(&bytes).expect("clipboard deserializes");
+        assert_eq!(loaded, clipboard);
+
+        let mut target = HashMap::new();
+        loaded.paste(
+            VoxelPos::new(0, 0, 0),
+            ClipboardRotation::Quarter,
+            |pos, block| {
+                target.insert(pos, block);
+            },
+        );
+
+        // The east-running stone/dirt row now runs south; glass swings west
+        assert_eq!(target.len(), 6);
+        assert_eq!(target[&VoxelPos::new(2, 0, 0)], BlockId::STONE);
+        assert_eq!(target[&VoxelPos::new(2, 0, 1)], BlockId::DIRT);
+        assert_eq!(target[&VoxelPos::new(0, 0, 0)], BlockId::GLASS);
+        let solid = target
+            .values()
+            .filter(|block| **block != BlockId::AIR)
+            .count();
+        assert_eq!(solid, 3);
+
+        // Four quarter turns are the identity
+        let mut turned = clipboard.clone();
+        for _ in 0..4 {
+            turned = turned.rotated(ClipboardRotation::Quarter);
+        }
+        assert_eq!(turned, clipboard);
+
+        assert!(
+            VoxelClipboard::capture(VoxelPos::new(1, 0, 0), VoxelPos::new(0, 0, 0), |_| {
+                BlockId::AIR
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_capture_rejects_regions_too_large_to_hold() {
+        let reach = 1 << 30;
+        let captured = VoxelClipboard::capture(
+            VoxelPos::new(-reach, -reach, -reach),
+            VoxelPos::new(reach, reach, reach),
+            |_| BlockId::AIR,
+        );
+        assert!(captured.is_err());
+
+        let captured = VoxelClipboard::capture(
+            VoxelPos::new(i32::MIN, 0, 0),
+            VoxelPos::new(i32::MAX, 0, 0),
+            |_| BlockId::AIR,
+        );
+        assert!(captured.is_err());
+    }
+}
@@ -4,11 +4,13 @@
 //! of the world system, independent of whether CPU or GPU backend is used.
 
 mod block;
+mod clipboard;
 mod position;
 mod ray;
 mod registry;
 
 pub use block::{BlockId, PhysicsProperties, RenderData};
+pub use clipboard::{copy_region, paste_region, ClipboardRotation, VoxelClipboard};
 pub use position::{ChunkPos, VoxelPos};
 pub use ray::{cast_ray, BlockFace, Ray, RaycastHit};
 pub use registry::{BlockRegistry, BlockRegistration};
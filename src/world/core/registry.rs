@@ -1,4 +1,6 @@
 use super::BlockId;
+use crate::world::block_tick::BlockTickHandler;
+use crate::world::block_updates::NeighborChangedHandler;
 use crate::world::blocks::block_data::{BlockProperties, BLOCK_PROPERTIES};
 use std::collections::HashMap;
 
@@ -18,6 +20,10 @@ pub struct BlockRegistry {
     name_to_id: HashMap<String, BlockId>,
     /// All registered blocks
     registrations: Vec<BlockRegistration>,
+    /// Block tick behaviour, run by `BlockTickScheduler`
+    tick_handlers: HashMap<BlockId, BlockTickHandler>,
+    /// Reactions to adjacent changes, run by `NeighborUpdater`
+    neighbor_handlers: HashMap<BlockId, NeighborChangedHandler>,
     next_engine_id: u16,
     next_game_id: u16,
 }
@@ -28,6 +34,8 @@ impl BlockRegistry {
             blocks: HashMap::new(),
             name_to_id: HashMap::new(),
             registrations: Vec::new(),
+            tick_handlers: HashMap::new(),
+            neighbor_handlers: HashMap::new(),
             next_engine_id: 1, // 0 is reserved for AIR, engine blocks use 1-99
             next_game_id: 100, // Game blocks start at 100
         };
@@ -90,6 +98,11 @@ impl BlockRegistry {
         self.blocks.get(&id)
     }
 
+    /// Every known block, built-in and registered, with its properties
+    pub fn iter_properties(&self) -> impl Iterator<Item = (BlockId, &BlockProperties)> {
+        self.blocks.iter().map(|(id, properties)| (*id, properties))
+    }
+
     /// Get a block ID by name
     pub fn get_id(&self, name: &str) -> Option<BlockId> {
         self.name_to_id.get(name).copied()
@@ -104,4 +117,24 @@ impl BlockRegistry {
     pub fn is_registered(&self, id: BlockId) -> bool {
         self.blocks.contains_key(&id)
     }
+
+    /// Set the tick handler for a block type, replacing any previous one
+    pub fn register_tick_handler(&mut self, id: BlockId, handler: BlockTickHandler) {
+        self.tick_handlers.insert(id, handler);
+    }
+
+    /// Tick handler for a block type, if it has one
+    pub fn tick_handler(&self, id: BlockId) -> Option<BlockTickHandler> {
+        self.tick_handlers.get(&id).copied()
+    }
+
+    /// Set the `on_neighbor_changed` handler for a block type, replacing any previous one
+    pub fn register_neighbor_handler(&mut self, id: BlockId, handler: NeighborChangedHandler) {
+        self.neighbor_handlers.insert(id, handler);
+    }
+
+    /// Neighbour change handler for a block type, if it has one
+    pub fn neighbor_handler(&self, id: BlockId) -> Option<NeighborChangedHandler> {
+        self.neighbor_handlers.get(&id).copied()
+    }
 }
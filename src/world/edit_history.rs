@@ -0,0 +1,207 @@
+//! Edit history - Undo/redo for block modifications
+//!
+//! Each undo step stores both sides of every voxel it changed, so undoing
+//! writes the old blocks back and redoing writes the new ones again. A bulk
+//! edit such as a fill is recorded as one step, not one per voxel. Writes go
+//! through a caller-supplied setter, so the history works with any world
+//! backend, e.g. `|pos, block| world.set_block(pos, block)`.
+
+use crate::world::core::{BlockId, VoxelPos};
+use std::collections::VecDeque;
+
+/// Default bound on undo steps
+pub const DEFAULT_MAX_UNDO_STEPS: usize = 100;
+
+/// One voxel edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockChange {
+    pub position: VoxelPos,
+    /// Block before the edit
+    pub old_block: BlockId,
+    /// Block after the edit
+    pub new_block: BlockId,
+}
+
+/// Edits undone and redone together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditStep {
+    /// Changes in the order they were applied
+    pub changes: Vec<BlockChange>,
+}
+
+/// Bounded undo/redo stacks
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    /// Oldest step at the front
+    pub undo_steps: VecDeque<EditStep>,
+    /// Most recently undone step at the back
+    pub redo_steps: Vec<EditStep>,
+    /// The oldest steps are dropped beyond this
+    pub max_steps: usize,
+}
+
+/// Create an empty history keeping at most `max_steps` undo steps
+pub fn create_edit_history(max_steps: usize) -> EditHistory {
+    EditHistory {
+        undo_steps: VecDeque::new(),
+        redo_steps: Vec::new(),
+        max_steps,
+    }
+}
+
+/// Record a single `set_block` as its own undo step
+pub fn record_block_change(
+    history: &mut EditHistory,
+    position: VoxelPos,
+    old_block: BlockId,
+    new_block: BlockId,
+) {
+    record_edit_step(
+        history,
+        vec![BlockChange {
+            position,
+            old_block,
+            new_block,
+        }],
+    );
+}
+
+/// Record a bulk edit as one undo step
+///
+/// Changes that leave a voxel as it was are dropped, and an edit that changed
+/// nothing is not recorded. A new edit discards everything that could be redone.
+pub fn record_edit_step(history: &mut EditHistory, changes: Vec<BlockChange>) {
+    let changes: Vec<BlockChange> = changes
+        .into_iter()
+        .filter(|change| change.old_block != change.new_block)
+        .collect();
+    if changes.is_empty() || history.max_steps == 0 {
+        return;
+    }
+
+    history.redo_steps.clear();
+    history.undo_steps.push_back(EditStep { changes });
+    while history.undo_steps.len() > history.max_steps {
+        history.undo_steps.pop_front();
+    }
+}
+
+/// Undo the newest step, writing its old blocks back in reverse order
+///
+/// Returns `Ok(false)` when there is nothing to undo. If a write fails the
+/// step stays on the undo stack; retrying rewrites the voxels already restored,
+/// which is harmless.
+pub fn undo_edit<E>(
+    history: &mut EditHistory,
+    mut set_block: impl FnMut(VoxelPos, BlockId) -> Result<(), E>,
+) -> Result<bool, E> {
+    let Some(step) = history.undo_steps.pop_back() else {
+        return Ok(false);
+    };
+
+    for change in step.changes.iter().rev() {
+        if let Err(e) = set_block(change.position, change.old_block) {
+            history.undo_steps.push_back(step);
+            return Err(e);
+        }
+    }
+
+    history.redo_steps.push(step);
+    Ok(true)
+}
+
+/// Redo the most recently undone step, writing its new blocks in order
+///
+/// Returns `Ok(false)` when there is nothing to redo. A failed write leaves
+/// the step on the redo stack.
+pub fn redo_edit<E>(
+    history: &mut EditHistory,
+    mut set_block: impl FnMut(VoxelPos, BlockId) -> Result<(), E>,
+) -> Result<bool, E> {
+    let Some(step) = history.redo_steps.pop() else {
+        return Ok(false);
+    };
+
+    for change in &step.changes {
+        if let Err(e) = set_block(change.position, change.new_block) {
+            history.redo_steps.push(step);
+            return Err(e);
+        }
+    }
+
+    history.undo_steps.push_back(step);
+    if history.undo_steps.len() > history.max_steps {
+        history.undo_steps.pop_front();
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn write(
+        world: &mut HashMap<VoxelPos, BlockId>,
+    ) -> impl FnMut(VoxelPos, BlockId) -> Result<(), String> + '_ {
+        |pos, block| {
+            world.insert(pos, block);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_undo_restores_broken_block_and_redo_breaks_it_again() {
+        let pos = VoxelPos::new(3, 10, -2);
+        let mut world = HashMap::from([(pos, BlockId::STONE)]);
+        let mut history = create_edit_history(DEFAULT_MAX_UNDO_STEPS);
+
+        // Break the block
+        world.insert(pos, BlockId::AIR);
+        record_block_change(&mut history, pos, BlockId::STONE, BlockId::AIR);
+
+        assert_eq!(undo_edit(&mut history, write(&mut world)), Ok(true));
+        assert_eq!(world[&pos], BlockId::STONE);
+
+        assert_eq!(redo_edit(&mut history, write(&mut world)), Ok(true));
+        assert_eq!(world[&pos], BlockId::AIR);
+
+        assert_eq!(redo_edit(&mut history, write(&mut world)), Ok(false));
+    }
+
+    #[test]
+    fn test_fill_is_one_step_and_history_is_bounded() {
+        let mut world = HashMap::new();
+        let mut history = create_edit_history(2);
+
+        let fill: Vec<BlockChange> = (0..1000)
+            .map(|i| BlockChange {
+                position: VoxelPos::new(i % 10, i / 100, (i / 10) % 10),
+                old_block: BlockId::AIR,
+                new_block: BlockId::SAND,
+            })
+            .collect();
+        record_edit_step(&mut history, fill);
+        assert_eq!(history.undo_steps.len(), 1);
+
+        record_block_change(
+            &mut history,
+            VoxelPos::new(0, 20, 0),
+            BlockId::AIR,
+            BlockId::DIRT,
+        );
+        record_block_change(
+            &mut history,
+            VoxelPos::new(0, 21, 0),
+            BlockId::AIR,
+            BlockId::DIRT,
+        );
+
+        // The fill was the oldest step and has been dropped
+        assert_eq!(history.undo_steps.len(), 2);
+        assert!(undo_edit(&mut history, write(&mut world)).expect("undo"));
+        assert!(undo_edit(&mut history, write(&mut world)).expect("undo"));
+        assert!(!undo_edit(&mut history, write(&mut world)).expect("undo"));
+        assert_eq!(world.len(), 2);
+    }
+}
@@ -0,0 +1,205 @@
+//! Biome selection and blending for CPU terrain generation
+//!
+//! A low-frequency noise value picks one biome per column. Near the border
+//! between two biomes their height parameters are blended so the terrain
+//! slopes from one to the other instead of stepping.
+
+use crate::world::core::BlockId;
+use noise::{NoiseFn, Perlin};
+
+/// Horizontal frequency of the biome noise - roughly one biome per 300 voxels
+const BIOME_NOISE_SCALE: f64 = 0.003;
+
+/// Perlin rarely leaves [-0.7, 0.7]; stretch it so outer biomes are not starved
+const BIOME_NOISE_GAIN: f64 = 1.4;
+
+/// Half-width of the blend zone on each side of a border, in biome bands
+const BIOME_BLEND_HALF_WIDTH: f64 = 0.25;
+
+/// Terrain shape and surface blocks of one biome
+#[derive(Debug, Clone, PartialEq)]
+pub struct BiomeDefinition {
+    pub name: String,
+    /// Voxels added to the base surface height
+    pub height_offset: f32,
+    /// Multiplier on the base surface wave (0 = flat)
+    pub height_scale: f32,
+    /// Top block of each column
+    pub surface_block: BlockId,
+    /// Block under the surface before stone starts
+    pub filler_block: BlockId,
+    pub filler_depth: u32,
+}
+
+impl BiomeDefinition {
+    pub fn plains() -> Self {
+        Self {
+            name: "plains".to_string(),
+            height_offset: 0.0,
+            height_scale: 0.5,
+            surface_block: BlockId::GRASS,
+            filler_block: BlockId::DIRT,
+            filler_depth: 3,
+        }
+    }
+
+    pub fn desert() -> Self {
+        Self {
+            name: "desert".to_string(),
+            height_offset: -2.0,
+            height_scale: 0.3,
+            surface_block: BlockId::SAND,
+            filler_block: BlockId::SANDSTONE,
+            filler_depth: 4,
+        }
+    }
+
+    pub fn mountains() -> Self {
+        Self {
+            name: "mountains".to_string(),
+            height_offset: 20.0,
+            height_scale: 4.0,
+            surface_block: BlockId::STONE,
+            filler_block: BlockId::STONE,
+            filler_depth: 0,
+        }
+    }
+}
+
+/// The built-in biome set, ordered so neighbouring bands blend sensibly
+pub fn default_biomes() -> Vec<BiomeDefinition> {
+    vec![
+        BiomeDefinition::desert(),
+        BiomeDefinition::plains(),
+        BiomeDefinition::mountains(),
+    ]
+}
+
+/// Blended biome parameters for one column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiomeColumn {
+    /// Index of the dominant biome; supplies the column's blocks
+    pub biome: usize,
+    pub height_offset: f32,
+    pub height_scale: f32,
+}
+
+impl BiomeColumn {
+    /// Surface Y after applying the biome to a base surface around `threshold`
+    pub fn surface_y(&self, base_surface_y: i32, threshold: i32) -> i32 {
+        let wave = (base_surface_y - threshold) as f32;
+        threshold + (self.height_offset + wave * self.height_scale).round() as i32
+    }
+}
+
+/// Per-column biome lookup
+///
+/// Biomes occupy equal bands of the noise range in list order, so only
+/// adjacent entries ever blend.
+pub struct BiomeMap {
+    noise: Perlin,
+    biomes: Vec<BiomeDefinition>,
+}
+
+impl BiomeMap {
+    pub fn new(seed: u32, biomes: Vec<BiomeDefinition>) -> Self {
+        let noise = Perlin::new(seed.wrapping_add(300)); // Different seed for biomes
+        Self { noise, biomes }
+    }
+
+    pub fn biomes(&self) -> &[BiomeDefinition] {
+        &self.biomes
+    }
+
+    /// Biome noise mapped to `[0, biome_count]`
+    fn band_position(&self, world_x: i32, world_z: i32) -> f64 {
+        let value = self.noise.get([
+            world_x as f64 * BIOME_NOISE_SCALE,
+            world_z as f64 * BIOME_NOISE_SCALE,
+        ]);
+        let unit = ((value * BIOME_NOISE_GAIN + 1.0) * 0.5).clamp(0.0, 1.0);
+        unit * self.biomes.len() as f64
+    }
+
+    /// Blended parameters at a column, or `None` when no biomes are defined
+    pub fn column(&self, world_x: i32, world_z: i32) -> Option<BiomeColumn> {
+        let last = self.biomes.len().checked_sub(1)?;
+        let position = self.band_position(world_x, world_z);
+        let band = (position.floor() as usize).min(last);
+
+        // Signed distance from the band centre, in bands (-0.5..0.5)
+        let offset = position - band as f64 - 0.5;
+        let neighbour = if offset >= 0.0 {
+            (band < last).then_some(band + 1)
+        } else {
+            band.checked_sub(1)
+        };
+
+        // Neighbour weight ramps from 0 inside the band to 0.5 at the border,
+        // where the neighbour's own ramp takes over - continuous across it
+        let edge_distance = 0.5 - offset.abs();
+        let neighbour_weight = match neighbour {
+            Some(_) if edge_distance < BIOME_BLEND_HALF_WIDTH => {
+                0.5 * (1.0 - edge_distance / BIOME_BLEND_HALF_WIDTH)
+            }
+            _ => 0.0,
+        } as f32;
+
+        let own = &self.biomes[band];
+        let other = &self.biomes[neighbour.unwrap_or(band)];
+        let blend = |a: f32, b: f32| a + (b - a) * neighbour_weight;
+
+        Some(BiomeColumn {
+            biome: band,
+            height_offset: blend(own.height_offset, other.height_offset),
+            height_scale: blend(own.height_scale, other.height_scale),
+        })
+    }
+
+    /// Dominant biome at a column
+    pub fn biome_at(&self, world_x: i32, world_z: i32) -> Option<&BiomeDefinition> {
+        self.column(world_x, world_z)
+            .map(|column| &self.biomes[column.biome])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_biome_borders_blend_without_seams() {
+        let map = BiomeMap::new(12345, default_biomes());
+        let mut seen = vec![false; map.biomes().len()];
+        let mut previous = map.column(0, 0).expect("biomes defined");
+
+        for x in 1..40_000 {
+            let column = map.column(x, 0).expect("biomes defined");
+            seen[column.biome] = true;
+
+            // Mountains sit 20 voxels above plains; without blending the
+            // offset would jump by that much at the border
+            assert!(
+                (column.height_offset - previous.height_offset).abs() < 1.0,
+                "seam at x = {}: {:?} -> {:?}",
+                x,
+                previous,
+                column
+            );
+            previous = column;
+        }
+
+        assert!(
+            seen.iter().all(|s| *s),
+            "every biome should appear: {:?}",
+            seen
+        );
+    }
+
+    #[test]
+    fn test_empty_biome_list_has_no_columns() {
+        let map = BiomeMap::new(1, Vec::new());
+        assert!(map.column(10, 10).is_none());
+        assert!(map.biome_at(10, 10).is_none());
+    }
+}
@@ -0,0 +1,61 @@
+//! Headless CPU world generation
+//!
+//! Runs the staged terrain -> caves -> ores passes with no wgpu device, for
+//! integration tests and dedicated servers. Output matches
+//! `generate_chunk_blocks` for the same config.
+
+use super::stages::{chunk_blocks_to_temp_chunk, column_surface_y, generate_chunk_blocks};
+use super::unified_generator::GeneratorConfig;
+use super::{BiomeMap, DensityField, WorldGenerator};
+use crate::world::core::ChunkPos;
+use crate::world::storage::TempChunk;
+
+/// Highest Y searched for solid ground in density mode
+const DENSITY_SCAN_TOP: i32 = 255;
+
+/// CPU-only generator running the staged generation passes
+pub struct CpuWorldGenerator {
+    config: GeneratorConfig,
+    /// Samplers for surface height queries; generation builds its own
+    biomes: BiomeMap,
+    density: DensityField,
+}
+
+impl CpuWorldGenerator {
+    pub fn new(config: GeneratorConfig) -> Self {
+        let seed = config.terrain_params.seed;
+        let biomes = BiomeMap::new(seed, config.biomes.clone());
+        let density = DensityField::new(seed, config.terrain_params.density_bias);
+        Self {
+            config,
+            biomes,
+            density,
+        }
+    }
+
+    pub fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+}
+
+impl WorldGenerator for CpuWorldGenerator {
+    fn generate_chunk(&self, chunk_pos: ChunkPos, chunk_size: u32) -> TempChunk {
+        chunk_blocks_to_temp_chunk(&generate_chunk_blocks(&self.config, chunk_pos, chunk_size))
+    }
+
+    fn get_surface_height(&self, world_x: f64, world_z: f64) -> i32 {
+        let (x, z) = (world_x.floor() as i32, world_z.floor() as i32);
+
+        // Same surface the terrain pass uses; density mode has none, so scan
+        column_surface_y(&self.config, &self.biomes, x, z).unwrap_or_else(|| {
+            (0..=DENSITY_SCAN_TOP)
+                .rev()
+                .find(|&y| self.density.is_solid(x, y, z))
+                .unwrap_or(0)
+        })
+    }
+
+    fn is_gpu(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,89 @@
+//! 3D density terrain for overhangs and floating islands
+//!
+//! Instead of a heightmap, every voxel samples 3D noise plus a height bias;
+//! positive density is solid. The value depends only on world coordinates,
+//! so neighbouring chunks agree voxel for voxel along their shared faces.
+
+use crate::constants::terrain::TERRAIN_THRESHOLD;
+use noise::{NoiseFn, Perlin};
+
+/// Frequency of the density noise - features span roughly 30 voxels
+const DENSITY_NOISE_SCALE: f64 = 0.03;
+
+/// Height bias added to the density noise
+///
+/// Linear in height: `+1` at `center_y - falloff`, `0` at `center_y` and
+/// `-1` at `center_y + falloff`. Noise only wins against the bias inside that
+/// band, which is where overhangs and floating islands form; below it is
+/// always solid and above it always air.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DensityBias {
+    pub center_y: f32,
+    /// Voxels over which the bias moves by 1 (must be positive)
+    pub falloff: f32,
+}
+
+impl Default for DensityBias {
+    fn default() -> Self {
+        Self {
+            center_y: TERRAIN_THRESHOLD as f32,
+            falloff: 24.0,
+        }
+    }
+}
+
+impl DensityBias {
+    pub fn at(&self, world_y: i32) -> f64 {
+        (self.center_y as f64 - world_y as f64) / self.falloff.max(f32::EPSILON) as f64
+    }
+}
+
+/// Per-voxel density sampler
+pub struct DensityField {
+    noise: Perlin,
+    bias: DensityBias,
+}
+
+impl DensityField {
+    pub fn new(seed: u32, bias: DensityBias) -> Self {
+        let noise = Perlin::new(seed.wrapping_add(400)); // Different seed for density
+        Self { noise, bias }
+    }
+
+    pub fn density(&self, world_x: i32, world_y: i32, world_z: i32) -> f64 {
+        let noise = self.noise.get([
+            world_x as f64 * DENSITY_NOISE_SCALE,
+            world_y as f64 * DENSITY_NOISE_SCALE,
+            world_z as f64 * DENSITY_NOISE_SCALE,
+        ]);
+        noise + self.bias.at(world_y)
+    }
+
+    pub fn is_solid(&self, world_x: i32, world_y: i32, world_z: i32) -> bool {
+        self.density(world_x, world_y, world_z) > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density_produces_overhangs() {
+        let field = DensityField::new(12345, DensityBias::default());
+        let band = TERRAIN_THRESHOLD - 24..TERRAIN_THRESHOLD + 24;
+
+        // An overhang is solid directly above air in the same column
+        let overhangs = (0..64)
+            .flat_map(|x| (0..64).map(move |z| (x, z)))
+            .filter(|&(x, z)| {
+                band.clone()
+                    .any(|y| !field.is_solid(x, y, z) && field.is_solid(x, y + 1, z))
+            })
+            .count();
+        assert!(overhangs > 0);
+
+        assert!(field.is_solid(0, TERRAIN_THRESHOLD - 100, 0));
+        assert!(!field.is_solid(0, TERRAIN_THRESHOLD + 100, 0));
+    }
+}
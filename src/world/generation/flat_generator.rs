@@ -0,0 +1,84 @@
+//! Flat world generation for tests and creative servers
+//!
+//! Fills everything below a fixed Y with one block and leaves air above.
+//! No noise and no GPU, so the output depends only on the chunk position.
+
+use super::WorldGenerator;
+use crate::world::core::{BlockId, ChunkPos};
+use crate::world::storage::TempChunk;
+
+/// CPU-only generator producing a flat world
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlatWorldGenerator {
+    /// First air layer; every voxel with `y < ground_level` is `block`
+    pub ground_level: i32,
+    pub block: BlockId,
+}
+
+impl FlatWorldGenerator {
+    pub fn new(ground_level: i32, block: BlockId) -> Self {
+        Self {
+            ground_level,
+            block,
+        }
+    }
+}
+
+impl WorldGenerator for FlatWorldGenerator {
+    fn generate_chunk(&self, chunk_pos: ChunkPos, chunk_size: u32) -> TempChunk {
+        let mut chunk = TempChunk::new_empty(chunk_pos, chunk_size);
+        if self.block == BlockId::AIR {
+            return chunk;
+        }
+
+        // Number of local layers below ground level in this chunk
+        let base_y = chunk_pos.y * chunk_size as i32;
+        let solid_layers = (self.ground_level - base_y).clamp(0, chunk_size as i32) as u32;
+
+        for y in 0..solid_layers {
+            for z in 0..chunk_size {
+                for x in 0..chunk_size {
+                    chunk.set_block(x, y, z, self.block);
+                }
+            }
+        }
+
+        chunk
+    }
+
+    fn get_surface_height(&self, _world_x: f64, _world_z: f64) -> i32 {
+        self.ground_level - 1
+    }
+
+    fn is_gpu(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_chunk_splits_at_ground_level() {
+        let generator = FlatWorldGenerator::new(20, BlockId::STONE);
+        let solid = |chunk: &TempChunk| {
+            chunk
+                .blocks()
+                .iter()
+                .filter(|b| **b == BlockId::STONE)
+                .count()
+        };
+
+        // Chunk spans y = 16..32, so 4 of its 16 layers are solid
+        let chunk = generator.generate_chunk(ChunkPos::new(0, 1, 0), 16);
+        assert_eq!(solid(&chunk), 4 * 16 * 16);
+
+        let below = generator.generate_chunk(ChunkPos::new(-2, 0, 5), 16);
+        assert_eq!(solid(&below), 16 * 16 * 16);
+
+        let above = generator.generate_chunk(ChunkPos::new(0, 2, 0), 16);
+        assert_eq!(solid(&above), 0);
+        assert_eq!(generator.get_surface_height(123.0, -9.0), 19);
+    }
+}
@@ -1,10 +1,14 @@
 //! GPU world generator wrapper that implements the WorldGenerator trait
 
+use crate::constants::core::CHUNK_SIZE;
 use crate::gpu::{GpuError, GpuErrorRecovery, GpuRecoveryError};
 use crate::world::{
     core::{BlockId, ChunkPos},
-    generation::{TerrainGeneratorSOA, WorldGenerator},
-    storage::{TempChunk, WorldBuffer},
+    generation::{
+        stages::{chunk_blocks_to_temp_chunk, create_chunk_blocks, run_terrain_stage},
+        BlockIds, GeneratedChunk, GenerationStages, TerrainGeneratorSOA, WorldGenerator,
+    },
+    storage::{TempChunk, VoxelData, WorldBuffer},
 };
 use std::sync::{Arc, Mutex};
 
@@ -17,8 +21,10 @@ use std::sync::{Arc, Mutex};
 pub struct GpuWorldGenerator {
     terrain_generator: Arc<TerrainGeneratorSOA>,
     device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
     world_buffer: Arc<Mutex<WorldBuffer>>,
     error_recovery: Arc<GpuErrorRecovery>,
+    stages: GenerationStages,
 }
 
 impl GpuWorldGenerator {
@@ -28,14 +34,17 @@ impl GpuWorldGenerator {
         device: Arc<wgpu::Device>,
         queue: Arc<wgpu::Queue>,
         world_buffer: Arc<Mutex<WorldBuffer>>,
+        stages: GenerationStages,
     ) -> Self {
-        let error_recovery = Arc::new(GpuErrorRecovery::new(device.clone(), queue));
+        let error_recovery = Arc::new(GpuErrorRecovery::new(device.clone(), queue.clone()));
 
         Self {
             terrain_generator,
             device,
+            queue,
             world_buffer,
             error_recovery,
+            stages,
         }
     }
 
@@ -51,6 +60,12 @@ impl GpuWorldGenerator {
             return Err(GpuError::DeviceLost);
         }
 
+        // Terrain pass disabled - nothing to dispatch, chunks stay empty
+        if !self.stages.terrain {
+            log::debug!("Terrain stage disabled, skipping GPU dispatch for {} chunks", chunk_positions.len());
+            return Ok(());
+        }
+
         // Validate encoder before use
         if let Err(e) = self.error_recovery.validate_encoder(encoder) {
             log::error!("Command encoder is invalid: {:?}", e);
@@ -86,58 +101,53 @@ impl GpuWorldGenerator {
     }
 
     /// Generate a chunk using CPU fallback with proper terrain logic
+    ///
+    /// Runs the CPU terrain stage, which shares `terrain_surface_y` with the
+    /// shader, so fallback chunks match GPU ones for the same position.
     fn generate_cpu_fallback(&self, chunk_pos: ChunkPos, chunk_size: u32) -> TempChunk {
-        use crate::world::core::{BlockId, VoxelPos};
-        
-        let mut chunk = TempChunk::new_empty(chunk_pos, chunk_size);
-        
-        // Use the same terrain generation logic as in the GPU shader
-        // TERRAIN_THRESHOLD = 64
-        const TERRAIN_THRESHOLD: i32 = 64;
-        
-        let world_x_base = chunk_pos.x * chunk_size as i32;
-        let world_y_base = chunk_pos.y * chunk_size as i32;
-        let world_z_base = chunk_pos.z * chunk_size as i32;
-        
-        for x in 0..chunk_size {
-            for z in 0..chunk_size {
-                let world_x = world_x_base + x as i32;
-                let world_z = world_z_base + z as i32;
-                
-                // Calculate terrain height with variation (matching GPU shader)
-                let height_variation = (world_x as f32 * 0.05).sin() * 5.0 + (world_z as f32 * 0.05).cos() * 5.0;
-                let surface_height = TERRAIN_THRESHOLD as f32 + height_variation;
-                
-                for y in 0..chunk_size {
-                    let world_y = world_y_base + y as i32;
-                    
-                    let block_id = if world_y < surface_height as i32 - 3 {
-                        // Deep underground: stone
-                        BlockId(1) // BLOCK_STONE
-                    } else if world_y < surface_height as i32 {
-                        // Just below surface: stone with occasional air (caves)
-                        let cave_noise_val = ((world_x + world_y * 7 + world_z * 13) % 100) as f32 / 100.0;
-                        if cave_noise_val > 0.85 && world_y < surface_height as i32 - 5 {
-                            BlockId(0) // BLOCK_AIR - cave
-                        } else {
-                            BlockId(1) // BLOCK_STONE
-                        }
-                    } else if world_y <= surface_height as i32 {
-                        // Surface layer: grass
-                        BlockId(3) // BLOCK_GRASS
-                    } else {
-                        // Above surface: air
-                        BlockId(0) // BLOCK_AIR
-                    };
-                    
-                    chunk.set_block(x, y, z, block_id);
-                }
-            }
+        let mut blocks = create_chunk_blocks(chunk_pos, chunk_size);
+
+        // Terrain pass disabled - later passes have nothing to carve or replace
+        if self.stages.terrain {
+            run_terrain_stage(&mut blocks, &BlockIds::default());
+        }
+
+        log::info!("CPU fallback generated terrain chunk {:?}", chunk_pos);
+        chunk_blocks_to_temp_chunk(&blocks)
+    }
+
+    /// CPU fallback for a whole batch, without touching the GPU
+    fn generate_cpu_batch(&self, positions: &[ChunkPos], chunk_size: u32) -> Vec<GeneratedChunk> {
+        positions
+            .iter()
+            .map(|&chunk_pos| GeneratedChunk {
+                chunk_pos,
+                chunk: self.generate_cpu_fallback(chunk_pos, chunk_size),
+            })
+            .collect()
+    }
+}
+
+/// Chunk from voxels read back from the WorldBuffer
+///
+/// Voxels are laid out `x + y * CHUNK_SIZE + z * CHUNK_SIZE^2`, as the
+/// terrain kernel writes them.
+fn voxels_to_temp_chunk(chunk_pos: ChunkPos, voxels: &[VoxelData]) -> TempChunk {
+    let mut temp = TempChunk::new_empty(chunk_pos, CHUNK_SIZE);
+    for (index, voxel) in voxels.iter().enumerate() {
+        let block = BlockId(voxel.block_id());
+        if block == BlockId::AIR {
+            continue;
+        }
+        let index = index as u32;
+        let x = index % CHUNK_SIZE;
+        let y = (index / CHUNK_SIZE) % CHUNK_SIZE;
+        let z = index / (CHUNK_SIZE * CHUNK_SIZE);
+        if z < CHUNK_SIZE {
+            temp.set_block(x, y, z, block);
         }
-        
-        log::info!("CPU fallback generated terrain chunk {:?} with surface at ~{}", chunk_pos, TERRAIN_THRESHOLD);
-        chunk
     }
+    temp
 }
 
 impl WorldGenerator for GpuWorldGenerator {
@@ -172,6 +182,69 @@ impl WorldGenerator for GpuWorldGenerator {
         }
     }
 
+    fn generate_chunks_batch(
+        &self,
+        positions: &[ChunkPos],
+        chunk_size: u32,
+    ) -> Vec<GeneratedChunk> {
+        if positions.is_empty() {
+            return Vec::new();
+        }
+
+        // The kernel only writes full-size chunks, and without a terrain
+        // pass or a readback buffer there is nothing to bring back, so the
+        // dispatch would only duplicate the CPU work
+        let readback_enabled = match self.world_buffer.lock() {
+            Ok(world_buffer) => world_buffer.readback_enabled(),
+            Err(_) => false,
+        };
+        if chunk_size != CHUNK_SIZE || !self.stages.terrain || !readback_enabled {
+            return self.generate_cpu_batch(positions, chunk_size);
+        }
+
+        // One compute pass covers the whole batch in the WorldBuffer
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("GPU terrain generation batch"),
+            });
+        if let Err(e) = self.generate_chunks_with_encoder(positions, &mut encoder) {
+            log::error!(
+                "GPU batch generation of {} chunks failed: {:?}, using CPU fallback",
+                positions.len(),
+                e
+            );
+            return self.generate_cpu_batch(positions, chunk_size);
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        // Read each chunk back; read_chunk waits for the dispatch to finish
+        let mut world_buffer = match self.world_buffer.lock() {
+            Ok(world_buffer) => world_buffer,
+            Err(_) => {
+                log::error!("WorldBuffer lock poisoned, using CPU fallback for batch");
+                return self.generate_cpu_batch(positions, chunk_size);
+            }
+        };
+        positions
+            .iter()
+            .map(|&chunk_pos| {
+                let chunk = match world_buffer.read_chunk(&self.device, &self.queue, chunk_pos) {
+                    Ok(voxels) => voxels_to_temp_chunk(chunk_pos, &voxels),
+                    Err(e) => {
+                        log::error!(
+                            "Readback of GPU chunk {:?} failed: {}, using CPU fallback",
+                            chunk_pos,
+                            e
+                        );
+                        self.generate_cpu_fallback(chunk_pos, chunk_size)
+                    }
+                };
+                GeneratedChunk { chunk_pos, chunk }
+            })
+            .collect()
+    }
+
     fn get_surface_height(&self, world_x: f64, world_z: f64) -> i32 {
         // Use the constant from the root constants.rs file
         use crate::constants::terrain::SEA_LEVEL;
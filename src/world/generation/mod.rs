@@ -5,9 +5,15 @@
 
 use crate::constants::terrain::SEA_LEVEL;
 
+mod biomes;
 mod caves;
+mod cpu_generator;
+mod density;
+mod flat_generator;
 mod gpu_world_generator;
 mod ores;
+mod stages;
+mod structures;
 mod terrain_gpu;
 mod unified_generator;
 
@@ -15,13 +21,27 @@ mod unified_generator;
 pub use gpu_world_generator::GpuWorldGenerator;
 pub use terrain_gpu::{TerrainGeneratorSOA, TerrainGeneratorSOABuilder};
 
+// CPU-only generation (tests, creative and dedicated servers)
+pub use cpu_generator::CpuWorldGenerator;
+pub use flat_generator::FlatWorldGenerator;
+
 // Supporting generators (these should also be GPU-based eventually)
+pub use biomes::{default_biomes, BiomeColumn, BiomeDefinition, BiomeMap};
 pub use caves::CaveGenerator;
-pub use ores::OreGenerator;
+pub use density::{DensityBias, DensityField};
+pub use ores::{default_ore_distributions, OreDistribution, OreGenerator};
+pub use structures::{StructurePlacer, StructureRule, StructureTemplate};
+
+// Staged CPU generation (terrain -> caves -> ores -> structures)
+pub use stages::{
+    block_index, chunk_blocks_to_temp_chunk, column_surface_y, generate_chunk_blocks,
+    generate_chunk_deterministic, hash_chunk_blocks, terrain_surface_y, terrain_wave_milli,
+    ChunkBlocks, GenerationStages,
+};
 
 // Unified generation interface
 pub use unified_generator::{
-    BlockIds, GeneratorConfig, GeneratorError, UnifiedGenerator, WorldGenerator,
+    BlockIds, GeneratedChunk, GeneratorConfig, GeneratorError, UnifiedGenerator, WorldGenerator,
 };
 
 /// Create a GPU-based generator
@@ -33,6 +53,11 @@ pub async fn create_unified_generator(
     UnifiedGenerator::new_gpu(device, buffer_manager, config).await
 }
 
+/// Create a CPU-only generator for headless use - no wgpu device required
+pub fn create_cpu_generator(config: GeneratorConfig) -> UnifiedGenerator {
+    UnifiedGenerator::new_cpu(config)
+}
+
 /// Terrain generation parameters that work across CPU and GPU backends
 #[derive(Debug, Clone, Copy)]
 pub struct TerrainParams {
@@ -44,6 +69,12 @@ pub struct TerrainParams {
     pub terrain_amplitude: f32,
     pub terrain_offset: f32,
     pub water_level: i32,
+    /// Shape terrain from 3D density instead of a heightmap
+    ///
+    /// CPU only; `UnifiedGenerator::new_gpu` uses the CPU backend when set.
+    pub use_3d_density: bool,
+    /// Height bias of the density field; only read with `use_3d_density`
+    pub density_bias: DensityBias,
 }
 
 impl Default for TerrainParams {
@@ -57,6 +88,8 @@ impl Default for TerrainParams {
             terrain_amplitude: 40.0,
             terrain_offset: SEA_LEVEL as f32, // Base terrain height at sea level
             water_level: SEA_LEVEL,           // Water level in voxels
+            use_3d_density: false,
+            density_bias: DensityBias::default(),
         }
     }
 }
@@ -72,4 +105,33 @@ mod tests {
         assert_eq!(params.sea_level, SEA_LEVEL as f32);
     }
 
+    #[test]
+    fn test_cpu_generator_runs_headless() {
+        let terrain_only = GeneratorConfig {
+            stages: GenerationStages::terrain_only(),
+            ..GeneratorConfig::default()
+        };
+        let with_biomes = GeneratorConfig {
+            biomes: default_biomes(),
+            ..terrain_only.clone()
+        };
+
+        for config in [terrain_only, with_biomes] {
+            let generator = create_cpu_generator(config.clone());
+            assert!(!generator.is_gpu());
+
+            // The reported surface is where the terrain pass put grass
+            let surface = generator.get_surface_height(5.5, -3.5);
+            let chunk_pos = crate::world::core::ChunkPos::new(0, surface.div_euclid(32), -1);
+            let local_y = surface.rem_euclid(32) as u32;
+            let blocks = generate_chunk_blocks(&config, chunk_pos, 32);
+            assert_eq!(
+                blocks.blocks[block_index(32, 5, local_y, 29)],
+                config.block_ids.grass
+            );
+
+            let chunk = generator.generate_chunk(chunk_pos, 32);
+            assert!(chunk.blocks().contains(&config.block_ids.grass));
+        }
+    }
 }
@@ -1,16 +1,169 @@
 use crate::BlockId;
 use noise::{NoiseFn, Perlin};
 
+/// Vein placement rule for one ore
+///
+/// Each chunk column starts `attempts_per_chunk` veins of `vein_size` connected
+/// blocks at a random height in `min_y..=max_y`. Veins never leave that band,
+/// so deep ores can be made rarer (fewer attempts) and larger (bigger veins)
+/// independently of shallow ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OreDistribution {
+    pub block: BlockId,
+    pub attempts_per_chunk: u32,
+    pub vein_size: u32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+/// Built-in ores: shallow ones common and small, deep ones rare and large
+pub fn default_ore_distributions() -> Vec<OreDistribution> {
+    vec![
+        OreDistribution {
+            block: BlockId::COAL_ORE,
+            attempts_per_chunk: 20,
+            vein_size: 8,
+            min_y: 0,
+            max_y: 128,
+        },
+        OreDistribution {
+            block: BlockId::IRON_ORE,
+            attempts_per_chunk: 10,
+            vein_size: 6,
+            min_y: 0,
+            max_y: 64,
+        },
+        OreDistribution {
+            block: BlockId::GOLD_ORE,
+            attempts_per_chunk: 2,
+            vein_size: 8,
+            min_y: 0,
+            max_y: 32,
+        },
+        OreDistribution {
+            block: BlockId::DIAMOND_ORE,
+            attempts_per_chunk: 1,
+            vein_size: 10,
+            min_y: 0,
+            max_y: 16,
+        },
+    ]
+}
+
+/// The six face neighbours a vein can grow into
+const VEIN_DIRECTIONS: [[i32; 3]; 6] = [
+    [1, 0, 0],
+    [-1, 0, 0],
+    [0, 1, 0],
+    [0, -1, 0],
+    [0, 0, 1],
+    [0, 0, -1],
+];
+
+/// SplitMix64 finalizer - portable, unlike `rand`'s unspecified std RNGs
+pub(super) fn mix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Deterministic random stream for one chunk column and ore
+struct VeinRng(u64);
+
+impl VeinRng {
+    fn new(seed: u32, column_x: i32, column_z: i32, ore_index: usize) -> Self {
+        let mut state = seed as u64;
+        for value in [
+            column_x as u32 as u64,
+            column_z as u32 as u64,
+            ore_index as u64,
+        ] {
+            state = mix64(state ^ value);
+        }
+        Self(state)
+    }
+
+    /// Uniform value in `0..bound` (`bound` > 0)
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 = mix64(self.0);
+        self.0 % bound
+    }
+}
+
 pub struct OreGenerator {
     ore_noise: Perlin,
     seed: u32,
+    distributions: Vec<OreDistribution>,
 }
 
 impl OreGenerator {
     pub fn new(seed: u32) -> Self {
+        Self::with_distributions(seed, default_ore_distributions())
+    }
+
+    pub fn with_distributions(seed: u32, distributions: Vec<OreDistribution>) -> Self {
         let ore_noise = Perlin::new(seed.wrapping_add(200)); // Different seed for ores
 
-        Self { ore_noise, seed }
+        Self {
+            ore_noise,
+            seed,
+            distributions,
+        }
+    }
+
+    pub fn distributions(&self) -> &[OreDistribution] {
+        &self.distributions
+    }
+
+    /// Every ore voxel of the veins started in one chunk column
+    ///
+    /// A vein grows from its start by attaching a face neighbour to a random
+    /// block already in the vein, so it is always connected. Veins may reach
+    /// into the adjacent columns; ones longer than a chunk are cut off there.
+    pub fn column_veins(
+        &self,
+        column_x: i32,
+        column_z: i32,
+        chunk_size: u32,
+    ) -> Vec<([i32; 3], BlockId)> {
+        let mut placed = Vec::new();
+        let size = chunk_size as i32;
+
+        for (ore_index, ore) in self.distributions.iter().enumerate() {
+            if ore.vein_size == 0 || ore.max_y < ore.min_y {
+                continue;
+            }
+            let mut rng = VeinRng::new(self.seed, column_x, column_z, ore_index);
+            let band_height = (ore.max_y - ore.min_y + 1) as u64;
+
+            for _ in 0..ore.attempts_per_chunk {
+                let start = [
+                    column_x * size + rng.below(chunk_size as u64) as i32,
+                    ore.min_y + rng.below(band_height) as i32,
+                    column_z * size + rng.below(chunk_size as u64) as i32,
+                ];
+                let mut vein = vec![start];
+
+                while vein.len() < ore.vein_size as usize {
+                    let base = vein[rng.below(vein.len() as u64) as usize];
+                    let first = rng.below(6) as usize;
+                    let next = (0..6)
+                        .map(|i| VEIN_DIRECTIONS[(first + i) % 6])
+                        .map(|d| [base[0] + d[0], base[1] + d[1], base[2] + d[2]])
+                        .find(|p| (ore.min_y..=ore.max_y).contains(&p[1]) && !vein.contains(p));
+                    match next {
+                        Some(position) => vein.push(position),
+                        // Boxed in at the band edge; a smaller vein is fine
+                        None => break,
+                    }
+                }
+
+                placed.extend(vein.into_iter().map(|position| (position, ore.block)));
+            }
+        }
+
+        placed
     }
 
     pub fn get_ore_at(
@@ -35,22 +188,22 @@ impl OreGenerator {
 
         // Coal - common, found at all depths below 128
         if world_y <= 128 && noise_value > 0.85 {
-            return BlockId(8); // Coal ore
+            return BlockId::COAL_ORE;
         }
 
         // Iron - less common, below 64
         if world_y <= 64 && noise_value > 0.9 {
-            return BlockId(9); // Iron ore
+            return BlockId::IRON_ORE;
         }
 
         // Gold - rare, below 32
         if world_y <= 32 && noise_value > 0.95 {
-            return BlockId(10); // Gold ore
+            return BlockId::GOLD_ORE;
         }
 
         // Diamond - very rare, below 16
         if world_y <= 16 && noise_value > 0.98 {
-            return BlockId(11); // Diamond ore
+            return BlockId::DIAMOND_ORE;
         }
 
         default_block
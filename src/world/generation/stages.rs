@@ -0,0 +1,597 @@
+//! Staged CPU chunk generation
+//!
+//! Generation runs as an ordered list of passes over a flat block array:
+//! terrain -> caves -> ores -> structures. Each pass is a pure kernel and can be switched
+//! off through `GenerationStages` to isolate bugs or benchmark a single pass.
+//! With `GeneratorConfig::biomes` set, the terrain pass shapes each column by
+//! its blended biome; with `TerrainParams::use_3d_density` it fills solid
+//! density instead. The GPU kernel reads neither yet.
+
+use super::unified_generator::{BlockIds, GeneratorConfig};
+use super::{BiomeMap, CaveGenerator, DensityField, OreGenerator, StructurePlacer};
+use crate::constants::core::CHUNK_SIZE;
+use crate::constants::terrain::{TERRAIN_THRESHOLD, TERRAIN_WAVE_AMPLITUDE, TERRAIN_WAVE_PERIOD};
+use crate::world::core::{BlockId, ChunkPos};
+use crate::world::storage::TempChunk;
+
+/// Per-stage toggles for chunk generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenerationStages {
+    /// Base terrain (stone body and grass surface). When disabled the chunk starts as air.
+    pub terrain: bool,
+    /// Cave carving
+    pub caves: bool,
+    /// Ore placement inside stone
+    pub ores: bool,
+    /// Structure templates stamped onto the surface
+    pub structures: bool,
+}
+
+impl Default for GenerationStages {
+    fn default() -> Self {
+        Self {
+            terrain: true,
+            caves: true,
+            ores: true,
+            structures: true,
+        }
+    }
+}
+
+impl GenerationStages {
+    /// Only the terrain pass - useful to isolate terrain bugs or benchmark it alone
+    pub fn terrain_only() -> Self {
+        Self {
+            terrain: true,
+            caves: false,
+            ores: false,
+            structures: false,
+        }
+    }
+}
+
+/// Flat block array for one chunk, indexed `x + y * size + z * size * size`
+/// (same layout as the GPU WorldBuffer)
+pub struct ChunkBlocks {
+    pub chunk_pos: ChunkPos,
+    pub size: u32,
+    pub blocks: Vec<BlockId>,
+}
+
+/// Create an all-air block array for a chunk
+pub fn create_chunk_blocks(chunk_pos: ChunkPos, size: u32) -> ChunkBlocks {
+    ChunkBlocks {
+        chunk_pos,
+        size,
+        blocks: vec![BlockId::AIR; (size * size * size) as usize],
+    }
+}
+
+/// Index of a local position inside a `ChunkBlocks` array
+#[inline]
+pub fn block_index(size: u32, x: u32, y: u32, z: u32) -> usize {
+    (x + y * size + z * size * size) as usize
+}
+
+/// Surface wave at `phase` in thousandths, `-1000..=1000`
+///
+/// Bhaskara I's rational sine approximation in pure integer math. Float
+/// `sin`/`cos` differ between CPU libm and GPU drivers, which moved the
+/// surface by a voxel wherever the height landed near an integer.
+/// `terrain_generation.wgsl` has a line-for-line copy; change both together.
+pub fn terrain_wave_milli(phase: i32) -> i32 {
+    let half_period = TERRAIN_WAVE_PERIOD / 2;
+    let t = phase.rem_euclid(TERRAIN_WAVE_PERIOD);
+    let (u, wave_sign) = if t < half_period {
+        (t, 1)
+    } else {
+        (t - half_period, -1)
+    };
+    let p = u * (half_period - u);
+    wave_sign * 16_000 * p / (5 * half_period * half_period - 4 * p)
+}
+
+/// Y of the grass block at a column - the terrain contract shared with the GPU shader
+pub fn terrain_surface_y(world_x: i32, world_z: i32) -> i32 {
+    // Quarter-period offset turns the z wave into a cosine
+    let quarter = TERRAIN_WAVE_PERIOD / 4;
+    let waves = terrain_wave_milli(world_x) + terrain_wave_milli(world_z.wrapping_add(quarter));
+    TERRAIN_THRESHOLD + (TERRAIN_WAVE_AMPLITUDE * waves).div_euclid(1000)
+}
+
+/// Terrain pass - fills stone below the surface and grass on it
+pub fn run_terrain_stage(chunk: &mut ChunkBlocks, block_ids: &BlockIds) {
+    let size = chunk.size;
+    let base_x = chunk.chunk_pos.x * size as i32;
+    let base_y = chunk.chunk_pos.y * size as i32;
+    let base_z = chunk.chunk_pos.z * size as i32;
+
+    for z in 0..size {
+        for x in 0..size {
+            let surface = terrain_surface_y(base_x + x as i32, base_z + z as i32);
+            for y in 0..size {
+                let world_y = base_y + y as i32;
+                let block = if world_y < surface {
+                    block_ids.stone
+                } else if world_y == surface {
+                    block_ids.grass
+                } else {
+                    continue;
+                };
+                chunk.blocks[block_index(size, x, y, z)] = block;
+            }
+        }
+    }
+}
+
+/// Terrain pass with biomes - blended surface height, blocks from the dominant biome
+pub fn run_biome_terrain_stage(chunk: &mut ChunkBlocks, biomes: &BiomeMap, block_ids: &BlockIds) {
+    let size = chunk.size;
+    let base_x = chunk.chunk_pos.x * size as i32;
+    let base_y = chunk.chunk_pos.y * size as i32;
+    let base_z = chunk.chunk_pos.z * size as i32;
+
+    for z in 0..size {
+        for x in 0..size {
+            let (world_x, world_z) = (base_x + x as i32, base_z + z as i32);
+            let Some(column) = biomes.column(world_x, world_z) else {
+                continue;
+            };
+            let biome = &biomes.biomes()[column.biome];
+            let surface = column.surface_y(terrain_surface_y(world_x, world_z), TERRAIN_THRESHOLD);
+            let filler_top = surface - biome.filler_depth as i32;
+
+            for y in 0..size {
+                let world_y = base_y + y as i32;
+                let block = if world_y < filler_top {
+                    block_ids.stone
+                } else if world_y < surface {
+                    biome.filler_block
+                } else if world_y == surface {
+                    biome.surface_block
+                } else {
+                    continue;
+                };
+                chunk.blocks[block_index(size, x, y, z)] = block;
+            }
+        }
+    }
+}
+
+/// Terrain pass in density mode - stone where the density field is solid,
+/// grass on solid voxels with air above
+///
+/// The voxel above is sampled from the field rather than the chunk, so the top
+/// layer gets grass even when the air is in the next chunk up.
+pub fn run_density_terrain_stage(
+    chunk: &mut ChunkBlocks,
+    field: &DensityField,
+    block_ids: &BlockIds,
+) {
+    let size = chunk.size;
+    let base_x = chunk.chunk_pos.x * size as i32;
+    let base_y = chunk.chunk_pos.y * size as i32;
+    let base_z = chunk.chunk_pos.z * size as i32;
+
+    for z in 0..size {
+        for x in 0..size {
+            let (world_x, world_z) = (base_x + x as i32, base_z + z as i32);
+            let mut solid = field.is_solid(world_x, base_y, world_z);
+            for y in 0..size {
+                let world_y = base_y + y as i32;
+                let solid_above = field.is_solid(world_x, world_y + 1, world_z);
+                if solid {
+                    chunk.blocks[block_index(size, x, y, z)] = if solid_above {
+                        block_ids.stone
+                    } else {
+                        block_ids.grass
+                    };
+                }
+                solid = solid_above;
+            }
+        }
+    }
+}
+
+/// Cave pass - carves air out of stone using the cave noise
+pub fn run_cave_stage(chunk: &mut ChunkBlocks, caves: &CaveGenerator, block_ids: &BlockIds) {
+    let size = chunk.size;
+    let base_x = chunk.chunk_pos.x * size as i32;
+    let base_y = chunk.chunk_pos.y * size as i32;
+    let base_z = chunk.chunk_pos.z * size as i32;
+
+    for z in 0..size {
+        for y in 0..size {
+            for x in 0..size {
+                let index = block_index(size, x, y, z);
+                if chunk.blocks[index] != block_ids.stone {
+                    continue;
+                }
+                if caves.is_cave(base_x + x as i32, base_y + y as i32, base_z + z as i32) {
+                    chunk.blocks[index] = block_ids.air;
+                }
+            }
+        }
+    }
+}
+
+/// Ore pass - replaces stone with the veins of this and the neighbouring
+/// chunk columns, so veins continue across chunk borders
+pub fn run_ore_stage(chunk: &mut ChunkBlocks, ores: &OreGenerator, block_ids: &BlockIds) {
+    let size = chunk.size as i32;
+    let base = [
+        chunk.chunk_pos.x * size,
+        chunk.chunk_pos.y * size,
+        chunk.chunk_pos.z * size,
+    ];
+
+    for dz in -1..=1 {
+        for dx in -1..=1 {
+            let veins =
+                ores.column_veins(chunk.chunk_pos.x + dx, chunk.chunk_pos.z + dz, chunk.size);
+            for (position, ore) in veins {
+                let local = [
+                    position[0] - base[0],
+                    position[1] - base[1],
+                    position[2] - base[2],
+                ];
+                if local.iter().any(|&v| v < 0 || v >= size) {
+                    continue;
+                }
+                let index = block_index(
+                    chunk.size,
+                    local[0] as u32,
+                    local[1] as u32,
+                    local[2] as u32,
+                );
+                if chunk.blocks[index] == block_ids.stone {
+                    chunk.blocks[index] = ore;
+                }
+            }
+        }
+    }
+}
+
+/// Top solid voxel of a world column as the terrain pass shapes it
+///
+/// `None` in density mode, where overhangs leave no single surface.
+pub fn column_surface_y(
+    config: &GeneratorConfig,
+    biomes: &BiomeMap,
+    x: i32,
+    z: i32,
+) -> Option<i32> {
+    if config.terrain_params.use_3d_density {
+        return None;
+    }
+    let base = terrain_surface_y(x, z);
+    Some(match biomes.column(x, z) {
+        Some(column) => column.surface_y(base, TERRAIN_THRESHOLD),
+        None => base,
+    })
+}
+
+/// Structure pass - stamps the structures of this and the neighbouring chunk
+/// columns into air, so structures continue across chunk borders
+pub fn run_structure_stage(
+    chunk: &mut ChunkBlocks,
+    placer: &StructurePlacer,
+    surface_y: &dyn Fn(i32, i32) -> Option<i32>,
+    block_ids: &BlockIds,
+) {
+    let size = chunk.size as i32;
+    let base = [
+        chunk.chunk_pos.x * size,
+        chunk.chunk_pos.y * size,
+        chunk.chunk_pos.z * size,
+    ];
+
+    // Placement columns are CHUNK_SIZE wide whatever size this chunk is
+    let reach = placer.horizontal_reach() as i32;
+    let column = |world: i32| world.div_euclid(CHUNK_SIZE as i32);
+    let (min_x, max_x) = (column(base[0] - reach), column(base[0] + size - 1 + reach));
+    let (min_z, max_z) = (column(base[2] - reach), column(base[2] + size - 1 + reach));
+
+    for column_z in min_z..=max_z {
+        for column_x in min_x..=max_x {
+            for (rule_index, anchor) in placer.column_placements(column_x, column_z, surface_y) {
+                let template = &placer.rules()[rule_index].template;
+                for (offset, block) in template.voxels() {
+                    let local = [0, 1, 2].map(|axis| anchor[axis] + offset[axis] - base[axis]);
+                    if local.iter().any(|&v| v < 0 || v >= size) {
+                        continue;
+                    }
+                    let index = block_index(
+                        chunk.size,
+                        local[0] as u32,
+                        local[1] as u32,
+                        local[2] as u32,
+                    );
+                    if chunk.blocks[index] == block_ids.air {
+                        chunk.blocks[index] = block;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Run every enabled stage for one chunk
+/// Disabled stages are skipped entirely, not computed and discarded.
+pub fn generate_chunk_blocks(
+    config: &GeneratorConfig,
+    chunk_pos: ChunkPos,
+    chunk_size: u32,
+) -> ChunkBlocks {
+    let mut chunk = create_chunk_blocks(chunk_pos, chunk_size);
+    let stages = config.stages;
+    let seed = config.terrain_params.seed;
+
+    if stages.terrain {
+        if config.terrain_params.use_3d_density {
+            let field = DensityField::new(seed, config.terrain_params.density_bias);
+            run_density_terrain_stage(&mut chunk, &field, &config.block_ids);
+        } else if config.biomes.is_empty() {
+            run_terrain_stage(&mut chunk, &config.block_ids);
+        } else {
+            let biomes = BiomeMap::new(seed, config.biomes.clone());
+            run_biome_terrain_stage(&mut chunk, &biomes, &config.block_ids);
+        }
+    }
+    if stages.caves {
+        run_cave_stage(&mut chunk, &CaveGenerator::new(seed), &config.block_ids);
+    }
+    if stages.ores {
+        let ores = OreGenerator::with_distributions(seed, config.ores.clone());
+        run_ore_stage(&mut chunk, &ores, &config.block_ids);
+    }
+    if stages.structures && !config.structures.is_empty() {
+        let placer = StructurePlacer::new(seed, config.structures.clone());
+        let biomes = BiomeMap::new(seed, config.biomes.clone());
+        let surface = |x, z| column_surface_y(config, &biomes, x, z);
+        run_structure_stage(&mut chunk, &placer, &surface, &config.block_ids);
+    }
+
+    chunk
+}
+
+/// Generate a chunk synchronously from only its position and a seed
+///
+/// Runs every enabled stage on the CPU with no GPU or async involvement, so the
+/// output depends on nothing but `config`, `chunk_pos`, `chunk_size` and `seed`.
+/// Golden tests hash the result with `hash_chunk_blocks` to catch unintended
+/// changes to generation output.
+pub fn generate_chunk_deterministic(
+    config: &GeneratorConfig,
+    chunk_pos: ChunkPos,
+    chunk_size: u32,
+    seed: u32,
+) -> ChunkBlocks {
+    let mut seeded = config.clone();
+    seeded.terrain_params.seed = seed;
+    generate_chunk_blocks(&seeded, chunk_pos, chunk_size)
+}
+
+/// Stable 64-bit FNV-1a hash of a chunk's blocks
+/// Unlike `DefaultHasher`, the value is identical across Rust versions and platforms.
+pub fn hash_chunk_blocks(chunk: &ChunkBlocks) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET;
+    for block in &chunk.blocks {
+        for byte in block.0.to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
+
+/// Copy a generated block array into a `TempChunk` for upload
+pub fn chunk_blocks_to_temp_chunk(chunk: &ChunkBlocks) -> TempChunk {
+    let mut temp = TempChunk::new_empty(chunk.chunk_pos, chunk.size);
+    let size = chunk.size;
+    for z in 0..size {
+        for y in 0..size {
+            for x in 0..size {
+                let block = chunk.blocks[block_index(size, x, y, z)];
+                if block != BlockId::AIR {
+                    temp.set_block(x, y, z, block);
+                }
+            }
+        }
+    }
+    temp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::generation::{BiomeDefinition, OreDistribution};
+
+    #[test]
+    fn test_disabled_terrain_leaves_air() {
+        let config = GeneratorConfig {
+            stages: GenerationStages {
+                terrain: false,
+                caves: true,
+                ores: true,
+                structures: true,
+            },
+            ..GeneratorConfig::default()
+        };
+
+        let chunk = generate_chunk_blocks(&config, ChunkPos::new(0, 0, 0), 16);
+        assert!(chunk.blocks.iter().all(|b| *b == BlockId::AIR));
+    }
+
+    #[test]
+    fn test_terrain_only_has_no_ores() {
+        let config = GeneratorConfig {
+            stages: GenerationStages::terrain_only(),
+            ..GeneratorConfig::default()
+        };
+        let ids = config.block_ids;
+
+        let chunk = generate_chunk_blocks(&config, ChunkPos::new(0, 0, 0), 16);
+        assert!(chunk.blocks.iter().all(|b| *b == ids.stone || *b == ids.grass || *b == ids.air));
+        assert!(chunk.blocks.iter().any(|b| *b == ids.stone));
+    }
+
+    #[test]
+    fn test_deterministic_generation_is_repeatable() {
+        let config = GeneratorConfig::default();
+        let pos = ChunkPos::new(3, 1, -2);
+
+        let a = generate_chunk_deterministic(&config, pos, 32, 42);
+        let b = generate_chunk_deterministic(&config, pos, 32, 42);
+        let c = generate_chunk_deterministic(&config, pos, 32, 43);
+
+        assert_eq!(hash_chunk_blocks(&a), hash_chunk_blocks(&b));
+        assert_eq!(a.blocks, b.blocks);
+        assert_ne!(hash_chunk_blocks(&a), hash_chunk_blocks(&c));
+    }
+
+    #[test]
+    fn test_integer_wave_tracks_sine() {
+        for phase in -200..200 {
+            let angle = phase as f32 * std::f32::consts::TAU / TERRAIN_WAVE_PERIOD as f32;
+            let error = terrain_wave_milli(phase) as f32 - angle.sin() * 1000.0;
+            assert!(error.abs() <= 3.0, "phase {}: error {}", phase, error);
+        }
+        assert_eq!(
+            terrain_wave_milli(TERRAIN_WAVE_PERIOD),
+            terrain_wave_milli(0)
+        );
+    }
+
+    #[test]
+    fn test_biomes_change_surface_blocks() {
+        let config = GeneratorConfig {
+            stages: GenerationStages::terrain_only(),
+            biomes: vec![BiomeDefinition::desert()],
+            ..GeneratorConfig::default()
+        };
+
+        let chunk = generate_chunk_blocks(&config, ChunkPos::new(0, 1, 0), 50);
+        assert!(chunk.blocks.contains(&BlockId::SAND));
+        assert!(!chunk.blocks.contains(&BlockId::GRASS));
+    }
+
+    #[test]
+    fn test_density_terrain_is_seamless_across_chunks() {
+        let mut config = GeneratorConfig {
+            stages: GenerationStages {
+                terrain: true,
+                caves: true,
+                ores: false,
+                structures: false,
+            },
+            ..GeneratorConfig::default()
+        };
+        config.terrain_params.use_3d_density = true;
+
+        // One 64-voxel chunk must equal the eight 32-voxel chunks covering it
+        let whole = generate_chunk_blocks(&config, ChunkPos::new(0, 0, 0), 64);
+        for (cx, cy, cz) in (0..8).map(|i| (i & 1, (i >> 1) & 1, i >> 2)) {
+            let part = generate_chunk_blocks(&config, ChunkPos::new(cx, cy, cz), 32);
+            for z in 0..32 {
+                for y in 0..32 {
+                    for x in 0..32 {
+                        let outer = block_index(
+                            64,
+                            cx as u32 * 32 + x,
+                            cy as u32 * 32 + y,
+                            cz as u32 * 32 + z,
+                        );
+                        assert_eq!(part.blocks[block_index(32, x, y, z)], whole.blocks[outer]);
+                    }
+                }
+            }
+        }
+        assert!(whole.blocks.contains(&config.block_ids.grass));
+    }
+
+    #[test]
+    fn test_ore_frequency_matches_distribution() {
+        let shallow = OreDistribution {
+            block: BlockId::COAL_ORE,
+            attempts_per_chunk: 8,
+            vein_size: 8,
+            min_y: 24,
+            max_y: 39,
+        };
+        // Deep ore: rarer but larger veins
+        let deep = OreDistribution {
+            block: BlockId::DIAMOND_ORE,
+            attempts_per_chunk: 1,
+            vein_size: 16,
+            min_y: 0,
+            max_y: 15,
+        };
+        let config = GeneratorConfig {
+            stages: GenerationStages {
+                terrain: true,
+                caves: false,
+                ores: true,
+                structures: false,
+            },
+            ores: vec![shallow.clone(), deep.clone()],
+            ..GeneratorConfig::default()
+        };
+
+        // 8x8 chunk columns, y = 0..64 - everything below y = 50 is stone
+        let size = 32;
+        let mut counts = std::collections::HashMap::new();
+        for cz in 0..8 {
+            for cx in 0..8 {
+                for cy in 0..2 {
+                    let chunk = generate_chunk_blocks(&config, ChunkPos::new(cx, cy, cz), size);
+                    for (index, block) in chunk.blocks.iter().enumerate() {
+                        if *block == shallow.block || *block == deep.block {
+                            let world_y =
+                                cy * size as i32 + (index as i32 / size as i32) % size as i32;
+                            *counts.entry((*block, world_y)).or_insert(0u32) += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        for ore in [&shallow, &deep] {
+            let band = ore.min_y..=ore.max_y;
+            let stray: u32 = counts
+                .iter()
+                .filter(|((block, y), _)| *block == ore.block && !band.contains(y))
+                .map(|(_, n)| n)
+                .sum();
+            assert_eq!(stray, 0, "{:?} placed outside its depth band", ore.block);
+
+            let measured: u32 = counts
+                .iter()
+                .filter(|((block, _), _)| *block == ore.block)
+                .map(|(_, n)| n)
+                .sum();
+            let band_voxels = 64.0 * (size * size) as f64 * (ore.max_y - ore.min_y + 1) as f64;
+            let expected = (ore.attempts_per_chunk * ore.vein_size) as f64 * 64.0 / band_voxels;
+            let rate = measured as f64 / band_voxels;
+            assert!(
+                (rate - expected).abs() <= expected * 0.15,
+                "{:?}: measured {:.5} expected {:.5}",
+                ore.block,
+                rate,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_golden_chunk_hash() {
+        // If this fails, generation output changed. Update the constant only
+        // when the change is intentional.
+        let config = GeneratorConfig::default();
+        let chunk = generate_chunk_deterministic(&config, ChunkPos::new(0, 1, 0), 50, 12345);
+        assert_eq!(hash_chunk_blocks(&chunk), 3517849780963894100);
+    }
+}
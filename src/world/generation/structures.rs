@@ -0,0 +1,235 @@
+//! Structure placement - small voxel schematics such as trees or ruins
+//!
+//! Like ore veins, placements are a pure function of the seed and the chunk
+//! column. Columns are always `CHUNK_SIZE` wide, whatever size is being
+//! generated. A structure reaching past its own column is deferred to the
+//! neighbours: every chunk replays the placements of the columns around it
+//! and keeps the voxels that fall inside, so the overflow lands whenever the
+//! neighbour is generated, in any order.
+
+use super::ores::mix64;
+use crate::constants::core::CHUNK_SIZE;
+use crate::world::core::BlockId;
+
+/// Salt separating structure streams from ore streams of the same column
+const STRUCTURE_SALT: u64 = 0x5354_5255_4354;
+
+/// Small voxel schematic placed relative to an anchor voxel
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructureTemplate {
+    /// Extent in voxels (x, y, z)
+    pub size: [u32; 3],
+    /// Template voxel that lands on the placement position
+    pub anchor: [u32; 3],
+    /// Voxels indexed `x + y * size_x + z * size_x * size_y`; air entries
+    /// leave the world untouched
+    pub blocks: Vec<BlockId>,
+}
+
+impl StructureTemplate {
+    /// `None` if `blocks` does not fill `size` or the anchor lies outside it
+    pub fn new(size: [u32; 3], anchor: [u32; 3], blocks: Vec<BlockId>) -> Option<Self> {
+        let volume = (size[0] * size[1] * size[2]) as usize;
+        let anchor_inside = (0..3).all(|axis| anchor[axis] < size[axis]);
+        (blocks.len() == volume && anchor_inside).then_some(Self {
+            size,
+            anchor,
+            blocks,
+        })
+    }
+
+    /// Vertical column of `height` blocks standing on its anchor, e.g. a trunk
+    pub fn column(block: BlockId, height: u32) -> Self {
+        Self {
+            size: [1, height, 1],
+            anchor: [0, 0, 0],
+            blocks: vec![block; height as usize],
+        }
+    }
+
+    /// Non-air voxels as offsets from the anchor
+    pub fn voxels(&self) -> impl Iterator<Item = ([i32; 3], BlockId)> + '_ {
+        let [size_x, size_y, _] = self.size;
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| **block != BlockId::AIR)
+            .map(move |(index, block)| {
+                let index = index as u32;
+                let position = [
+                    index % size_x,
+                    (index / size_x) % size_y,
+                    index / (size_x * size_y),
+                ];
+                let offset = [0, 1, 2].map(|axis| position[axis] as i32 - self.anchor[axis] as i32);
+                (offset, *block)
+            })
+    }
+
+    /// Furthest any voxel sits from the anchor along x or z
+    pub fn horizontal_reach(&self) -> u32 {
+        let reach = |axis: usize| self.anchor[axis].max(self.size[axis] - 1 - self.anchor[axis]);
+        reach(0).max(reach(2))
+    }
+}
+
+/// A template and how often it is placed
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructureRule {
+    pub template: StructureTemplate,
+    /// Placements per chunk column, each on the surface of a random voxel column
+    pub attempts_per_chunk: u32,
+}
+
+/// Deterministic random stream for one chunk column and rule
+struct StructureRng(u64);
+
+impl StructureRng {
+    fn new(seed: u32, column_x: i32, column_z: i32, rule_index: usize) -> Self {
+        let mut state = seed as u64 ^ STRUCTURE_SALT;
+        for value in [
+            column_x as u32 as u64,
+            column_z as u32 as u64,
+            rule_index as u64,
+        ] {
+            state = mix64(state ^ value);
+        }
+        Self(state)
+    }
+
+    /// Uniform value in `0..bound` (`bound` > 0)
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 = mix64(self.0);
+        self.0 % bound
+    }
+}
+
+pub struct StructurePlacer {
+    seed: u32,
+    rules: Vec<StructureRule>,
+}
+
+impl StructurePlacer {
+    pub fn new(seed: u32, rules: Vec<StructureRule>) -> Self {
+        Self { seed, rules }
+    }
+
+    pub fn rules(&self) -> &[StructureRule] {
+        &self.rules
+    }
+
+    /// Furthest any structure reaches from its anchor along x or z
+    pub fn horizontal_reach(&self) -> u32 {
+        self.rules
+            .iter()
+            .map(|rule| rule.template.horizontal_reach())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Anchors of the structures started in one `CHUNK_SIZE` chunk column,
+    /// as `(rule index, world position)`
+    ///
+    /// `surface_y` gives the top solid voxel of a world column; structures
+    /// stand on it, and columns where it returns `None` get none.
+    pub fn column_placements(
+        &self,
+        column_x: i32,
+        column_z: i32,
+        surface_y: &dyn Fn(i32, i32) -> Option<i32>,
+    ) -> Vec<(usize, [i32; 3])> {
+        let mut placements = Vec::new();
+        let size = CHUNK_SIZE as i32;
+
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            let mut rng = StructureRng::new(self.seed, column_x, column_z, rule_index);
+            for _ in 0..rule.attempts_per_chunk {
+                let world_x = column_x * size + rng.below(CHUNK_SIZE as u64) as i32;
+                let world_z = column_z * size + rng.below(CHUNK_SIZE as u64) as i32;
+                if let Some(surface) = surface_y(world_x, world_z) {
+                    placements.push((rule_index, [world_x, surface + 1, world_z]));
+                }
+            }
+        }
+
+        placements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::core::ChunkPos;
+    use crate::world::generation::{
+        block_index, generate_chunk_blocks, GenerationStages, GeneratorConfig,
+    };
+
+    fn config_with(rules: Vec<StructureRule>) -> GeneratorConfig {
+        GeneratorConfig {
+            stages: GenerationStages {
+                structures: true,
+                ..GenerationStages::terrain_only()
+            },
+            structures: rules,
+            ..GeneratorConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_tree_trunk_lands_on_surface() {
+        let trunk = StructureRule {
+            template: StructureTemplate::column(BlockId::WOOD, 3),
+            attempts_per_chunk: 1,
+        };
+        let config = config_with(vec![trunk.clone()]);
+        let placer = StructurePlacer::new(config.terrain_params.seed, vec![trunk]);
+        let surface = |x, z| Some(crate::world::generation::terrain_surface_y(x, z));
+
+        let placements = placer.column_placements(0, 0, &surface);
+        assert_eq!(placements.len(), 1);
+        let (_, [x, y, z]) = placements[0];
+
+        // Chunk (0, 1, 0) spans y = 50..100, around the surface
+        let chunk = generate_chunk_blocks(&config, ChunkPos::new(0, 1, 0), CHUNK_SIZE);
+        let at = |dy: i32| chunk.blocks[block_index(50, x as u32, (y + dy - 50) as u32, z as u32)];
+        assert_eq!(at(-1), config.block_ids.grass);
+        assert_eq!([at(0), at(1), at(2)], [BlockId::WOOD; 3]);
+        assert_eq!(at(3), BlockId::AIR);
+    }
+
+    #[test]
+    fn test_structures_continue_across_chunk_borders() {
+        // 3x4x3 tree: trunk in the middle, a leaf layer on top
+        let mut blocks = vec![BlockId::AIR; 36];
+        for y in 0..3 {
+            blocks[4 + y * 9] = BlockId::WOOD;
+        }
+        blocks[27..36].fill(BlockId::LEAVES);
+        let tree = StructureRule {
+            template: StructureTemplate::new([3, 4, 3], [1, 0, 1], blocks)
+                .expect("blocks fill the template"),
+            attempts_per_chunk: 24,
+        };
+        let config = config_with(vec![tree]);
+
+        // One 64-voxel chunk must equal the 32-voxel chunks covering it
+        let whole = generate_chunk_blocks(&config, ChunkPos::new(0, 1, 0), 64);
+        for (cx, cy, cz) in (0..8).map(|i| (i & 1, (i >> 1) & 1, i >> 2)) {
+            let part = generate_chunk_blocks(&config, ChunkPos::new(cx, 2 + cy, cz), 32);
+            for z in 0..32 {
+                for y in 0..32 {
+                    for x in 0..32 {
+                        let outer = block_index(
+                            64,
+                            cx as u32 * 32 + x,
+                            cy as u32 * 32 + y,
+                            cz as u32 * 32 + z,
+                        );
+                        assert_eq!(part.blocks[block_index(32, x, y, z)], whole.blocks[outer]);
+                    }
+                }
+            }
+        }
+        assert!(whole.blocks.contains(&BlockId::LEAVES));
+    }
+}
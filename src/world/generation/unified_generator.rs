@@ -1,6 +1,9 @@
 //! GPU-first generation interface
 
-use super::TerrainParams;
+use super::{
+    default_ore_distributions, BiomeDefinition, GenerationStages, OreDistribution, StructureRule,
+    TerrainParams,
+};
 use crate::world::core::{BlockId, ChunkPos};
 use crate::world::storage::TempChunk;
 
@@ -9,6 +12,24 @@ pub trait WorldGenerator: Send + Sync {
     /// Generate a chunk at the given position
     fn generate_chunk(&self, chunk_pos: ChunkPos, chunk_size: u32) -> TempChunk;
 
+    /// Generate several chunks in one go, in the order given
+    ///
+    /// The default generates them one at a time; GPU backends override it to
+    /// cover the whole batch with a single dispatch.
+    fn generate_chunks_batch(
+        &self,
+        positions: &[ChunkPos],
+        chunk_size: u32,
+    ) -> Vec<GeneratedChunk> {
+        positions
+            .iter()
+            .map(|&chunk_pos| GeneratedChunk {
+                chunk_pos,
+                chunk: self.generate_chunk(chunk_pos, chunk_size),
+            })
+            .collect()
+    }
+
     /// Get surface height at world coordinates
     fn get_surface_height(&self, world_x: f64, world_z: f64) -> i32;
 
@@ -31,11 +52,18 @@ pub trait WorldGenerator: Send + Sync {
     }
 }
 
-/// GPU-based unified generator
+/// One chunk produced by `WorldGenerator::generate_chunks_batch`
+pub struct GeneratedChunk {
+    pub chunk_pos: ChunkPos,
+    pub chunk: TempChunk,
+}
+
+/// Unified generator over the GPU or the headless CPU backend
 pub struct UnifiedGenerator {
     generator: Box<dyn WorldGenerator>,
-    device: std::sync::Arc<wgpu::Device>,
-    buffer_manager: std::sync::Arc<crate::gpu::GpuBufferManager>,
+    /// GPU resources; `None` for the CPU backend
+    device: Option<std::sync::Arc<wgpu::Device>>,
+    buffer_manager: Option<std::sync::Arc<crate::gpu::GpuBufferManager>>,
 }
 
 impl UnifiedGenerator {
@@ -47,36 +75,48 @@ impl UnifiedGenerator {
     ) -> Result<Self, GeneratorError> {
         Ok(UnifiedGenerator {
             generator,
-            device,
-            buffer_manager,
+            device: Some(device),
+            buffer_manager: Some(buffer_manager),
         })
     }
 
     /// Create GPU-based generator
+    ///
+    /// Falls back to the CPU backend when the config uses features the GPU
+    /// terrain kernel does not implement, so the world looks the same either way.
     pub async fn new_gpu(
         device: std::sync::Arc<wgpu::Device>,
         buffer_manager: std::sync::Arc<crate::gpu::GpuBufferManager>,
         config: GeneratorConfig,
     ) -> Result<Self, GeneratorError> {
+        let cpu_only = config.cpu_only_features();
+        if !cpu_only.is_empty() {
+            log::warn!(
+                "[UnifiedGenerator] Using the CPU backend, the GPU kernel does not support: {}",
+                cpu_only.join(", ")
+            );
+            return Ok(Self::new_cpu(config));
+        }
+
         // Create the GPU terrain generator
         let terrain_generator = super::TerrainGeneratorSOABuilder::new()
             .with_vectorization(config.use_vectorization)
             .build(device.clone(), buffer_manager.clone())
             .map_err(|e| GeneratorError::InitError(format!("Failed to create terrain generator: {:?}", e)))?;
 
-        // Create world buffer for GPU operations
+        // Create world buffer for GPU operations; batches read their chunks back
         let world_buffer_desc = crate::world::storage::WorldBufferDescriptor {
             view_distance: 16, // 16 chunks view distance
             enable_atomics: true,
-            enable_readback: false,
+            enable_readback: true,
         };
         let world_buffer = std::sync::Arc::new(std::sync::Mutex::new(
             crate::world::storage::WorldBuffer::new(device.clone(), &world_buffer_desc),
         ));
 
-        // Note: GPU generation through the WorldGenerator trait will return empty chunks
-        // since the trait doesn't provide access to command encoders. Actual GPU generation
-        // must be done through the renderer when a command encoder is available.
+        // Note: generate_chunk only records GPU work and returns the CPU fallback,
+        // since the trait doesn't provide access to command encoders. Batches
+        // submit their own dispatch and read the chunks back from the world buffer.
 
         // Create GPU world generator wrapper
         let gpu_generator = super::GpuWorldGenerator::new(
@@ -84,18 +124,28 @@ impl UnifiedGenerator {
             device.clone(),
             buffer_manager.queue().clone(),
             world_buffer,
+            config.stages,
         );
 
         Ok(UnifiedGenerator {
             generator: Box::new(gpu_generator) as Box<dyn WorldGenerator>,
-            device,
-            buffer_manager,
+            device: Some(device),
+            buffer_manager: Some(buffer_manager),
         })
     }
 
-    /// Check if using GPU backend (always true)
+    /// Create a CPU-only generator; needs no wgpu device
+    pub fn new_cpu(config: GeneratorConfig) -> Self {
+        UnifiedGenerator {
+            generator: Box::new(super::CpuWorldGenerator::new(config)),
+            device: None,
+            buffer_manager: None,
+        }
+    }
+
+    /// Check if using GPU backend
     pub fn is_gpu(&self) -> bool {
-        true
+        self.generator.is_gpu()
     }
 }
 
@@ -104,12 +154,20 @@ impl WorldGenerator for UnifiedGenerator {
         self.generator.generate_chunk(chunk_pos, chunk_size)
     }
 
+    fn generate_chunks_batch(
+        &self,
+        positions: &[ChunkPos],
+        chunk_size: u32,
+    ) -> Vec<GeneratedChunk> {
+        self.generator.generate_chunks_batch(positions, chunk_size)
+    }
+
     fn get_surface_height(&self, world_x: f64, world_z: f64) -> i32 {
         self.generator.get_surface_height(world_x, world_z)
     }
 
     fn is_gpu(&self) -> bool {
-        true
+        self.generator.is_gpu()
     }
 
     fn get_world_buffer(
@@ -125,6 +183,29 @@ pub struct GeneratorConfig {
     pub terrain_params: TerrainParams,
     pub block_ids: BlockIds,
     pub use_vectorization: bool,
+    /// Which generation passes run; disabled passes are skipped entirely
+    pub stages: GenerationStages,
+    /// Biomes blended by the CPU terrain pass; empty keeps the single base terrain.
+    /// Setting any makes `UnifiedGenerator::new_gpu` use the CPU backend.
+    pub biomes: Vec<BiomeDefinition>,
+    /// Ore veins placed by the ore pass, in priority order
+    pub ores: Vec<OreDistribution>,
+    /// Structures stamped by the structure pass; skipped in density mode
+    pub structures: Vec<StructureRule>,
+}
+
+impl GeneratorConfig {
+    /// Configured features only the CPU generator implements
+    pub fn cpu_only_features(&self) -> Vec<&'static str> {
+        let mut features = Vec::new();
+        if !self.biomes.is_empty() {
+            features.push("biomes");
+        }
+        if self.terrain_params.use_3d_density {
+            features.push("3d density");
+        }
+        features
+    }
 }
 
 impl Default for GeneratorConfig {
@@ -133,6 +214,10 @@ impl Default for GeneratorConfig {
             terrain_params: TerrainParams::default(),
             block_ids: BlockIds::default(),
             use_vectorization: true,
+            stages: GenerationStages::default(),
+            biomes: Vec::new(),
+            ores: default_ore_distributions(),
+            structures: Vec::new(),
         }
     }
 }
@@ -182,6 +267,24 @@ mod tests {
     fn test_generator_config_default() {
         let config = GeneratorConfig::default();
         assert!(config.use_vectorization);
+        assert_eq!(config.stages, GenerationStages::default());
+        assert!(config.cpu_only_features().is_empty());
+    }
+
+    #[test]
+    fn test_biomes_need_the_cpu_backend() {
+        let config = GeneratorConfig {
+            biomes: crate::world::generation::default_biomes(),
+            ..GeneratorConfig::default()
+        };
+        assert_eq!(config.cpu_only_features(), vec!["biomes"]);
+    }
+
+    #[test]
+    fn test_density_mode_needs_the_cpu_backend() {
+        let mut config = GeneratorConfig::default();
+        config.terrain_params.use_3d_density = true;
+        assert_eq!(config.cpu_only_features(), vec!["3d density"]);
     }
 
     #[test]
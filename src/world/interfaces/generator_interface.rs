@@ -4,12 +4,14 @@ use super::{capabilities, UnifiedInterface};
 use crate::world::{
     core::{ChunkPos, VoxelPos},
     generation::{TerrainParams, UnifiedGenerator, WorldGenerator},
+    management::{MonitorConfig, PerformanceMonitor},
     storage::TempChunk,
 };
 use crate::constants::core::CHUNK_SIZE;
 use crate::constants::monitoring::PROFILING_SESSION_TIMEOUT_MS;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
 
 /// Universal generator interface
 pub trait GeneratorInterface: UnifiedInterface {
@@ -41,12 +43,27 @@ pub trait GeneratorInterface: UnifiedInterface {
 /// Unified generator interface implementation
 pub struct UnifiedGeneratorInterface {
     generator: Arc<UnifiedGenerator>,
+    /// Generation times and batch sizes of this interface
+    monitor: Mutex<PerformanceMonitor>,
 }
 
 impl UnifiedGeneratorInterface {
     /// Create a new unified generator interface
     pub fn new(generator: Arc<UnifiedGenerator>) -> Self {
-        Self { generator }
+        Self {
+            generator,
+            monitor: Mutex::new(PerformanceMonitor::new(MonitorConfig::default())),
+        }
+    }
+
+    /// Generation statistics, including batch counts and sizes
+    pub fn generation_stats(&self) -> crate::world::management::GenerationStats {
+        self.lock_monitor().metrics().generation_stats.clone()
+    }
+
+    /// The monitor only holds statistics, so a poisoned lock is still usable
+    fn lock_monitor(&self) -> std::sync::MutexGuard<'_, PerformanceMonitor> {
+        self.monitor.lock().unwrap_or_else(PoisonError::into_inner)
     }
 }
 
@@ -72,10 +89,22 @@ impl UnifiedInterface for UnifiedGeneratorInterface {
     }
 
     fn performance_metrics(&self) -> Option<HashMap<String, f64>> {
-        // TODO: Implement performance metrics for generator
+        let stats = self.generation_stats();
         Some(HashMap::from([
-            ("chunks_generated".to_string(), 0.0),
-            ("avg_generation_time_ms".to_string(), 0.0),
+            (
+                "chunks_generated".to_string(),
+                stats.chunks_generated as f64,
+            ),
+            (
+                "avg_generation_time_ms".to_string(),
+                stats.avg_generation_time_ms,
+            ),
+            (
+                "batches_generated".to_string(),
+                stats.batches_generated as f64,
+            ),
+            ("avg_batch_size".to_string(), stats.avg_batch_size),
+            ("peak_batch_size".to_string(), stats.peak_batch_size as f64),
         ]))
     }
 }
@@ -103,9 +132,24 @@ impl GeneratorInterface for UnifiedGeneratorInterface {
     ) -> Result<Vec<GenerationResult>, GeneratorError> {
         let mut results = Vec::with_capacity(requests.len());
 
-        for request in requests {
-            let result = self.generate_chunk(request)?;
-            results.push(result);
+        // Consecutive requests of one chunk size go to the generator as a single batch
+        for group in requests.chunk_by(|a, b| a.chunk_size == b.chunk_size) {
+            let positions: Vec<ChunkPos> = group.iter().map(|request| request.chunk_pos).collect();
+            let start = Instant::now();
+            let chunks = self
+                .generator
+                .generate_chunks_batch(&positions, group[0].chunk_size);
+            let elapsed = start.elapsed();
+            self.lock_monitor()
+                .record_generation_batch(positions.len(), elapsed);
+            let per_chunk_ms = elapsed.as_secs_f64() * 1000.0 / positions.len() as f64;
+
+            results.extend(chunks.into_iter().map(|generated| GenerationResult {
+                chunk_pos: generated.chunk_pos,
+                chunk: Some(generated.chunk),
+                generation_time_ms: per_chunk_ms,
+                metadata: HashMap::from([("batch_size".to_string(), positions.len() as f64)]),
+            }));
         }
 
         Ok(results)
@@ -253,6 +297,26 @@ mod tests {
         assert_eq!(request.priority, GenerationPriority::Normal);
     }
 
+    #[test]
+    fn test_batch_generation_is_recorded() {
+        let generator = Arc::new(UnifiedGenerator::new_cpu(
+            crate::world::generation::GeneratorConfig::default(),
+        ));
+        let interface = UnifiedGeneratorInterface::new(generator);
+
+        let requests = (0..3)
+            .map(|x| GenerationRequest::new(ChunkPos { x, y: 0, z: 0 }, 8))
+            .collect();
+        let results = interface.generate_batch(requests).expect("batch generates");
+        assert_eq!(results.len(), 3);
+
+        let stats = interface.generation_stats();
+        assert_eq!(stats.chunks_generated, 3);
+        assert_eq!(stats.batches_generated, 1);
+        assert_eq!(stats.peak_batch_size, 3);
+        assert_eq!(stats.avg_batch_size, 3.0);
+    }
+
     #[test]
     fn test_generation_priority_ordering() {
         assert!(GenerationPriority::Critical > GenerationPriority::High);
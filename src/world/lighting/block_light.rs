@@ -0,0 +1,300 @@
+//! Colored block light propagation
+//!
+//! Block light is kept per color channel so tinted sources such as lava or
+//! crystals color their surroundings. Each channel floods outwards on its
+//! own, losing `LIGHT_FALLOFF` per block. The brightest channel is mirrored
+//! into the low nibble of `ChunkLightData::light_data` for code that reads a
+//! single block light level.
+//!
+//! Light crosses chunk borders: a chunk is lit by every emitter within
+//! `BLOCK_LIGHT_REACH`, including those in neighbouring chunks, so adjacent
+//! chunks agree at their shared faces.
+
+use super::{BlockProvider, ChunkLightData};
+use crate::constants::lighting::{LIGHT_FALLOFF, MAX_LIGHT_LEVEL};
+use crate::world::core::{BlockId, ChunkPos, VoxelPos};
+use std::collections::VecDeque;
+
+/// Farthest a block's light travels, in blocks
+pub const BLOCK_LIGHT_REACH: i32 = ((MAX_LIGHT_LEVEL - 1) / LIGHT_FALLOFF) as i32;
+
+/// Face neighbours light spreads to
+const NEIGHBOURS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Pack RGB block light into 4 bits per channel (`0x0RGB`), the layout
+/// uploaded to the GPU
+pub fn pack_block_light(rgb: [u8; 3]) -> u16 {
+    let [r, g, b] = rgb.map(|channel| channel.min(MAX_LIGHT_LEVEL) as u16);
+    (r << 8) | (g << 4) | b
+}
+
+/// Split packed block light back into red, green and blue levels
+pub fn unpack_block_light(packed: u16) -> [u8; 3] {
+    [
+        ((packed >> 8) & 0xF) as u8,
+        ((packed >> 4) & 0xF) as u8,
+        (packed & 0xF) as u8,
+    ]
+}
+
+/// Per-channel emission for a block's `light_emission` and `light_color`
+pub fn emission_rgb(level: u8, color: [f32; 3]) -> [u8; 3] {
+    let level = level.min(MAX_LIGHT_LEVEL) as f32;
+    color.map(|tint| (level * tint.clamp(0.0, 1.0)).round() as u8)
+}
+
+/// Block light calculator - floods colored light from emitting blocks
+pub struct BlockLightCalculator;
+
+impl BlockLightCalculator {
+    /// Recompute block light for a whole chunk
+    ///
+    /// Emitters are read through `BlockProvider::light_emission`, in the chunk
+    /// and up to `BLOCK_LIGHT_REACH` blocks around it, so the provider must
+    /// also know the neighbouring chunks' blocks. The flood runs over that
+    /// padded box; only the chunk's own voxels are stored.
+    pub fn recompute_chunk(chunk: &ChunkLightData, blocks: &dyn BlockProvider) {
+        let size = chunk.size;
+        // Light that leaves the padded box cannot come back with any level left
+        let padded = size + 2 * BLOCK_LIGHT_REACH as u32;
+        let base_x = chunk.chunk_pos.x * size as i32 - BLOCK_LIGHT_REACH;
+        let base_y = chunk.chunk_pos.y * size as i32 - BLOCK_LIGHT_REACH;
+        let base_z = chunk.chunk_pos.z * size as i32 - BLOCK_LIGHT_REACH;
+        let index = |x: u32, y: u32, z: u32| (x + y * padded + z * padded * padded) as usize;
+        let world_pos = |x: u32, y: u32, z: u32| {
+            VoxelPos::new(base_x + x as i32, base_y + y as i32, base_z + z as i32)
+        };
+
+        let mut rgb = vec![[0u8; 3]; (padded * padded * padded) as usize];
+        let mut queue = VecDeque::new();
+
+        for z in 0..padded {
+            for y in 0..padded {
+                for x in 0..padded {
+                    let emission = blocks.light_emission(world_pos(x, y, z));
+                    if emission != [0; 3] {
+                        rgb[index(x, y, z)] = emission.map(|channel| channel.min(MAX_LIGHT_LEVEL));
+                        queue.push_back((x, y, z));
+                    }
+                }
+            }
+        }
+
+        while let Some((x, y, z)) = queue.pop_front() {
+            let spread = rgb[index(x, y, z)].map(|channel| channel.saturating_sub(LIGHT_FALLOFF));
+            if spread == [0; 3] {
+                continue;
+            }
+
+            for (dx, dy, dz) in NEIGHBOURS {
+                let (nx, ny, nz) = (x as i32 + dx, y as i32 + dy, z as i32 + dz);
+                if !(0..padded as i32).contains(&nx)
+                    || !(0..padded as i32).contains(&ny)
+                    || !(0..padded as i32).contains(&nz)
+                {
+                    continue;
+                }
+                let (nx, ny, nz) = (nx as u32, ny as u32, nz as u32);
+                if !passes_light(blocks, world_pos(nx, ny, nz)) {
+                    continue;
+                }
+
+                // Channels are independent: a neighbour keeps its own brighter channels
+                let neighbour = &mut rgb[index(nx, ny, nz)];
+                let merged = [
+                    neighbour[0].max(spread[0]),
+                    neighbour[1].max(spread[1]),
+                    neighbour[2].max(spread[2]),
+                ];
+                if merged != *neighbour {
+                    *neighbour = merged;
+                    queue.push_back((nx, ny, nz));
+                }
+            }
+        }
+
+        let mut light_data = chunk.light_data.write();
+        let mut block_light_rgb = chunk.block_light_rgb.write();
+        let reach = BLOCK_LIGHT_REACH as u32;
+        for z in 0..size {
+            for y in 0..size {
+                for x in 0..size {
+                    let light = rgb[index(x + reach, y + reach, z + reach)];
+                    let i = (x + y * size + z * size * size) as usize;
+                    if let Some(packed) = block_light_rgb.get_mut(i) {
+                        *packed = pack_block_light(light);
+                    }
+                    if let Some(packed) = light_data.get_mut(i) {
+                        let brightest = light[0].max(light[1]).max(light[2]);
+                        *packed = (*packed & 0xF0) | brightest;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Chunks within `BLOCK_LIGHT_REACH` of the voxel box `min..=max`
+    ///
+    /// These are the chunks whose block light can change when a block in the
+    /// box changes, and the chunks whose emitters can light a chunk spanning it.
+    pub fn chunks_in_reach(min: VoxelPos, max: VoxelPos, chunk_size: u32) -> Vec<ChunkPos> {
+        let low = VoxelPos::new(
+            min.x - BLOCK_LIGHT_REACH,
+            min.y - BLOCK_LIGHT_REACH,
+            min.z - BLOCK_LIGHT_REACH,
+        )
+        .to_chunk_pos(chunk_size);
+        let high = VoxelPos::new(
+            max.x + BLOCK_LIGHT_REACH,
+            max.y + BLOCK_LIGHT_REACH,
+            max.z + BLOCK_LIGHT_REACH,
+        )
+        .to_chunk_pos(chunk_size);
+
+        let mut chunks = Vec::new();
+        for z in low.z..=high.z {
+            for y in low.y..=high.y {
+                for x in low.x..=high.x {
+                    chunks.push(ChunkPos::new(x, y, z));
+                }
+            }
+        }
+        chunks
+    }
+
+    /// Read the block light color stored for a local position
+    pub fn block_light_at(chunk: &ChunkLightData, x: u32, y: u32, z: u32) -> [u8; 3] {
+        let size = chunk.size;
+        let index = (x + y * size + z * size * size) as usize;
+        chunk
+            .block_light_rgb
+            .read()
+            .get(index)
+            .map(|packed| unpack_block_light(*packed))
+            .unwrap_or([0; 3])
+    }
+}
+
+/// Whether block light can enter the voxel at `pos`
+fn passes_light(blocks: &dyn BlockProvider, pos: VoxelPos) -> bool {
+    blocks.get_block(pos) == BlockId::AIR || blocks.is_transparent(pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct TestBlocks {
+        lights: HashMap<VoxelPos, [u8; 3]>,
+    }
+
+    impl BlockProvider for TestBlocks {
+        fn get_block(&self, pos: VoxelPos) -> BlockId {
+            if self.lights.contains_key(&pos) {
+                BlockId::LAVA
+            } else {
+                BlockId::AIR
+            }
+        }
+
+        fn is_transparent(&self, pos: VoxelPos) -> bool {
+            !self.lights.contains_key(&pos)
+        }
+
+        fn light_emission(&self, pos: VoxelPos) -> [u8; 3] {
+            self.lights.get(&pos).copied().unwrap_or([0; 3])
+        }
+    }
+
+    #[test]
+    fn test_red_light_tints_neighbours_red() {
+        let chunk = ChunkLightData::new(ChunkPos::new(0, 0, 0), 16);
+        let red = emission_rgb(15, [1.0, 0.2, 0.1]);
+        let blocks = TestBlocks {
+            lights: HashMap::from([(VoxelPos::new(8, 8, 8), red)]),
+        };
+
+        BlockLightCalculator::recompute_chunk(&chunk, &blocks);
+
+        for (x, y, z) in [(9, 8, 8), (7, 8, 8), (8, 9, 8), (8, 8, 7)] {
+            let [r, g, b] = BlockLightCalculator::block_light_at(&chunk, x, y, z);
+            assert_eq!(r, red[0] - LIGHT_FALLOFF);
+            assert!(r > g && r > b);
+        }
+
+        // Each channel fades on its own; green and blue run out first
+        let [r, g, b] = BlockLightCalculator::block_light_at(&chunk, 8, 8, 12);
+        assert_eq!((r, g, b), (11, 0, 0));
+
+        // Single-level readers see the brightest channel
+        let packed = chunk.light_data.read()[(9 + 8 * 16 + 8 * 256) as usize];
+        assert_eq!(packed & 0x0F, 14);
+    }
+
+    #[test]
+    fn test_light_crosses_chunk_borders_without_seams() {
+        let size = 16;
+        let left = ChunkLightData::new(ChunkPos::new(0, 0, 0), size);
+        let right = ChunkLightData::new(ChunkPos::new(1, 0, 0), size);
+        // One block from the +X face of the left chunk
+        let torch = emission_rgb(15, [1.0, 0.6, 0.2]);
+        let blocks = TestBlocks {
+            lights: HashMap::from([(VoxelPos::new(14, 8, 8), torch)]),
+        };
+
+        BlockLightCalculator::recompute_chunk(&left, &blocks);
+        BlockLightCalculator::recompute_chunk(&right, &blocks);
+
+        // Across the border the level keeps falling one per block
+        let last_left = BlockLightCalculator::block_light_at(&left, 15, 8, 8);
+        let first_right = BlockLightCalculator::block_light_at(&right, 0, 8, 8);
+        assert_eq!(last_left[0], torch[0] - LIGHT_FALLOFF);
+        assert_eq!(first_right[0], torch[0] - 2 * LIGHT_FALLOFF);
+        assert_eq!(
+            BlockLightCalculator::block_light_at(&right, 5, 8, 8)[0],
+            torch[0] - 7 * LIGHT_FALLOFF
+        );
+
+        // Every face cell matches a flood over both chunks as one region
+        for z in 0..size {
+            for y in 0..size {
+                let torch_pos = VoxelPos::new(14, 8, 8);
+                let expected = |x: i32| {
+                    let distance = (x - torch_pos.x).abs()
+                        + (y as i32 - torch_pos.y).abs()
+                        + (z as i32 - torch_pos.z).abs();
+                    torch.map(|channel| channel.saturating_sub(distance as u8 * LIGHT_FALLOFF))
+                };
+                assert_eq!(
+                    BlockLightCalculator::block_light_at(&left, 15, y, z),
+                    expected(15)
+                );
+                assert_eq!(
+                    BlockLightCalculator::block_light_at(&right, 0, y, z),
+                    expected(16)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunks_in_reach_include_neighbours_near_a_border() {
+        let near_edge = VoxelPos::new(14, 8, 8);
+        let chunks = BlockLightCalculator::chunks_in_reach(near_edge, near_edge, 16);
+
+        // 14 blocks either way reaches x = 0..=28, y and z = -6..=22
+        assert!(chunks.contains(&ChunkPos::new(0, 0, 0)));
+        assert!(chunks.contains(&ChunkPos::new(1, 0, 0)));
+        assert!(chunks.contains(&ChunkPos::new(0, -1, 1)));
+        assert!(!chunks.contains(&ChunkPos::new(-1, 0, 0)));
+        assert_eq!(chunks.len(), 2 * 3 * 3);
+    }
+}
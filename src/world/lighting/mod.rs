@@ -3,6 +3,7 @@
 //! Complete lighting system migrated from CPU to GPU for optimal performance.
 //! Provides time-of-day, light propagation, and skylight calculations.
 
+mod block_light;
 mod skylight;
 mod time_of_day;
 
@@ -12,6 +13,9 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::Duration;
 
+pub use block_light::{
+    emission_rgb, pack_block_light, unpack_block_light, BlockLightCalculator, BLOCK_LIGHT_REACH,
+};
 pub use skylight::SkylightCalculator;
 pub use time_of_day::*;
 
@@ -29,36 +33,54 @@ pub enum LightType {
 pub struct LightLevel {
     /// Skylight level (0-15)
     pub sky: u8,
-    /// Block light level (0-15)
-    pub block: u8,
+    /// Block light level per color channel, red, green, blue (0-15 each)
+    pub block: [u8; 3],
 }
 
 impl LightLevel {
+    /// Create a light level with white block light
     pub fn new(sky: u8, block: u8) -> Self {
+        let block = block.min(15);
+        Self::with_color(sky, [block; 3])
+    }
+
+    /// Create a light level with colored block light
+    pub fn with_color(sky: u8, block: [u8; 3]) -> Self {
         Self {
             sky: sky.min(15),
-            block: block.min(15),
+            block: block.map(|channel| channel.min(15)),
         }
     }
 
+    /// Brightest block light channel
+    pub fn block_level(&self) -> u8 {
+        self.block[0].max(self.block[1]).max(self.block[2])
+    }
+
     /// Get the maximum light level from either source
     pub fn max_light(&self) -> u8 {
-        self.sky.max(self.block)
+        self.sky.max(self.block_level())
     }
 
     /// Get combined light level for rendering
     pub fn combined(&self) -> u8 {
-        self.sky.max(self.block)
+        self.sky.max(self.block_level())
     }
 
     /// Create a dark light level
     pub fn dark() -> Self {
-        Self { sky: 0, block: 0 }
+        Self {
+            sky: 0,
+            block: [0; 3],
+        }
     }
 
     /// Create a fully lit skylight level
     pub fn full_sky() -> Self {
-        Self { sky: 15, block: 0 }
+        Self {
+            sky: 15,
+            block: [0; 3],
+        }
     }
 }
 
@@ -86,6 +108,8 @@ pub struct LightingStats {
 pub struct ChunkLightData {
     pub chunk_pos: ChunkPos,
     pub light_data: Arc<RwLock<Vec<u8>>>, // Packed light data
+    /// Block light color per voxel, packed by `pack_block_light`
+    pub block_light_rgb: Arc<RwLock<Vec<u16>>>,
     pub size: u32,
 }
 
@@ -95,6 +119,7 @@ impl ChunkLightData {
         Self {
             chunk_pos,
             light_data: Arc::new(RwLock::new(vec![0; total_size])),
+            block_light_rgb: Arc::new(RwLock::new(vec![0; total_size])),
             size,
         }
     }
@@ -104,4 +129,10 @@ impl ChunkLightData {
 pub trait BlockProvider: Send + Sync {
     fn get_block(&self, pos: VoxelPos) -> BlockId;
     fn is_transparent(&self, pos: VoxelPos) -> bool;
+
+    /// Block light emitted at `pos` per color channel (0-15 each)
+    fn light_emission(&self, pos: VoxelPos) -> [u8; 3] {
+        let _ = pos;
+        [0; 3]
+    }
 }
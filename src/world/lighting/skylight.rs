@@ -3,6 +3,8 @@
 //! This module provides skylight propagation and column updates
 //! compatible with the GPU-first architecture.
 
+use super::{BlockProvider, ChunkLightData};
+use crate::constants::lighting::{LIGHT_FALLOFF, MAX_LIGHT_LEVEL};
 use crate::world::core::{BlockId, VoxelPos};
 use crate::world::{functional_wrapper, interfaces::WorldInterface};
 
@@ -40,6 +42,103 @@ impl SkylightCalculator {
         }
     }
 
+    /// Recompute top-down skylight for one (x, z) column of a chunk
+    ///
+    /// `ChunkLightData` holds light but no blocks, hence `blocks`, and the
+    /// skylight entering the top of the column lives in the chunk above,
+    /// hence `light_from_above` (`MAX_LIGHT_LEVEL` under open sky). Skylight
+    /// is stored in the high nibble of each `ChunkLightData` byte, block
+    /// light in the low nibble.
+    ///
+    /// Only the top-down pass runs here; the caller owns horizontal spread.
+    /// When this returns a change, the columns from `columns_in_falloff`
+    /// need their light spread again.
+    ///
+    /// Returns the inclusive local Y range whose skylight changed, so block
+    /// light propagation can be scoped to it, or `None` if nothing changed.
+    pub fn recompute_column(
+        chunk: &ChunkLightData,
+        blocks: &dyn BlockProvider,
+        x: u32,
+        z: u32,
+        light_from_above: u8,
+    ) -> Option<(u32, u32)> {
+        let size = chunk.size;
+        if x >= size || z >= size {
+            return None;
+        }
+
+        let base_x = chunk.chunk_pos.x * size as i32;
+        let base_y = chunk.chunk_pos.y * size as i32;
+        let base_z = chunk.chunk_pos.z * size as i32;
+
+        let mut light_data = chunk.light_data.write();
+        let mut current_light = light_from_above.min(MAX_LIGHT_LEVEL);
+        let mut changed: Option<(u32, u32)> = None;
+
+        for y in (0..size).rev() {
+            let pos = VoxelPos::new(base_x + x as i32, base_y + y as i32, base_z + z as i32);
+            let block = blocks.get_block(pos);
+
+            if block == BlockId::AIR {
+                // Air passes skylight through unchanged
+            } else if blocks.is_transparent(pos) {
+                current_light = current_light.saturating_sub(LIGHT_FALLOFF);
+            } else {
+                current_light = 0;
+            }
+
+            let index = (x + y * size + z * size * size) as usize;
+            let Some(packed) = light_data.get_mut(index) else {
+                continue;
+            };
+            let new_packed = (current_light << 4) | (*packed & 0x0F);
+            if new_packed != *packed {
+                *packed = new_packed;
+                changed = Some(match changed {
+                    Some((_, max_y)) => (y, max_y),
+                    None => (y, y),
+                });
+            }
+        }
+
+        changed
+    }
+
+    /// Columns of a chunk close enough to (x, z) for sideways skylight to reach
+    ///
+    /// These are the columns within the falloff radius, by Manhattan
+    /// distance, excluding (x, z) itself.
+    pub fn columns_in_falloff(chunk_size: u32, x: u32, z: u32) -> Vec<(u32, u32)> {
+        let reach = ((MAX_LIGHT_LEVEL - 1) / LIGHT_FALLOFF) as i32;
+        let (x, z) = (x as i32, z as i32);
+        let mut columns = Vec::new();
+        for dz in -reach..=reach {
+            let span = reach - dz.abs();
+            for dx in -span..=span {
+                let (nx, nz) = (x + dx, z + dz);
+                let inside =
+                    (0..chunk_size as i32).contains(&nx) && (0..chunk_size as i32).contains(&nz);
+                if inside && (dx, dz) != (0, 0) {
+                    columns.push((nx as u32, nz as u32));
+                }
+            }
+        }
+        columns
+    }
+
+    /// Read the skylight level stored for a local position
+    pub fn sky_light_at(chunk: &ChunkLightData, x: u32, y: u32, z: u32) -> u8 {
+        let size = chunk.size;
+        let index = (x + y * size + z * size * size) as usize;
+        chunk
+            .light_data
+            .read()
+            .get(index)
+            .map(|packed| packed >> 4)
+            .unwrap_or(0)
+    }
+
     /// Update skylight for a specific position and its neighbors
     pub fn update_at_position<W: WorldInterface>(world: &mut W, pos: VoxelPos) {
         // Update the column containing this position
@@ -71,3 +170,67 @@ fn is_transparent<W: WorldInterface>(world: &W, block_id: BlockId) -> bool {
     // Most blocks are opaque
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::core::ChunkPos;
+    use std::collections::HashSet;
+
+    struct TestBlocks {
+        solid: HashSet<VoxelPos>,
+    }
+
+    impl BlockProvider for TestBlocks {
+        fn get_block(&self, pos: VoxelPos) -> BlockId {
+            if self.solid.contains(&pos) {
+                BlockId::STONE
+            } else {
+                BlockId::AIR
+            }
+        }
+
+        fn is_transparent(&self, pos: VoxelPos) -> bool {
+            !self.solid.contains(&pos)
+        }
+    }
+
+    #[test]
+    fn test_placed_block_darkens_column_below() {
+        let chunk = ChunkLightData::new(ChunkPos::new(0, 0, 0), 16);
+        let mut blocks = TestBlocks {
+            solid: HashSet::new(),
+        };
+
+        let lit = SkylightCalculator::recompute_column(&chunk, &blocks, 3, 4, MAX_LIGHT_LEVEL);
+        assert_eq!(lit, Some((0, 15)));
+        assert_eq!(
+            SkylightCalculator::sky_light_at(&chunk, 3, 0, 4),
+            MAX_LIGHT_LEVEL
+        );
+
+        blocks.solid.insert(VoxelPos::new(3, 10, 4));
+        let changed = SkylightCalculator::recompute_column(&chunk, &blocks, 3, 4, MAX_LIGHT_LEVEL);
+
+        assert_eq!(changed, Some((0, 10)));
+        assert_eq!(
+            SkylightCalculator::sky_light_at(&chunk, 3, 11, 4),
+            MAX_LIGHT_LEVEL
+        );
+        assert_eq!(SkylightCalculator::sky_light_at(&chunk, 3, 9, 4), 0);
+    }
+
+    #[test]
+    fn test_columns_in_falloff_stay_within_reach_and_chunk() {
+        let reach = ((MAX_LIGHT_LEVEL - 1) / LIGHT_FALLOFF) as u32;
+        let columns = SkylightCalculator::columns_in_falloff(16, 3, 4);
+
+        assert!(columns.contains(&(4, 4)));
+        assert!(columns.contains(&(0, 0)));
+        assert!(!columns.contains(&(3, 4)));
+        assert!(!columns.contains(&(15, 15)));
+        assert!(columns
+            .iter()
+            .all(|&(x, z)| x < 16 && z < 16 && x.abs_diff(3) + z.abs_diff(4) <= reach));
+    }
+}
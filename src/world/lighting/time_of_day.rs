@@ -111,6 +111,77 @@ pub fn calculate_sun_color(time: &TimeOfDayData) -> [f32; 3] {
     }
 }
 
+/// Night sky, the horizon glow at dawn and dusk, and the midday sky
+const NIGHT_SKY_COLOR: [f32; 3] = [0.02, 0.02, 0.08];
+const HORIZON_SKY_COLOR: [f32; 3] = [0.9, 0.5, 0.3];
+const DAY_SKY_COLOR: [f32; 3] = [0.5, 0.8, 1.0];
+
+/// Ambient intensity left over at midnight
+const MIDNIGHT_AMBIENT: f32 = 0.02;
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn mix_color(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
+
+/// Sky color, ambient intensity (0.0 - 1.0) and sun direction for a time
+/// Pure function - unlike the step-wise helpers above, every output changes
+/// continuously, so dawn and dusk fade instead of snapping. The sun keeps
+/// turning below the horizon at night; its direction points towards the sun.
+pub fn compute_ambient(time: &TimeOfDayData) -> ([f32; 3], f32, Vector3<f32>) {
+    // Sunrise at 6:00, zenith at 12:00, sunset at 18:00, nadir at midnight
+    let angle = (time.hours - 6.0) / 24.0 * std::f32::consts::TAU;
+    let sun_direction = Vector3::new(angle.cos(), angle.sin(), 0.0);
+    let elevation = sun_direction.y;
+
+    let daylight = smoothstep(-0.2, 1.0, elevation);
+    let ambient_intensity = MIDNIGHT_AMBIENT + (1.0 - MIDNIGHT_AMBIENT) * daylight;
+
+    // Blue sky fades in as the sun climbs; the glow peaks with the sun on the horizon
+    let blue = smoothstep(-0.2, 0.4, elevation);
+    let base = mix_color(NIGHT_SKY_COLOR, DAY_SKY_COLOR, blue);
+    let glow = 1.0 - (elevation.abs() / 0.25).min(1.0);
+    let sky_color = mix_color(base, HORIZON_SKY_COLOR, glow * 0.7);
+
+    (sky_color, ambient_intensity, sun_direction)
+}
+
+/// Ambient lighting in the layout the shaders read (std140 compatible)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct AmbientUniform {
+    pub sky_color: [f32; 3],
+    pub ambient_intensity: f32,
+    pub sun_direction: [f32; 3],
+    pub _padding: f32,
+}
+
+/// Build the ambient uniform for a time of day
+/// Pure function - upload the result whenever the cycle advances
+pub fn create_ambient_uniform(time: &TimeOfDayData) -> AmbientUniform {
+    let (sky_color, ambient_intensity, sun_direction) = compute_ambient(time);
+    AmbientUniform {
+        sky_color,
+        ambient_intensity,
+        sun_direction: sun_direction.into(),
+        _padding: 0.0,
+    }
+}
+
+/// Set the time from a fraction of a day (0.0 midnight, 0.5 noon)
+/// Function - transforms time data; fractions outside 0..1 wrap around
+pub fn set_time(time: &mut TimeOfDayData, fraction: f32) {
+    time.hours = fraction.rem_euclid(1.0) * 24.0;
+}
+
 /// Advance time by delta seconds
 /// Function - transforms time data by advancing it
 pub fn advance_time(time: &mut TimeOfDayData, delta_seconds: f32, day_length_seconds: f32) {
@@ -193,3 +264,37 @@ pub fn calculate_global_light_level(cycle: &DayNightCycleData) -> u8 {
 pub fn set_time_scale(cycle: &mut DayNightCycleData, scale: f32) {
     cycle.time_scale = scale.max(0.0);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ambient_dark_at_midnight_and_peaks_at_noon() {
+        let mut time = midnight_time();
+        let (night_sky, midnight, moon_side) = compute_ambient(&time);
+        assert!(midnight < 0.05);
+        assert!(moon_side.y < -0.99);
+        assert!(night_sky.iter().all(|c| *c < 0.1));
+
+        let (day_sky, noon, sun) = compute_ambient(&noon_time());
+        assert!((noon - 1.0).abs() < 1e-5);
+        assert!(sun.y > 0.99);
+        assert!(day_sky[2] > day_sky[0]);
+
+        // Brightness climbs through the morning and never beats noon
+        let mut previous = midnight;
+        for step in 1..=48 {
+            set_time(&mut time, step as f32 / 96.0);
+            let (_, intensity, _) = compute_ambient(&time);
+            assert!(intensity >= previous && intensity <= noon);
+            previous = intensity;
+        }
+
+        // Dawn is warmer than midday
+        set_time(&mut time, 0.25);
+        let (dawn_sky, dawn, _) = compute_ambient(&time);
+        assert!(dawn > midnight && dawn < noon);
+        assert!(dawn_sky[0] > dawn_sky[2]);
+    }
+}
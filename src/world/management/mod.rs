@@ -13,7 +13,9 @@ pub use chunk_manager::{
     ChunkManagerConfig, ChunkManagerInterface, ChunkStats, UnifiedChunkManager,
 };
 pub use parallel_world::{ParallelWorld, ParallelWorldConfig, SpawnFinder};
-pub use performance::{GenerationStats, PerformanceMonitor, WorldPerformanceMetrics};
+pub use performance::{
+    GenerationStats, MonitorConfig, PerformanceMonitor, WorldPerformanceMetrics,
+};
 pub use world_manager::{UnifiedWorldManager, WorldError, WorldManagerConfig};
 
 /// Backend selection for unified managers
@@ -21,6 +21,10 @@ pub struct GenerationStats {
     pub backend: String,
     pub cache_hits: u64,
     pub cache_misses: u64,
+    /// Batches recorded through `record_generation_batch`
+    pub batches_generated: u64,
+    pub avg_batch_size: f64,
+    pub peak_batch_size: usize,
 }
 
 /// Storage performance statistics
@@ -59,6 +63,7 @@ pub struct PerformanceMonitor {
     start_time: Instant,
     last_update: Instant,
     generation_times: VecDeque<Duration>,
+    batch_sizes: VecDeque<usize>,
     compute_times: VecDeque<Duration>,
     frame_times: VecDeque<Duration>,
     metrics: WorldPerformanceMetrics,
@@ -74,6 +79,7 @@ impl PerformanceMonitor {
             start_time: now,
             last_update: now,
             generation_times: VecDeque::with_capacity(config.sample_size),
+            batch_sizes: VecDeque::with_capacity(config.sample_size),
             compute_times: VecDeque::with_capacity(config.sample_size),
             frame_times: VecDeque::with_capacity(config.sample_size),
             metrics: WorldPerformanceMetrics::default(),
@@ -91,6 +97,29 @@ impl PerformanceMonitor {
         self.update_generation_stats();
     }
 
+    /// Record a batch of chunks generated together in `duration`
+    ///
+    /// Each chunk is counted with its share of the batch time.
+    pub fn record_generation_batch(&mut self, batch_size: usize, duration: Duration) {
+        if batch_size == 0 {
+            return;
+        }
+
+        self.batch_sizes.push_back(batch_size);
+        if self.batch_sizes.len() > self.config.sample_size {
+            self.batch_sizes.pop_front();
+        }
+
+        let stats = &mut self.metrics.generation_stats;
+        stats.chunks_generated += batch_size as u64;
+        stats.batches_generated += 1;
+        stats.peak_batch_size = stats.peak_batch_size.max(batch_size);
+        stats.avg_batch_size =
+            self.batch_sizes.iter().sum::<usize>() as f64 / self.batch_sizes.len() as f64;
+
+        self.record_generation_time(duration / batch_size as u32);
+    }
+
     /// Record a compute pass time
     pub fn record_compute_time(&mut self, duration: Duration) {
         self.compute_times.push_back(duration);
@@ -206,6 +235,7 @@ impl PerformanceMonitor {
              ========================\n\
              Uptime: {:.2}s\n\
              Generation: {:.2}ms avg, {:.2}ms peak ({})\n\
+             Batches: {} ({:.1} chunks avg, {} peak)\n\
              Compute: {:.2}ms avg, {} passes\n\
              Memory: {:.1}MB / {:.1}MB peak\n\
              Chunks: {} loaded\n\
@@ -214,6 +244,9 @@ impl PerformanceMonitor {
             self.metrics.generation_stats.avg_generation_time_ms,
             self.metrics.generation_stats.peak_generation_time_ms,
             self.metrics.generation_stats.backend,
+            self.metrics.generation_stats.batches_generated,
+            self.metrics.generation_stats.avg_batch_size,
+            self.metrics.generation_stats.peak_batch_size,
             self.metrics.compute_stats.avg_compute_time_ms,
             self.metrics.compute_stats.compute_passes,
             self.metrics.storage_stats.memory_usage_mb,
@@ -263,6 +296,9 @@ impl Default for GenerationStats {
             backend: "Unknown".to_string(),
             cache_hits: 0,
             cache_misses: 0,
+            batches_generated: 0,
+            avg_batch_size: 0.0,
+            peak_batch_size: 0,
         }
     }
 }
@@ -324,4 +360,19 @@ mod tests {
         assert_eq!(metrics.generation_stats.avg_generation_time_ms, 15.0);
         assert_eq!(metrics.generation_stats.peak_generation_time_ms, 20.0);
     }
+
+    #[test]
+    fn test_generation_batch_recording() {
+        let mut monitor = PerformanceMonitor::new(MonitorConfig::default());
+        monitor.record_generation_batch(8, Duration::from_millis(40));
+        monitor.record_generation_batch(2, Duration::from_millis(20));
+
+        let stats = &monitor.metrics().generation_stats;
+        assert_eq!(stats.chunks_generated, 10);
+        assert_eq!(stats.batches_generated, 2);
+        assert_eq!(stats.avg_batch_size, 5.0);
+        assert_eq!(stats.peak_batch_size, 8);
+        // Per-chunk times: 5ms and 10ms
+        assert_eq!(stats.avg_generation_time_ms, 7.5);
+    }
 }
@@ -19,11 +19,14 @@
 //! 3. **DOP architecture**: Data-oriented design throughout
 //! 4. **Zero-copy**: Minimize CPU/GPU transfers
 
+pub mod block_tick;
+pub mod block_updates;
 pub mod blocks;
 pub mod compute;
 pub mod core;
 pub mod data_types;
 pub mod dop_bridge;
+pub mod edit_history;
 pub mod error;
 pub mod functional_wrapper;
 pub mod generation;
@@ -36,8 +39,8 @@ pub mod world_operations;
 
 // Re-export core types for convenience
 pub use core::{
-    BlockFace, BlockId, BlockRegistry, ChunkPos, PhysicsProperties, Ray, RaycastHit,
-    RenderData, VoxelPos,
+    copy_region, paste_region, BlockFace, BlockId, BlockRegistry, ChunkPos, ClipboardRotation,
+    PhysicsProperties, Ray, RaycastHit, RenderData, VoxelClipboard, VoxelPos,
 };
 
 // Re-export storage systems
@@ -54,7 +57,11 @@ pub use storage::{
 
 // Re-export generation systems
 pub use generation::{
+    BiomeDefinition,
+    BiomeMap,
     CaveGenerator,
+    // CPU-only flat generator
+    FlatWorldGenerator,
     OreGenerator,
     // GPU generators
     TerrainGeneratorSOA,
@@ -109,12 +116,25 @@ pub use blocks::register_basic_blocks;
 
 // Re-export lighting system
 pub use lighting::{
-    DayNightCycleData, LightLevel, LightType, LightUpdate, LightingStats, SkylightCalculator,
-    TimeOfDayData,
+    AmbientUniform, BlockLightCalculator, DayNightCycleData, LightLevel, LightType, LightUpdate,
+    LightingStats, SkylightCalculator, TimeOfDayData,
+};
+
+// Re-export block ticks
+pub use block_tick::{BlockAccess, BlockTickHandler, BlockTickScheduler, TickRequests};
+pub use block_updates::{NeighborChangedHandler, NeighborUpdater};
+
+// Re-export edit history
+pub use edit_history::{
+    create_edit_history, record_block_change, record_edit_step, redo_edit, undo_edit, BlockChange,
+    EditHistory, EditStep,
 };
 
 // Re-export weather system
-pub use weather_manager::{WeatherManager, WeatherZone};
+pub use weather_manager::{
+    blend_weather_data, ActiveWeatherTransition, WeatherManager, WeatherTransitionUpdate,
+    WeatherZone,
+};
 
 /// Helper function to convert voxel position to chunk position
 /// Following DOP principles - pure function that transforms data
@@ -6,9 +6,12 @@ use crate::morton::morton_encode;
 use crate::world::core::ChunkPos;
 use bytemuck::{Pod, Zeroable};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Instant;
 
+/// Size of one voxel's packed RGB block light in the block light buffer
+const BLOCK_LIGHT_BYTES_PER_VOXEL: u64 = std::mem::size_of::<u16>() as u64;
+
 /// Packed voxel data format for GPU storage
 /// Uses 32 bits per voxel:
 /// - Bits 0-15: Block ID (64K block types)
@@ -84,6 +87,10 @@ pub struct WorldBuffer {
     /// Chunk metadata buffer (loaded/generated flags, timestamps, etc)
     metadata_buffer: wgpu::Buffer,
 
+    /// Colored block light per voxel, packed by `lighting::pack_block_light`
+    /// into one u16 each, in the same slot layout as the voxel buffer
+    block_light_buffer: wgpu::Buffer,
+
     /// Staging buffer for CPU->GPU uploads (if needed)
     staging_buffer: Option<wgpu::Buffer>,
 
@@ -171,6 +178,14 @@ impl WorldBuffer {
             mapped_at_creation: false,
         });
 
+        // Block light buffer, two bytes per voxel
+        let block_light_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("World Block Light Buffer"),
+            size: total_voxels * BLOCK_LIGHT_BYTES_PER_VOXEL,
+            usage: usage::STORAGE,
+            mapped_at_creation: false,
+        });
+
         // Optional staging buffer for uploads
         let staging_buffer = if desc.enable_readback {
             Some(device.create_buffer(&wgpu::BufferDescriptor {
@@ -222,6 +237,7 @@ impl WorldBuffer {
             device,
             voxel_buffer,
             metadata_buffer,
+            block_light_buffer,
             staging_buffer,
             bind_group,
             bind_group_layout,
@@ -253,6 +269,14 @@ impl WorldBuffer {
         &self.metadata_buffer
     }
 
+    /// Get the block light buffer (for custom bind groups)
+    ///
+    /// Each voxel takes 16 bits at `slot * VOXELS_PER_CHUNK + local index`,
+    /// so a shader reading it as `array<u32>` finds two voxels per word.
+    pub fn block_light_buffer(&self) -> &wgpu::Buffer {
+        &self.block_light_buffer
+    }
+
     /// Get the view distance
     pub fn view_distance(&self) -> u32 {
         self.view_distance
@@ -263,6 +287,11 @@ impl WorldBuffer {
         self.max_chunks
     }
 
+    /// Whether `read_chunk` can copy chunks back to the CPU
+    pub fn readback_enabled(&self) -> bool {
+        self.staging_buffer.is_some()
+    }
+
     /// Get or allocate a buffer slot for a chunk position
     /// CRITICAL: Prevents slot collisions that cause GPU readback failures
     pub fn get_chunk_slot(&mut self, chunk_pos: ChunkPos) -> u32 {
@@ -329,6 +358,31 @@ impl WorldBuffer {
         }
     }
 
+    /// Slot a chunk occupies, without allocating one for it
+    pub fn chunk_slot(&self, chunk_pos: ChunkPos) -> Option<u32> {
+        self.chunk_slots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&chunk_pos)
+            .copied()
+    }
+
+    /// Smallest chunk box, as inclusive (min, max), holding every chunk with a slot
+    pub fn resident_chunk_bounds(&self) -> Option<(ChunkPos, ChunkPos)> {
+        let chunk_slots = self
+            .chunk_slots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut positions = chunk_slots.keys();
+        let first = *positions.next()?;
+        Some(positions.fold((first, first), |(min, max), pos| {
+            (
+                ChunkPos::new(min.x.min(pos.x), min.y.min(pos.y), min.z.min(pos.z)),
+                ChunkPos::new(max.x.max(pos.x), max.y.max(pos.y), max.z.max(pos.z)),
+            )
+        }))
+    }
+
     /// Calculate buffer offset for a chunk slot
     pub fn slot_offset(&self, slot: u32) -> u64 {
         calculations::chunk_slot_offset(slot)
@@ -394,6 +448,42 @@ impl WorldBuffer {
         );
     }
 
+    /// Upload a chunk's packed RGB block light next to its voxels
+    ///
+    /// `block_light` is `ChunkLightData::block_light_rgb` of a full-size chunk.
+    /// Returns false if the chunk has no slot, i.e. was never uploaded.
+    pub fn upload_block_light(
+        &self,
+        queue: &wgpu::Queue,
+        chunk_pos: ChunkPos,
+        block_light: &[u16],
+    ) -> bool {
+        if block_light.len() != VOXELS_PER_CHUNK as usize {
+            log::error!(
+                "[WORLD_BUFFER] Invalid block light count for chunk {:?}: expected {}, got {}",
+                chunk_pos,
+                VOXELS_PER_CHUNK,
+                block_light.len()
+            );
+            return false;
+        }
+        let Some(slot) = self.chunk_slot(chunk_pos) else {
+            log::warn!(
+                "[WORLD_BUFFER] Block light for chunk {:?} has no slot to go to",
+                chunk_pos
+            );
+            return false;
+        };
+
+        let offset = slot as u64 * VOXELS_PER_CHUNK as u64 * BLOCK_LIGHT_BYTES_PER_VOXEL;
+        queue.write_buffer(
+            &self.block_light_buffer,
+            offset,
+            bytemuck::cast_slice(block_light),
+        );
+        true
+    }
+
     /// Clear a chunk to air
     pub fn clear_chunk(&mut self, encoder: &mut wgpu::CommandEncoder, chunk_pos: ChunkPos) {
         let start = Instant::now();
@@ -5,6 +5,7 @@
 
 use crate::gpu::types::terrain::TerrainParams;
 use crate::constants::weather::*;
+use crate::world::compute::{WeatherData, WeatherTransition};
 use crate::world::core::ChunkPos;
 
 /// Weather zone information
@@ -22,6 +23,28 @@ pub struct WeatherZone {
     pub temperature: f32,
 }
 
+/// An in-progress blend between two weather states
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveWeatherTransition {
+    /// Zone index being transitioned (`None` = global weather)
+    pub zone: Option<usize>,
+    pub from: WeatherData,
+    pub to: WeatherData,
+    pub duration_ticks: u32,
+    pub elapsed_ticks: u32,
+}
+
+/// Blended weather for one zone after a tick, ready for GPU upload
+#[derive(Debug, Clone, Copy)]
+pub struct WeatherTransitionUpdate {
+    /// Zone index (`None` = global weather)
+    pub zone: Option<usize>,
+    /// GPU transition record with `current` set to the blended weather
+    pub transition: WeatherTransition,
+    /// The transition reached its target this tick and was removed
+    pub finished: bool,
+}
+
 /// Weather manager for world generation
 pub struct WeatherManager {
     /// Current global weather
@@ -30,6 +53,8 @@ pub struct WeatherManager {
     pub base_temperature: f32,
     /// Active weather zones
     pub zones: Vec<WeatherZone>,
+    /// In-progress transitions, at most one per zone
+    pub transitions: Vec<ActiveWeatherTransition>,
 }
 
 impl WeatherManager {
@@ -40,6 +65,7 @@ impl WeatherManager {
             global_intensity: INTENSITY_NONE,
             base_temperature: 20.0, // 20°C default
             zones: Vec::new(),
+            transitions: Vec::new(),
         }
     }
 
@@ -55,6 +81,82 @@ impl WeatherManager {
         self.zones.push(zone);
     }
 
+    /// Start blending `zone` (or the global weather for `None`) from one state to another
+    ///
+    /// Replaces any transition already running for the same zone. Each zone
+    /// transitions independently, so a storm can roll across zones one by one.
+    pub fn begin_transition(
+        &mut self,
+        zone: Option<usize>,
+        from: WeatherData,
+        to: WeatherData,
+        duration_ticks: u32,
+    ) {
+        self.transitions.retain(|t| t.zone != zone);
+        self.transitions.push(ActiveWeatherTransition {
+            zone,
+            from,
+            to,
+            duration_ticks,
+            elapsed_ticks: 0,
+        });
+    }
+
+    /// Advance every transition by one tick
+    ///
+    /// Returns the blended weather for each zone. Nothing is uploaded here,
+    /// since only the caller knows which GPU region a zone covers: each update
+    /// must go to `WeatherGpu::upload_region_transition`, or the GPU keeps
+    /// the old weather. Zone weather type and intensity follow the blend; on
+    /// the final tick the target is applied exactly.
+    #[must_use = "upload each update with `WeatherGpu::upload_region_transition`"]
+    pub fn tick_transitions(&mut self) -> Vec<WeatherTransitionUpdate> {
+        let mut updates = Vec::with_capacity(self.transitions.len());
+
+        for transition in &mut self.transitions {
+            transition.elapsed_ticks =
+                (transition.elapsed_ticks + 1).min(transition.duration_ticks);
+            let t = if transition.duration_ticks == 0 {
+                1.0
+            } else {
+                transition.elapsed_ticks as f32 / transition.duration_ticks as f32
+            };
+            let current = blend_weather_data(&transition.from, &transition.to, t);
+
+            updates.push(WeatherTransitionUpdate {
+                zone: transition.zone,
+                transition: WeatherTransition {
+                    current,
+                    target_weather: transition.to,
+                    progress: (t * u16::MAX as f32) as u16,
+                    speed: 0,
+                    time_remaining: transition.duration_ticks - transition.elapsed_ticks,
+                },
+                finished: transition.elapsed_ticks >= transition.duration_ticks,
+            });
+        }
+
+        for update in &updates {
+            let (weather_type, intensity) =
+                unpack_weather(update.transition.current.weather_type_intensity);
+            match update.zone {
+                Some(index) => {
+                    if let Some(zone) = self.zones.get_mut(index) {
+                        zone.weather_type = weather_type;
+                        zone.intensity = intensity;
+                    }
+                }
+                None => {
+                    self.global_weather = weather_type;
+                    self.global_intensity = intensity;
+                }
+            }
+        }
+
+        self.transitions.retain(|t| t.elapsed_ticks < t.duration_ticks);
+        updates
+    }
+
     /// Get weather parameters for a specific chunk position
     pub fn get_weather_at(&self, pos: ChunkPos) -> (u32, u32, f32) {
         // Check if position is within any weather zone
@@ -177,6 +279,53 @@ impl WeatherManager {
     }
 }
 
+/// Interpolate between two weather states (`t` in 0.0..=1.0)
+///
+/// Precipitation, visibility (fog density), wind, temperature and humidity are
+/// blended linearly; wind direction takes the shortest arc. The weather type
+/// switches at the midpoint while intensity is blended. `t >= 1.0` returns `to`.
+pub fn blend_weather_data(from: &WeatherData, to: &WeatherData, t: f32) -> WeatherData {
+    if t >= 1.0 {
+        return *to;
+    }
+    let t = t.max(0.0);
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+    let (from_type, from_intensity) = unpack_weather(from.weather_type_intensity);
+    let (to_type, to_intensity) = unpack_weather(to.weather_type_intensity);
+    let weather_type = if t < 0.5 { from_type } else { to_type };
+    let intensity = lerp(from_intensity as f32, to_intensity as f32).round() as u32;
+
+    let mut direction_delta = to.wind_direction as f32 - from.wind_direction as f32;
+    if direction_delta > 180.0 {
+        direction_delta -= 360.0;
+    } else if direction_delta < -180.0 {
+        direction_delta += 360.0;
+    }
+    let wind_direction = (from.wind_direction as f32 + direction_delta * t).rem_euclid(360.0);
+
+    WeatherData {
+        weather_type_intensity: pack_weather(weather_type, intensity),
+        temperature: lerp(from.temperature as f32, to.temperature as f32).round() as i16,
+        humidity: lerp(from.humidity as f32, to.humidity as f32).round() as u16,
+        wind_speed: lerp(from.wind_speed as f32, to.wind_speed as f32).round() as u16,
+        wind_direction: wind_direction.round() as u16 % 360,
+        visibility: lerp(from.visibility as f32, to.visibility as f32).round() as u16,
+        precipitation_rate: lerp(from.precipitation_rate as f32, to.precipitation_rate as f32)
+            .round() as u16,
+    }
+}
+
+/// Split `WeatherData::weather_type_intensity` into (type, intensity)
+fn unpack_weather(packed: u32) -> (u32, u32) {
+    (packed & 0xFF, (packed >> 8) & 0xFF)
+}
+
+/// Pack weather type (bits 0-7) and intensity (bits 8-15)
+fn pack_weather(weather_type: u32, intensity: u32) -> u32 {
+    (weather_type & 0xFF) | ((intensity & 0xFF) << 8)
+}
+
 impl Default for WeatherManager {
     fn default() -> Self {
         Self::new()
@@ -217,4 +366,55 @@ mod tests {
         assert_eq!(zone_intensity, INTENSITY_EXTREME);
         assert_eq!(zone_temp, -20.0);
     }
+
+    #[test]
+    fn test_zone_transition_reaches_target() {
+        let mut manager = WeatherManager::new();
+        manager.add_zone(WeatherZone {
+            center: ChunkPos::new(0, 0, 0),
+            radius: 4,
+            weather_type: WEATHER_CLEAR,
+            intensity: INTENSITY_NONE,
+            temperature: 20.0,
+        });
+
+        let from = WeatherData::clear();
+        let to = WeatherData {
+            weather_type_intensity: pack_weather(WEATHER_STORM, INTENSITY_HEAVY),
+            wind_speed: 250,
+            visibility: 200,
+            precipitation_rate: 800,
+            ..WeatherData::clear()
+        };
+        manager.begin_transition(Some(0), from, to, 4);
+
+        let first = manager.tick_transitions();
+        assert!(!first[0].finished);
+        let halfway = manager.tick_transitions()[0].transition.current;
+        assert_eq!(halfway.precipitation_rate, 400);
+        assert_eq!(halfway.visibility, 600);
+        assert_eq!(manager.zones[0].weather_type, WEATHER_STORM);
+
+        let third = manager.tick_transitions();
+        assert_eq!(third[0].transition.time_remaining, 1);
+        let last = manager.tick_transitions();
+        assert!(last[0].finished);
+        assert_eq!(last[0].transition.current.precipitation_rate, to.precipitation_rate);
+        assert_eq!(last[0].transition.current.wind_speed, to.wind_speed);
+        assert_eq!(manager.zones[0].intensity, INTENSITY_HEAVY);
+        assert!(manager.transitions.is_empty());
+    }
+
+    #[test]
+    fn test_wind_direction_takes_shortest_arc() {
+        let from = WeatherData {
+            wind_direction: 350,
+            ..WeatherData::clear()
+        };
+        let to = WeatherData {
+            wind_direction: 10,
+            ..WeatherData::clear()
+        };
+        assert_eq!(blend_weather_data(&from, &to, 0.5).wind_direction, 0);
+    }
 }
@@ -36,3 +36,38 @@ fn test_gpu_surface_workaround() {
     assert_eq!(hearth_engine::gpu::constants::CHUNK_SIZE, 50);
     println!("✓ Constants validated");
 }
+
+#[test]
+fn test_live_config_update() {
+    let mut config = EngineConfig {
+        render_distance: 2,
+        ..Default::default()
+    };
+
+    let wider = EngineConfig {
+        render_distance: 3,
+        ..Default::default()
+    };
+    let change = config
+        .apply_live_update(&wider)
+        .expect("render_distance 3 is valid");
+    assert_eq!(change.render_distance, Some((2, 3)));
+    assert_eq!(config.render_distance, 3);
+
+    // Invalid values are rejected and leave the running config alone
+    let too_far = EngineConfig {
+        render_distance: 10_000,
+        ..Default::default()
+    };
+    assert!(config.apply_live_update(&too_far).is_err());
+    assert_eq!(config.render_distance, 3);
+
+    let resized = EngineConfig {
+        chunk_size: config.chunk_size * 2,
+        ..Default::default()
+    };
+    let err = config
+        .apply_live_update(&resized)
+        .expect_err("chunk_size changes need a restart");
+    assert!(err.to_string().contains("restart"));
+}
@@ -0,0 +1,120 @@
+//! Cross-backend determinism: the same chunk generated on CPU and GPU must match voxel for voxel
+//!
+//! Only the terrain pass is compared. Water, snow, weather and custom block
+//! distributions exist only in the shader, so the GPU params switch them off.
+
+use hearth_engine::constants::core::CHUNK_SIZE;
+use hearth_engine::gpu::TerrainParams;
+use hearth_engine::world::core::{BlockId, ChunkPos};
+use hearth_engine::world::generation::{
+    generate_chunk_deterministic, hash_chunk_blocks, GenerationStages, GeneratorConfig,
+    TerrainGeneratorSOA,
+};
+use hearth_engine::world::storage::{WorldBuffer, WorldBufferDescriptor};
+use std::sync::Arc;
+
+const SEED: u32 = 12345;
+
+/// Request a device, or `None` when the machine has no usable adapter
+fn create_device() -> Option<(Arc<wgpu::Device>, Arc<wgpu::Queue>)> {
+    pollster::block_on(async {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            })
+            .await?;
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("Determinism Test Device"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::default(),
+                },
+                None,
+            )
+            .await
+            .ok()?;
+        Some((Arc::new(device), Arc::new(queue)))
+    })
+}
+
+/// Generate one chunk with the GPU terrain kernel and read its block ids back
+fn generate_gpu_blocks(
+    device: &Arc<wgpu::Device>,
+    queue: &Arc<wgpu::Queue>,
+    chunk_pos: ChunkPos,
+) -> Vec<BlockId> {
+    let generator = TerrainGeneratorSOA::new(device.clone(), queue.clone())
+        .expect("terrain generator should build");
+    generator
+        .update_params(&TerrainParams {
+            seed: SEED,
+            // Keep water out of the compared chunk
+            sea_level: 0.0,
+            num_distributions: 0,
+            weather_type_intensity: 0,
+            temperature: 200,
+            ..TerrainParams::default()
+        })
+        .expect("params should upload");
+
+    let mut world_buffer = WorldBuffer::new(
+        device.clone(),
+        &WorldBufferDescriptor {
+            view_distance: 1,
+            enable_atomics: true,
+            enable_readback: true,
+        },
+    );
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Determinism Test Encoder"),
+    });
+    generator
+        .generate_chunks(&mut world_buffer, &[chunk_pos], &mut encoder)
+        .expect("GPU generation should dispatch");
+    queue.submit(Some(encoder.finish()));
+
+    world_buffer
+        .read_chunk_blocking(device, queue, chunk_pos)
+        .expect("chunk readback should succeed")
+        .iter()
+        .map(|voxel| BlockId(voxel.block_id()))
+        .collect()
+}
+
+#[test]
+fn test_cpu_and_gpu_terrain_match() {
+    let Some((device, queue)) = create_device() else {
+        eprintln!("No GPU adapter available, skipping cross-backend determinism test");
+        return;
+    };
+
+    let config = GeneratorConfig {
+        stages: GenerationStages::terrain_only(),
+        ..GeneratorConfig::default()
+    };
+
+    // Chunk y = 1 spans the whole surface band, so stone, grass and air all occur
+    for chunk_pos in [ChunkPos::new(0, 1, 0), ChunkPos::new(-3, 1, 7)] {
+        let cpu = generate_chunk_deterministic(&config, chunk_pos, CHUNK_SIZE, SEED);
+        let gpu = generate_gpu_blocks(&device, &queue, chunk_pos);
+
+        assert_eq!(gpu.len(), cpu.blocks.len());
+        let mismatches = cpu.blocks.iter().zip(&gpu).filter(|(c, g)| c != g).count();
+        assert_eq!(
+            mismatches,
+            0,
+            "chunk {:?}: {} voxels differ (CPU hash {})",
+            chunk_pos,
+            mismatches,
+            hash_chunk_blocks(&cpu)
+        );
+    }
+}
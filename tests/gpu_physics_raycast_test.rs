@@ -0,0 +1,228 @@
+//! GPU ray queries against the world buffer agree with the CPU `cast_ray`
+
+use cgmath::{Point3, Vector3};
+use hearth_engine::constants::core::{CHUNK_SIZE, VOXELS_PER_CHUNK};
+use hearth_engine::memory::{MemoryConfig, MemoryManager};
+use hearth_engine::world::compute::{
+    ray_hit, ray_query, HierarchicalPhysics, SparseVoxelOctree, VoxelBvh,
+};
+use hearth_engine::world::core::{cast_ray, RaycastHit};
+use hearth_engine::world::interfaces::UnifiedInterface;
+use hearth_engine::world::storage::{VoxelData, WorldBuffer, WorldBufferDescriptor};
+use hearth_engine::world::{OperationResult, QueryResult, WorldError, WorldOperation, WorldQuery};
+use hearth_engine::*;
+use std::sync::Arc;
+
+/// `cast_ray` advances in 0.1 steps, so it reports a hit up to one step late
+const CAST_RAY_STEP: f32 = 0.1;
+
+/// Request a device, or `None` when the machine has no usable adapter
+fn create_device() -> Option<(Arc<wgpu::Device>, Arc<wgpu::Queue>)> {
+    pollster::block_on(async {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            })
+            .await?;
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("Raycast Test Device"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::default(),
+                },
+                None,
+            )
+            .await
+            .ok()?;
+        Some((Arc::new(device), Arc::new(queue)))
+    })
+}
+
+/// Stone blocks in otherwise empty space, for the CPU `cast_ray`
+struct StoneWorld {
+    stone: Vec<VoxelPos>,
+}
+
+impl UnifiedInterface for StoneWorld {
+    fn backend_type(&self) -> &str {
+        "CPU"
+    }
+
+    fn supports_capability(&self, _capability: &str) -> bool {
+        false
+    }
+}
+
+impl WorldInterface for StoneWorld {
+    fn get_block(&self, pos: VoxelPos) -> BlockId {
+        if self.stone.contains(&pos) {
+            BlockId::STONE
+        } else {
+            BlockId::AIR
+        }
+    }
+
+    fn set_block(&mut self, _pos: VoxelPos, _block_id: BlockId) -> Result<(), WorldError> {
+        Err(WorldError::OperationFailed {
+            message: "read-only test world".to_string(),
+        })
+    }
+
+    fn get_surface_height(&self, _x: f64, _z: f64) -> i32 {
+        0
+    }
+
+    fn is_chunk_loaded(&self, _chunk_pos: ChunkPos) -> bool {
+        true
+    }
+
+    fn load_chunk(&mut self, _chunk_pos: ChunkPos) -> Result<(), WorldError> {
+        Ok(())
+    }
+
+    fn unload_chunk(&mut self, _chunk_pos: ChunkPos) -> Result<(), WorldError> {
+        Ok(())
+    }
+
+    fn raycast(&self, ray: Ray, max_distance: f32) -> Option<RaycastHit> {
+        cast_ray(self, ray, max_distance)
+    }
+
+    fn query(&self, _query: WorldQuery) -> Result<QueryResult, WorldError> {
+        Err(WorldError::OperationFailed {
+            message: "queries are not supported".to_string(),
+        })
+    }
+
+    fn get_chunks_in_radius(&self, _center: ChunkPos, _radius: u32) -> Vec<ChunkPos> {
+        Vec::new()
+    }
+
+    fn batch_operation(
+        &mut self,
+        _operations: Vec<WorldOperation>,
+    ) -> Result<Vec<OperationResult>, WorldError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Full chunk of voxels with stone at the given world positions
+fn chunk_voxels(chunk_pos: ChunkPos, stone: &[VoxelPos]) -> Vec<VoxelData> {
+    let mut voxels = vec![VoxelData::AIR; VOXELS_PER_CHUNK as usize];
+    for pos in stone {
+        if pos.to_chunk_pos(CHUNK_SIZE) == chunk_pos {
+            let (x, y, z) = pos.to_local_pos(CHUNK_SIZE);
+            let index = x + (y + z * CHUNK_SIZE) * CHUNK_SIZE;
+            voxels[index as usize] = VoxelData::new(BlockId::STONE.0, 0, 0, 0);
+        }
+    }
+    voxels
+}
+
+#[test]
+fn test_gpu_raycast_matches_cast_ray() {
+    let Some((device, queue)) = create_device() else {
+        eprintln!("No GPU adapter available, skipping GPU raycast test");
+        return;
+    };
+
+    let mut world_buffer = WorldBuffer::new(
+        device.clone(),
+        &WorldBufferDescriptor {
+            view_distance: 1,
+            enable_atomics: false,
+            enable_readback: false,
+        },
+    );
+
+    // One stone block on each side of the x = 0 chunk border. An empty chunk
+    // goes up first so neither block's chunk sits in slot 0.
+    let target = VoxelPos::new(10, 5, 10);
+    let negative_target = VoxelPos::new(-3, 5, 10);
+    let stone = [target, negative_target];
+    for chunk_pos in [
+        ChunkPos::new(0, 1, 0),
+        ChunkPos::new(0, 0, 0),
+        ChunkPos::new(-1, 0, 0),
+    ] {
+        world_buffer.upload_chunk(&queue, chunk_pos, &chunk_voxels(chunk_pos, &stone));
+    }
+
+    let mut memory_manager = MemoryManager::new(device.clone(), MemoryConfig::default());
+    let octree = SparseVoxelOctree::new(device.clone(), &mut memory_manager, 64);
+    let bvh = VoxelBvh::new(device.clone(), &mut memory_manager, 16);
+    let physics = HierarchicalPhysics::new(device.clone(), &mut memory_manager, 16);
+
+    let rays = [
+        // Straight at the block along +Z
+        (
+            Ray::new(Point3::new(10.5, 5.5, 2.5), Vector3::new(0.0, 0.0, 1.0)),
+            32.0,
+        ),
+        // Same ray, stopping short of it
+        (
+            Ray::new(Point3::new(10.5, 5.5, 2.5), Vector3::new(0.0, 0.0, 1.0)),
+            4.0,
+        ),
+        // Diagonal ray into the block's top face
+        (
+            Ray::new(Point3::new(8.3, 9.6, 8.4), Vector3::new(0.5, -1.0, 0.5)),
+            32.0,
+        ),
+        // Along -X across the chunk border into negative coordinates
+        (
+            Ray::new(Point3::new(5.5, 5.5, 10.5), Vector3::new(-1.0, 0.0, 0.0)),
+            32.0,
+        ),
+        // Through chunks with no stone in reach
+        (
+            Ray::new(Point3::new(-20.5, 30.5, 10.5), Vector3::new(1.0, 0.0, 0.0)),
+            40.0,
+        ),
+    ];
+    let queries: Vec<_> = rays
+        .iter()
+        .map(|&(ray, max_distance)| ray_query(ray, max_distance))
+        .collect();
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Raycast Test Encoder"),
+    });
+    physics.execute_queries(&queue, &mut encoder, &world_buffer, &octree, &bvh, &queries);
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let results = pollster::block_on(physics.read_results(&device, &queue, queries.len()))
+        .expect("Failed to read ray query results");
+
+    let world = StoneWorld {
+        stone: stone.to_vec(),
+    };
+    for (&(ray, max_distance), gpu) in rays.iter().zip(&results) {
+        let cpu = cast_ray(&world, ray, max_distance);
+        match (ray_hit(gpu), cpu) {
+            (Some((gpu_voxel, gpu_distance)), Some(cpu_hit)) => {
+                assert_eq!(gpu_voxel, cpu_hit.position);
+                assert!(cpu_hit.distance >= gpu_distance - 1e-4);
+                assert!(cpu_hit.distance <= gpu_distance + CAST_RAY_STEP + 1e-4);
+                assert_eq!(gpu.block_id, BlockId::STONE.0 as u32);
+            }
+            (gpu_hit, cpu_hit) => assert_eq!(
+                gpu_hit.map(|(voxel, _)| voxel),
+                cpu_hit.map(|hit| hit.position)
+            ),
+        }
+    }
+
+    assert_eq!(ray_hit(&results[0]), Some((target, 7.5)));
+    assert_eq!(ray_hit(&results[1]), None);
+    assert_eq!(results[2].hit_normal, [0.0, 1.0, 0.0]);
+    assert_eq!(ray_hit(&results[3]), Some((negative_target, 7.5)));
+    assert_eq!(ray_hit(&results[4]), None);
+}